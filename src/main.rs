@@ -1,6 +1,7 @@
 mod wallet_manager;
+use std::process::ExitCode;
 use wallet_manager::wallet_app;
 
-fn main() {
-    wallet_app();
+fn main() -> ExitCode {
+    wallet_app()
 }
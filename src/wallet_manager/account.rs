@@ -0,0 +1,315 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    WalletError,
+    i18n::t,
+    ledger::AccountClass,
+    locale::NumberFormat,
+    money::{Currency, Money},
+};
+
+pub(super) type AccountId = u32;
+
+/// Jenis akun: aset (uang milik sendiri) atau liabilitas (utang, misalnya
+/// kartu kredit). Saldo liabilitas bernilai negatif selama masih ada utang.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum AccountKind {
+    #[default]
+    #[value(alias = "aset", help = t!("help.value.account_kind.asset"))]
+    Asset,
+    #[value(alias = "liabilitas", help = t!("help.value.account_kind.liability"))]
+    Liability,
+}
+
+impl Display for AccountKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        AccountClass::from(*self).fmt(f)
+    }
+}
+
+impl From<AccountKind> for AccountClass {
+    fn from(kind: AccountKind) -> Self {
+        match kind {
+            AccountKind::Asset => AccountClass::Asset,
+            AccountKind::Liability => AccountClass::Liability,
+        }
+    }
+}
+
+/// Pilihan kebijakan cerukan di baris perintah.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(super) enum OverdraftMode {
+    #[value(alias = "larang", help = t!("help.value.overdraft_mode.forbid"))]
+    Forbid,
+    #[value(alias = "konfirmasi", help = t!("help.value.overdraft_mode.confirm"))]
+    Confirm,
+    #[value(alias = "izinkan", help = t!("help.value.overdraft_mode.allow"))]
+    Allow,
+}
+
+/// Kebijakan ketika sebuah perubahan (tambah, ubah atau hapus transaksi)
+/// membuat saldo akun menjadi minus atau makin minus.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub(super) enum OverdraftPolicy {
+    Forbid,
+    Confirm,
+    /// `limit` adalah batas cerukan atau limit kartu kredit; `None` berarti
+    /// tanpa batas.
+    Allow {
+        #[serde(default)]
+        limit: Option<Money>,
+    },
+}
+
+impl OverdraftPolicy {
+    pub(super) fn new(mode: OverdraftMode, limit: Option<Money>) -> Result<Self, WalletError> {
+        match (mode, limit) {
+            (OverdraftMode::Allow, limit) => Ok(OverdraftPolicy::Allow { limit }),
+            (_, Some(_)) => Err(WalletError::InvalidInput(t!("error.limit_without_allow"))),
+            (OverdraftMode::Forbid, None) => Ok(OverdraftPolicy::Forbid),
+            (OverdraftMode::Confirm, None) => Ok(OverdraftPolicy::Confirm),
+        }
+    }
+
+    /// Kebijakan bawaan: aset tidak boleh minus, liabilitas bebas minus
+    /// karena saldo minus berarti utang.
+    fn default_for(kind: AccountKind) -> Self {
+        match kind {
+            AccountKind::Asset => OverdraftPolicy::Forbid,
+            AccountKind::Liability => OverdraftPolicy::Allow { limit: None },
+        }
+    }
+}
+
+impl OverdraftPolicy {
+    /// Keterangan kebijakan; batas cerukan ditulis dalam mata uang akunnya.
+    pub(super) fn describe(&self, currency: Currency, format: &NumberFormat) -> String {
+        match self {
+            OverdraftPolicy::Forbid => t!("overdraft.forbid"),
+            OverdraftPolicy::Confirm => t!("overdraft.confirm_mode"),
+            OverdraftPolicy::Allow { limit: None } => t!("overdraft.allow"),
+            OverdraftPolicy::Allow { limit: Some(limit) } => {
+                t!(
+                    "overdraft.allow_limit",
+                    limit = format.money(*limit, currency)
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Account {
+    pub(super) id: AccountId,
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) kind: AccountKind,
+    /// Kebijakan cerukan yang dipilih pengguna; `None` memakai bawaan jenis akun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) overdraft: Option<OverdraftPolicy>,
+    /// Mata uang saldo akun; semua transaksi akun ini memakai mata uang yang sama.
+    #[serde(default)]
+    pub(super) currency: Currency,
+}
+
+impl Account {
+    pub(super) fn overdraft(&self) -> OverdraftPolicy {
+        self.overdraft
+            .unwrap_or_else(|| OverdraftPolicy::default_for(self.kind))
+    }
+
+    pub(super) fn overdraft_label(&self, format: &NumberFormat) -> String {
+        self.overdraft().describe(self.currency, format)
+    }
+}
+
+/// Daftar akun (dompet tunai, rekening bank, e-wallet, kartu kredit, dsb.).
+/// Setiap transaksi tercatat di salah satu akun.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct AccountList {
+    accounts: Vec<Account>,
+}
+
+impl AccountList {
+    /// Akun bawaan untuk dompet baru dan data dari versi lama.
+    pub(super) fn with_defaults() -> Self {
+        let mut list = Self::default();
+        list.push("Tunai".to_string(), AccountKind::Asset, Currency::IDR);
+        list
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Account> {
+        self.accounts.iter()
+    }
+
+    pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Account> {
+        self.accounts.iter_mut()
+    }
+
+    pub(super) fn get(&self, id: AccountId) -> Option<&Account> {
+        self.accounts.iter().find(|a| a.id == id)
+    }
+
+    pub(super) fn kind(&self, id: AccountId) -> AccountKind {
+        self.get(id).map(|a| a.kind).unwrap_or_default()
+    }
+
+    pub(super) fn currency(&self, id: AccountId) -> Currency {
+        self.get(id).map(|a| a.currency).unwrap_or_default()
+    }
+
+    pub(super) fn name(&self, id: AccountId) -> &str {
+        self.get(id).map(|a| a.name.as_str()).unwrap_or("-")
+    }
+
+    /// Akun pertama, dipakai jika pengguna tidak memilih akun.
+    pub(super) fn default_id(&self) -> AccountId {
+        self.accounts.first().map(|a| a.id).unwrap_or(0)
+    }
+
+    /// Mencari akun berdasarkan nama; huruf besar/kecil diabaikan.
+    pub(super) fn find(&self, name: &str) -> Result<AccountId, WalletError> {
+        self.accounts
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name.trim()))
+            .map(|a| a.id)
+            .ok_or_else(|| {
+                WalletError::InvalidInput(t!("error.account_name_not_found", name = name.trim()))
+            })
+    }
+
+    pub(super) fn add(
+        &mut self,
+        name: String,
+        kind: AccountKind,
+        currency: Currency,
+    ) -> Result<AccountId, WalletError> {
+        let name = Self::validate_name(name)?;
+        if self.find(&name).is_ok() {
+            return Err(WalletError::InvalidInput(t!(
+                "error.account_exists",
+                name = name
+            )));
+        }
+        Ok(self.push(name, kind, currency))
+    }
+
+    pub(super) fn set_overdraft(
+        &mut self,
+        id: AccountId,
+        policy: OverdraftPolicy,
+    ) -> Result<(), WalletError> {
+        let account = self
+            .accounts
+            .iter_mut()
+            .find(|a| a.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.account_not_found")))?;
+        account.overdraft = Some(policy);
+        Ok(())
+    }
+
+    pub(super) fn rename(&mut self, id: AccountId, name: String) -> Result<(), WalletError> {
+        let name = Self::validate_name(name)?;
+        if self.find(&name).is_ok_and(|other| other != id) {
+            return Err(WalletError::InvalidInput(t!(
+                "error.account_exists",
+                name = name
+            )));
+        }
+
+        let account = self
+            .accounts
+            .iter_mut()
+            .find(|a| a.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.account_not_found")))?;
+        account.name = name;
+        Ok(())
+    }
+
+    /// Menghapus akun. Pemanggil bertanggung jawab memastikan akun tidak
+    /// lagi dipakai transaksi.
+    pub(super) fn remove(&mut self, id: AccountId) -> Result<Account, WalletError> {
+        if self.accounts.len() == 1 {
+            return Err(WalletError::InvalidInput(t!("error.last_account")));
+        }
+
+        let position = self
+            .accounts
+            .iter()
+            .position(|a| a.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.account_not_found")))?;
+        Ok(self.accounts.remove(position))
+    }
+
+    fn push(&mut self, name: String, kind: AccountKind, currency: Currency) -> AccountId {
+        let id = self.accounts.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+        self.accounts.push(Account {
+            id,
+            name,
+            kind,
+            overdraft: None,
+            currency,
+        });
+        id
+    }
+
+    fn validate_name(name: String) -> Result<String, WalletError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(WalletError::InvalidInput(t!("error.account_name_empty")));
+        }
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_names_are_unique_ignoring_case() {
+        let mut list = AccountList::with_defaults();
+        let cash = list.default_id();
+        let bank = list
+            .add(" BCA ".to_string(), AccountKind::Asset, Currency::IDR)
+            .unwrap();
+        assert_eq!(list.find("bca").unwrap(), bank);
+        assert_eq!(list.name(bank), "BCA");
+        assert!(
+            list.add("bca".to_string(), AccountKind::Asset, Currency::IDR)
+                .is_err()
+        );
+        assert!(list.rename(cash, "Bca".to_string()).is_err());
+        list.rename(bank, "bca".to_string()).unwrap();
+    }
+
+    #[test]
+    fn last_account_cannot_be_removed() {
+        let mut list = AccountList::with_defaults();
+        let card = list
+            .add(
+                "Kartu Kredit".to_string(),
+                AccountKind::Liability,
+                Currency::IDR,
+            )
+            .unwrap();
+        assert_eq!(list.kind(card), AccountKind::Liability);
+        list.remove(card).unwrap();
+        assert!(list.remove(list.default_id()).is_err());
+        assert_eq!(list.len(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local, NaiveDate};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{TransactionId, WalletError, history::Revision, i18n::t, storage::WalletData};
+
+/// Bagian `WalletData` yang tidak dicatat sebagai perubahan tersendiri:
+/// transaksi dicatat per revisi, sedangkan riwayat batalkan serta penghitung
+/// ID dan peristiwa hanya pembukuan internal.
+const UNTRACKED_SECTIONS: &[&str] = &[
+    "items",
+    "history",
+    "next_id",
+    "next_schedule_id",
+    "event_seq",
+];
+
+/// Jenis perubahan yang dicatat di log audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum AuditAction {
+    #[value(alias = "tambah", help = t!("help.value.audit_action.add"))]
+    Add,
+    #[value(alias = "ubah", help = t!("help.value.audit_action.update"))]
+    Update,
+    #[value(alias = "hapus", help = t!("help.value.audit_action.delete"))]
+    Delete,
+    #[value(alias = "impor", help = t!("help.value.audit_action.import"))]
+    Import,
+    #[value(alias = "batal", help = t!("help.value.audit_action.undo"))]
+    Undo,
+    #[value(alias = "ulangi", help = t!("help.value.audit_action.redo"))]
+    Redo,
+    #[value(alias = "berulang", help = t!("help.value.audit_action.recurring"))]
+    Recurring,
+    #[value(alias = "akun", help = t!("help.value.audit_action.account"))]
+    Account,
+    #[value(alias = "kategori", help = t!("help.value.audit_action.category"))]
+    Category,
+    #[value(alias = "kurs", help = t!("help.value.audit_action.rate"))]
+    Rate,
+    #[value(alias = "anggaran", help = t!("help.value.audit_action.budget"))]
+    Budget,
+    #[value(alias = "tujuan", help = t!("help.value.audit_action.goal"))]
+    Goal,
+    #[value(alias = "pengaturan", help = t!("help.value.audit_action.settings"))]
+    Settings,
+    #[value(alias = "bangun-ulang", help = t!("help.value.audit_action.rebuild"))]
+    Rebuild,
+}
+
+impl AuditAction {
+    /// Kode stabil untuk keluaran mesin.
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            AuditAction::Add => "add",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Import => "import",
+            AuditAction::Undo => "undo",
+            AuditAction::Redo => "redo",
+            AuditAction::Recurring => "recurring",
+            AuditAction::Account => "account",
+            AuditAction::Category => "category",
+            AuditAction::Rate => "rate",
+            AuditAction::Budget => "budget",
+            AuditAction::Goal => "goal",
+            AuditAction::Settings => "settings",
+            AuditAction::Rebuild => "rebuild",
+        }
+    }
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&match self {
+            AuditAction::Add => t!("audit.action_add"),
+            AuditAction::Update => t!("audit.action_update"),
+            AuditAction::Delete => t!("audit.action_delete"),
+            AuditAction::Import => t!("audit.action_import"),
+            AuditAction::Undo => t!("audit.action_undo"),
+            AuditAction::Redo => t!("audit.action_redo"),
+            AuditAction::Recurring => t!("audit.action_recurring"),
+            AuditAction::Account => t!("audit.action_account"),
+            AuditAction::Category => t!("audit.action_category"),
+            AuditAction::Rate => t!("audit.action_rate"),
+            AuditAction::Budget => t!("audit.action_budget"),
+            AuditAction::Goal => t!("audit.action_goal"),
+            AuditAction::Settings => t!("audit.action_settings"),
+            AuditAction::Rebuild => t!("audit.action_rebuild"),
+        })
+    }
+}
+
+/// Satu entri log audit. Entri yang sudah ditulis tidak pernah diubah atau
+/// dihapus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct AuditEntry {
+    pub(super) at: DateTime<Local>,
+    /// Siapa yang melakukan perubahan; lihat `actor`.
+    pub(super) actor: String,
+    pub(super) action: AuditAction,
+    /// Transaksi yang berubah beserta nilai sebelum dan sesudahnya.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) revisions: Vec<Revision>,
+    /// Bagian data lain yang berubah, misalnya `accounts` atau `budgets`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) sections: Vec<String>,
+}
+
+impl AuditEntry {
+    /// Entri untuk perubahan dari `before` ke `after`, atau `None` jika
+    /// tidak ada yang berubah. `revisions` adalah hasil `changed_items` untuk
+    /// transaksi kedua keadaan itu.
+    pub(super) fn new(
+        action: AuditAction,
+        revisions: Vec<Revision>,
+        before: &WalletData,
+        after: &WalletData,
+    ) -> Option<Self> {
+        let sections = changed_sections(before, after);
+        if revisions.is_empty() && sections.is_empty() {
+            return None;
+        }
+        Some(Self {
+            at: Local::now(),
+            actor: actor(),
+            action,
+            revisions,
+            sections,
+        })
+    }
+
+    pub(super) fn involves(&self, id: TransactionId) -> bool {
+        self.revisions.iter().any(|revision| revision.id == id)
+    }
+}
+
+/// Nama pelaku perubahan: `WALLET_ACTOR`, lalu nama pengguna sistem operasi.
+fn actor() -> String {
+    ["WALLET_ACTOR", "USER", "USERNAME"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("data dompet selalu valid sebagai JSON")
+}
+
+/// Nama bagian `WalletData` selain transaksi yang isinya berubah.
+fn changed_sections(before: &WalletData, after: &WalletData) -> Vec<String> {
+    let (Value::Object(before), Value::Object(after)) = (to_value(before), to_value(after)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| !UNTRACKED_SECTIONS.contains(&name.as_str()))
+        .filter(|name| before.get(*name) != after.get(*name))
+        .cloned()
+        .collect()
+}
+
+/// Kriteria untuk menelusuri log audit. Field kosong berarti tanpa batasan.
+#[derive(Debug, Default)]
+pub(super) struct AuditFilter {
+    /// Nama pelaku, tanpa membedakan huruf besar/kecil.
+    pub(super) actor: Option<String>,
+    pub(super) action: Option<AuditAction>,
+    /// Hanya entri yang mengubah transaksi ini.
+    pub(super) transaction: Option<TransactionId>,
+    /// Rentang tanggal entri, keduanya ikut dihitung.
+    pub(super) from: Option<NaiveDate>,
+    pub(super) to: Option<NaiveDate>,
+}
+
+impl AuditFilter {
+    pub(super) fn matches(&self, entry: &AuditEntry) -> bool {
+        let date = entry.at.date_naive();
+        self.actor
+            .as_ref()
+            .is_none_or(|actor| entry.actor.eq_ignore_ascii_case(actor.trim()))
+            && self.action.is_none_or(|action| entry.action == action)
+            && self.transaction.is_none_or(|id| entry.involves(id))
+            && self.from.is_none_or(|from| date >= from)
+            && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// Membaca satu baris log audit berformat JSON Lines.
+pub(super) fn parse_line(line: &str, number: usize) -> Result<AuditEntry, WalletError> {
+    serde_json::from_str(line)
+        .map_err(|e| WalletError::Storage(t!("error.corrupt_audit", line = number, error = e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_list_changed_sections_only() {
+        let before = WalletData::default();
+        assert!(AuditEntry::new(AuditAction::Budget, Vec::new(), &before, &before).is_none());
+
+        let mut after = before.clone();
+        after.next_id = 9;
+        assert!(AuditEntry::new(AuditAction::Add, Vec::new(), &before, &after).is_none());
+
+        after.budgets.set_threshold(90).unwrap();
+        let entry = AuditEntry::new(AuditAction::Budget, Vec::new(), &before, &after).unwrap();
+        assert_eq!(entry.sections, ["budgets"]);
+        assert!(!entry.actor.is_empty());
+    }
+
+    #[test]
+    fn filter_matches_every_given_criterion() {
+        let entry = AuditEntry {
+            at: Local::now(),
+            actor: "budi".to_string(),
+            action: AuditAction::Delete,
+            revisions: vec![Revision {
+                id: TransactionId(3),
+                before: None,
+                after: None,
+            }],
+            sections: Vec::new(),
+        };
+        let today = entry.at.date_naive();
+        let filter = AuditFilter {
+            actor: Some(" Budi ".to_string()),
+            action: Some(AuditAction::Delete),
+            transaction: Some(TransactionId(3)),
+            from: Some(today),
+            to: Some(today),
+        };
+        assert!(filter.matches(&entry));
+        assert!(AuditFilter::default().matches(&entry));
+
+        let other = |filter: AuditFilter| !filter.matches(&entry);
+        assert!(other(AuditFilter {
+            actor: Some("sari".to_string()),
+            ..AuditFilter::default()
+        }));
+        assert!(other(AuditFilter {
+            action: Some(AuditAction::Add),
+            ..AuditFilter::default()
+        }));
+        assert!(other(AuditFilter {
+            transaction: Some(TransactionId(4)),
+            ..AuditFilter::default()
+        }));
+        assert!(other(AuditFilter {
+            from: today.succ_opt(),
+            ..AuditFilter::default()
+        }));
+    }
+}
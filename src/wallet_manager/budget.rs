@@ -0,0 +1,215 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    WalletError,
+    category::CategoryId,
+    i18n::t,
+    money::{Currency, Money},
+};
+
+/// Batas peringatan bawaan, dalam persen dari anggaran.
+const DEFAULT_THRESHOLD: u8 = 80;
+
+fn default_threshold() -> u8 {
+    DEFAULT_THRESHOLD
+}
+
+/// Anggaran bulanan satu kategori. Anggaran kategori utama ikut menghitung
+/// pengeluaran sub-kategorinya.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Budget {
+    pub(super) category: CategoryId,
+    pub(super) limit: Money,
+    /// Mata uang anggaran; pengeluaran dikonversi ke mata uang ini.
+    pub(super) currency: Currency,
+}
+
+/// Seluruh anggaran beserta batas peringatannya.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BudgetList {
+    /// Peringatan dicetak saat pemakaian mencapai persen ini.
+    #[serde(default = "default_threshold")]
+    threshold: u8,
+    #[serde(default)]
+    budgets: Vec<Budget>,
+}
+
+impl Default for BudgetList {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            budgets: Vec::new(),
+        }
+    }
+}
+
+impl BudgetList {
+    pub(super) fn is_empty(&self) -> bool {
+        self.budgets.is_empty() && self.threshold == DEFAULT_THRESHOLD
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Budget> {
+        self.budgets.iter()
+    }
+
+    pub(super) fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub(super) fn set_threshold(&mut self, percent: u8) -> Result<(), WalletError> {
+        if !(1..=100).contains(&percent) {
+            return Err(WalletError::InvalidInput(t!(
+                "error.budget_threshold",
+                percent = percent
+            )));
+        }
+        self.threshold = percent;
+        Ok(())
+    }
+
+    /// Menambah atau mengganti anggaran sebuah kategori. Mengembalikan
+    /// `true` jika anggaran lama diganti.
+    pub(super) fn set(&mut self, budget: Budget) -> Result<bool, WalletError> {
+        if !budget.limit.is_positive() {
+            return Err(WalletError::InvalidInput(t!("error.budget_not_positive")));
+        }
+        match self
+            .budgets
+            .iter_mut()
+            .find(|b| b.category == budget.category)
+        {
+            Some(existing) => {
+                *existing = budget;
+                Ok(true)
+            }
+            None => {
+                self.budgets.push(budget);
+                Ok(false)
+            }
+        }
+    }
+
+    pub(super) fn remove(&mut self, category: CategoryId) -> Option<Budget> {
+        let position = self.budgets.iter().position(|b| b.category == category)?;
+        Some(self.budgets.remove(position))
+    }
+}
+
+/// Keadaan anggaran berdasarkan persentase pemakaian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum BudgetLevel {
+    Safe,
+    /// Pemakaian sudah mencapai batas peringatan.
+    Warning,
+    /// Pengeluaran melebihi anggaran.
+    Over,
+}
+
+impl BudgetLevel {
+    /// Kode stabil untuk keluaran mesin.
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            BudgetLevel::Safe => "ok",
+            BudgetLevel::Warning => "warning",
+            BudgetLevel::Over => "over",
+        }
+    }
+}
+
+impl Display for BudgetLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetLevel::Safe => f.pad(&t!("budget.level_safe")),
+            BudgetLevel::Warning => f.pad(&t!("budget.level_warning")),
+            BudgetLevel::Over => f.pad(&t!("budget.level_over")),
+        }
+    }
+}
+
+/// Pemakaian satu anggaran dalam satu bulan.
+#[derive(Debug, Clone)]
+pub(super) struct BudgetStatus {
+    pub(super) category: CategoryId,
+    pub(super) limit: Money,
+    pub(super) spent: Money,
+    pub(super) currency: Currency,
+}
+
+impl BudgetStatus {
+    pub(super) fn remaining(&self) -> Result<Money, WalletError> {
+        self.limit.checked_sub(self.spent)
+    }
+
+    /// Bagian anggaran yang sudah terpakai, dalam persen; bisa lebih dari 100.
+    pub(super) fn percent(&self) -> f64 {
+        self.spent.minor() as f64 / self.limit.minor() as f64 * 100.0
+    }
+
+    pub(super) fn level(&self, threshold: u8) -> BudgetLevel {
+        if self.spent > self.limit {
+            BudgetLevel::Over
+        } else if self.percent() >= f64::from(threshold) {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Safe
+        }
+    }
+}
+
+/// Bilah kemajuan selebar `width` karakter, misalnya "███████░░░" untuk
+/// 70%. Pemakaian di atas 100% digambar penuh.
+pub(super) fn progress_bar(percent: f64, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(spent: i64) -> BudgetStatus {
+        BudgetStatus {
+            category: 1,
+            limit: Money::from_minor(1_000),
+            spent: Money::from_minor(spent),
+            currency: Currency::IDR,
+        }
+    }
+
+    #[test]
+    fn level_follows_the_threshold() {
+        assert_eq!(status(790).level(80), BudgetLevel::Safe);
+        assert_eq!(status(800).level(80), BudgetLevel::Warning);
+        assert_eq!(status(1_000).level(80), BudgetLevel::Warning);
+        assert_eq!(status(1_001).level(80), BudgetLevel::Over);
+        assert_eq!(status(1_250).percent(), 125.0);
+        assert_eq!(status(1_250).remaining().unwrap(), Money::from_minor(-250));
+    }
+
+    #[test]
+    fn budgets_are_replaced_per_category() {
+        let mut list = BudgetList::default();
+        let budget = |limit| Budget {
+            category: 1,
+            limit: Money::from_minor(limit),
+            currency: Currency::IDR,
+        };
+        assert!(!list.set(budget(1_000)).unwrap());
+        assert!(list.set(budget(2_000)).unwrap());
+        assert!(list.set(budget(0)).is_err());
+        assert_eq!(list.iter().count(), 1);
+        assert!(list.set_threshold(0).is_err());
+        assert!(list.set_threshold(101).is_err());
+        list.set_threshold(90).unwrap();
+        assert_eq!(list.threshold(), 90);
+    }
+
+    #[test]
+    fn progress_bar_is_capped() {
+        assert_eq!(progress_bar(70.0, 10), "███████░░░");
+        assert_eq!(progress_bar(150.0, 4), "████");
+        assert_eq!(progress_bar(-5.0, 4), "░░░░");
+    }
+}
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use super::{WalletError, i18n::t};
+
+pub(super) type CategoryId = u32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Category {
+    pub(super) id: CategoryId,
+    pub(super) name: String,
+    /// `None` untuk kategori utama, selain itu ID kategori induk.
+    pub(super) parent: Option<CategoryId>,
+}
+
+/// Daftar kategori dua tingkat (kategori utama > sub-kategori).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct CategoryList {
+    categories: Vec<Category>,
+}
+
+impl CategoryList {
+    /// Kategori bawaan untuk dompet baru.
+    pub(super) fn with_defaults() -> Self {
+        let defaults: [(&str, &[&str]); 7] = [
+            ("Makanan", &["Makan Siang", "Makan Malam", "Jajan"]),
+            ("Transportasi", &["Ojol", "Bensin", "Parkir"]),
+            ("Tagihan", &["Listrik", "Internet", "Pulsa"]),
+            ("Belanja", &[]),
+            ("Hiburan", &[]),
+            ("Gaji", &[]),
+            ("Lainnya", &[]),
+        ];
+
+        let mut list = Self::default();
+        for (name, children) in defaults {
+            let parent = list.push(name.to_string(), None);
+            for child in children {
+                list.push(child.to_string(), Some(parent));
+            }
+        }
+        list
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    pub(super) fn get(&self, id: CategoryId) -> Option<&Category> {
+        self.categories.iter().find(|c| c.id == id)
+    }
+
+    /// Nama lengkap kategori, misalnya "Makanan > Makan Siang".
+    pub(super) fn path(&self, id: CategoryId) -> Option<String> {
+        let category = self.get(id)?;
+        match category.parent.and_then(|p| self.get(p)) {
+            Some(parent) => Some(format!("{} > {}", parent.name, category.name)),
+            None => Some(category.name.clone()),
+        }
+    }
+
+    /// ID kategori utama dari sebuah kategori (dirinya sendiri jika sudah utama).
+    pub(super) fn root_of(&self, id: CategoryId) -> CategoryId {
+        self.get(id).and_then(|c| c.parent).unwrap_or(id)
+    }
+
+    /// Mencari kategori dari nama lengkap ("Makanan > Makan Siang") atau
+    /// nama saja jika namanya unik. Huruf besar/kecil diabaikan.
+    pub(super) fn find_path(&self, path: &str) -> Result<CategoryId, WalletError> {
+        let parts: Vec<&str> = path.split('>').map(str::trim).collect();
+        let not_found =
+            || WalletError::InvalidInput(t!("error.category_not_found", name = path.trim()));
+
+        match parts.as_slice() {
+            [parent, child] => {
+                let parent = self.find(parent, None).ok_or_else(not_found)?;
+                self.find(child, Some(parent)).ok_or_else(not_found)
+            }
+            [name] => {
+                if let Some(id) = self.find(name, None) {
+                    return Ok(id);
+                }
+                let mut matches = self
+                    .categories
+                    .iter()
+                    .filter(|c| c.name.eq_ignore_ascii_case(name));
+                match (matches.next(), matches.next()) {
+                    (Some(category), None) => Ok(category.id),
+                    (Some(_), Some(_)) => Err(WalletError::InvalidInput(t!(
+                        "error.category_ambiguous",
+                        name = name
+                    ))),
+                    _ => Err(not_found()),
+                }
+            }
+            _ => Err(not_found()),
+        }
+    }
+
+    pub(super) fn children(&self, parent: CategoryId) -> impl Iterator<Item = &Category> {
+        self.categories
+            .iter()
+            .filter(move |c| c.parent == Some(parent))
+    }
+
+    /// Seluruh kategori dalam urutan pohon: setiap kategori utama diikuti
+    /// sub-kategorinya. Urutan ini dipakai sebagai nomor pilihan di menu.
+    pub(super) fn tree(&self) -> Vec<&Category> {
+        let mut result = Vec::with_capacity(self.categories.len());
+        for root in self.categories.iter().filter(|c| c.parent.is_none()) {
+            result.push(root);
+            result.extend(self.children(root.id));
+        }
+        result
+    }
+
+    pub(super) fn add(
+        &mut self,
+        name: String,
+        parent: Option<CategoryId>,
+    ) -> Result<CategoryId, WalletError> {
+        let name = Self::validate_name(name)?;
+
+        if let Some(parent_id) = parent {
+            match self.get(parent_id) {
+                None => {
+                    return Err(WalletError::InvalidInput(t!("error.parent_not_found")));
+                }
+                Some(p) if p.parent.is_some() => {
+                    return Err(WalletError::InvalidInput(t!("error.nested_category")));
+                }
+                Some(_) => {}
+            }
+        }
+
+        if self.find(&name, parent).is_some() {
+            return Err(WalletError::InvalidInput(t!(
+                "error.category_exists",
+                name = name
+            )));
+        }
+
+        Ok(self.push(name, parent))
+    }
+
+    pub(super) fn rename(&mut self, id: CategoryId, name: String) -> Result<(), WalletError> {
+        let name = Self::validate_name(name)?;
+        let parent = self
+            .get(id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.category_missing")))?
+            .parent;
+
+        if self.find(&name, parent).is_some_and(|other| other != id) {
+            return Err(WalletError::InvalidInput(t!(
+                "error.category_exists",
+                name = name
+            )));
+        }
+
+        if let Some(category) = self.categories.iter_mut().find(|c| c.id == id) {
+            category.name = name;
+        }
+        Ok(())
+    }
+
+    /// Menghapus kategori. Pemanggil bertanggung jawab memastikan kategori
+    /// tidak lagi dipakai transaksi.
+    pub(super) fn remove(&mut self, id: CategoryId) -> Result<Category, WalletError> {
+        if self.children(id).next().is_some() {
+            return Err(WalletError::InvalidInput(t!("error.category_has_children")));
+        }
+
+        let position = self
+            .categories
+            .iter()
+            .position(|c| c.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.category_missing")))?;
+        Ok(self.categories.remove(position))
+    }
+
+    fn find(&self, name: &str, parent: Option<CategoryId>) -> Option<CategoryId> {
+        self.categories
+            .iter()
+            .find(|c| c.parent == parent && c.name.eq_ignore_ascii_case(name))
+            .map(|c| c.id)
+    }
+
+    fn push(&mut self, name: String, parent: Option<CategoryId>) -> CategoryId {
+        let id = self.categories.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        self.categories.push(Category { id, name, parent });
+        id
+    }
+
+    fn validate_name(name: String) -> Result<String, WalletError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(WalletError::InvalidInput(t!("error.category_name_empty")));
+        }
+        if name.contains('>') {
+            return Err(WalletError::InvalidInput(t!(
+                "error.category_name_separator"
+            )));
+        }
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sub_categories_are_found_by_path_or_unique_name() {
+        let mut list = CategoryList::with_defaults();
+        let food = list.find_path("makanan").unwrap();
+        let lunch = list.find_path("Makanan > makan siang").unwrap();
+        assert_eq!(list.path(lunch).unwrap(), "Makanan > Makan Siang");
+        assert_eq!(list.root_of(lunch), food);
+        assert_eq!(
+            list.find_path("Jajan").unwrap(),
+            list.children(food).last().unwrap().id
+        );
+
+        // Nama yang sama boleh dipakai di induk lain, tetapi tidak lagi unik.
+        let bills = list.find_path("Tagihan").unwrap();
+        list.add("Parkir".to_string(), Some(bills)).unwrap();
+        assert!(list.find_path("Parkir").is_err());
+        assert!(list.find_path("Tagihan > Parkir").is_ok());
+    }
+
+    #[test]
+    fn invalid_categories_are_rejected() {
+        let mut list = CategoryList::with_defaults();
+        let food = list.find_path("Makanan").unwrap();
+        let lunch = list.find_path("Makan Siang").unwrap();
+        assert!(list.add("Nasi".to_string(), Some(lunch)).is_err());
+        assert!(list.add("makan siang".to_string(), Some(food)).is_err());
+        assert!(list.add("A > B".to_string(), None).is_err());
+        assert!(list.add("  ".to_string(), None).is_err());
+        assert!(list.add("Sarapan".to_string(), Some(99)).is_err());
+    }
+
+    #[test]
+    fn parent_with_sub_categories_cannot_be_removed() {
+        let mut list = CategoryList::with_defaults();
+        let transport = list.find_path("Transportasi").unwrap();
+        assert!(list.remove(transport).is_err());
+
+        let children: Vec<_> = list.children(transport).map(|c| c.id).collect();
+        for child in children {
+            list.remove(child).unwrap();
+        }
+        list.remove(transport).unwrap();
+        assert!(list.find_path("Transportasi").is_err());
+    }
+}
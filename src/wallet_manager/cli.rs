@@ -0,0 +1,1849 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+
+use super::{
+    DATE_FORMAT, TransactionId, TypeTransaction, WalletApp, WalletError, WalletItems, WalletList,
+    account::{AccountId, AccountKind, OverdraftMode, OverdraftPolicy},
+    audit::{AuditAction, AuditFilter},
+    duplicate::{DuplicateAction, Resolution},
+    exchange::{ExchangeRate, Rate, read_rates},
+    filter::TransactionFilter,
+    goal::{Goal, GoalLink},
+    i18n::{Language, t},
+    import::{Column, ColumnMapping, ImportResult, RejectedRow, read_statement},
+    ledger::{LedgerAccount, Posting},
+    locale::{Locale, NegativeStyle, NumberFormat, SymbolPosition},
+    money::Currency,
+    output::{
+        AccountRecord, AuditRecord, BalanceRecord, BudgetRecord, EventRecord, GoalRecord,
+        OccurrenceRecord, OutputFormat, PeriodRecord, RateRecord, ScheduleRecord, SummaryRecord,
+        TagRecord, TransactionRecord, TrialBalanceRecord, outln, write_csv, write_json,
+    },
+    parse_amount, parse_date, parse_description,
+    period::{DateRange, Period, parse_month},
+    schedule::{Frequency, Recurrence},
+    tag::parse_tags,
+    today,
+};
+
+/// Baris perintah `wallet`. Semua teks bantuan diambil dari katalog bahasa
+/// saat `Cli::command()` dibangun, jadi bahasa harus dipilih lebih dulu.
+#[derive(Parser)]
+#[command(name = "wallet", version, about = t!("help.about"))]
+pub(super) struct Cli {
+    #[command(subcommand)]
+    pub(super) command: Option<Command>,
+    #[arg(long, global = true, value_enum, default_value_t, help = t!("help.format"))]
+    pub(super) format: OutputFormat,
+    #[arg(long, global = true, help = t!("help.allow_overdraft"))]
+    pub(super) allow_overdraft: bool,
+    #[arg(long, global = true, value_enum, ignore_case = true, help = t!("help.lang"))]
+    pub(super) lang: Option<Language>,
+}
+
+impl Cli {
+    /// Seperti `Cli::parse`, tetapi judul bagian, opsi `--help`/`--version`
+    /// dan sub-perintah `help` bawaan clap ikut memakai bahasa aktif.
+    pub(super) fn parse_localized() -> Self {
+        let mut command = Cli::command();
+        command.build();
+        let mut command = localize_help(command);
+        let matches = command.get_matches_mut();
+        Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.format(&mut command).exit())
+    }
+
+    /// Bahasa dari opsi `--lang` sebelum baris perintah diurai, agar teks
+    /// bantuan sudah memakai bahasa itu. Nilai yang tidak dikenal diabaikan
+    /// di sini dan dilaporkan saat penguraian.
+    pub(super) fn requested_language() -> Option<Language> {
+        let mut args = std::env::args_os().skip(1);
+        while let Some(arg) = args.next() {
+            let value = match arg.to_str() {
+                Some("--") => break,
+                Some("--lang") => args.next(),
+                Some(arg) => match arg.strip_prefix("--lang=") {
+                    Some(value) => Some(value.into()),
+                    None => continue,
+                },
+                None => continue,
+            };
+            return value
+                .as_ref()
+                .and_then(|value| value.to_str())
+                .and_then(|value| Language::from_str(value, true).ok());
+        }
+        None
+    }
+}
+
+/// Menerjemahkan bagian bantuan yang dibuat clap sendiri pada `command`
+/// yang sudah di-`build`, beserta semua sub-perintahnya. Label lain dari
+/// clap (misalnya "Possible values" dan pesan galat penguraian) tetap
+/// berbahasa Inggris.
+fn localize_help(command: clap::Command) -> clap::Command {
+    let has_arg = |id: &str| command.get_arguments().any(|arg| arg.get_id() == id);
+    let (has_help, has_version) = (has_arg("help"), has_arg("version"));
+    let mut command = command
+        .help_template(format!(
+            "{{before-help}}{{about-with-newline}}\n{} {{usage}}\n\n{{all-args}}{{after-help}}",
+            t!("help.usage")
+        ))
+        .subcommand_help_heading(t!("help.heading_commands"))
+        .mut_args(|arg| {
+            if arg.get_help_heading().is_some() {
+                arg
+            } else if arg.is_positional() {
+                arg.help_heading(t!("help.heading_arguments"))
+            } else {
+                arg.help_heading(t!("help.heading_options"))
+            }
+        })
+        .mut_subcommands(localize_help);
+    if has_help {
+        command = command.mut_arg("help", |arg| match arg.get_long_help() {
+            Some(_) => arg
+                .help(t!("help.flag_help_short"))
+                .long_help(t!("help.flag_help_long")),
+            None => arg.help(t!("help.flag_help")),
+        });
+    }
+    if has_version {
+        command = command.mut_arg("version", |arg| arg.help(t!("help.flag_version")));
+    }
+    if command.get_name() == "help" {
+        command = command.about(t!("help.help_command"));
+    }
+    command
+}
+
+#[derive(Subcommand)]
+pub(super) enum Command {
+    #[command(about = t!("help.add"))]
+    Add(AddArgs),
+    #[command(about = t!("help.update"))]
+    Update(UpdateArgs),
+    #[command(about = t!("help.list"))]
+    List(ListArgs),
+    #[command(about = t!("help.balance"))]
+    Balance {
+        #[arg(long, help = t!("help.arg.balance_account"))]
+        account: Option<String>,
+        #[arg(long, help = t!("help.balance.as_of"))]
+        as_of: Option<String>,
+    },
+    #[command(about = t!("help.accounts"))]
+    Accounts {
+        #[command(subcommand)]
+        action: Option<AccountAction>,
+    },
+    #[command(about = t!("help.transfer"))]
+    Transfer(TransferArgs),
+    #[command(about = t!("help.journal"))]
+    Journal(JournalArgs),
+    #[command(about = t!("help.trial_balance"))]
+    TrialBalance,
+    #[command(about = t!("help.summary"))]
+    Summary,
+    #[command(alias = "laporan", about = t!("help.report"))]
+    Report(ReportArgs),
+    #[command(alias = "anggaran", about = t!("help.budget"))]
+    Budget(BudgetArgs),
+    #[command(alias = "berulang", about = t!("help.recurring"))]
+    Recurring {
+        #[command(subcommand)]
+        action: Option<RecurringAction>,
+    },
+    #[command(alias = "tujuan", about = t!("help.goal"))]
+    Goal {
+        #[command(subcommand)]
+        action: Option<GoalAction>,
+    },
+    #[command(about = t!("help.delete"))]
+    Delete { id: String },
+    #[command(alias = "batal", about = t!("help.undo"))]
+    Undo {
+        #[arg(long, help = t!("help.undo.dry_run"))]
+        dry_run: bool,
+    },
+    #[command(alias = "ulangi", about = t!("help.redo"))]
+    Redo {
+        #[arg(long, help = t!("help.redo.dry_run"))]
+        dry_run: bool,
+    },
+    #[command(about = t!("help.audit"))]
+    Audit(AuditArgs),
+    #[command(alias = "peristiwa", about = t!("help.events"))]
+    Events(EventArgs),
+    #[command(about = t!("help.search"))]
+    Search { keyword: String },
+    #[command(about = t!("help.tags"))]
+    Tags,
+    #[command(about = t!("help.import"))]
+    Import(ImportArgs),
+    #[command(alias = "kurs", about = t!("help.rates"))]
+    Rates {
+        #[command(subcommand)]
+        action: Option<RateAction>,
+    },
+    #[command(alias = "tampilan", about = t!("help.display"))]
+    Display(DisplayArgs),
+}
+
+#[derive(Subcommand)]
+pub(super) enum RateAction {
+    #[command(about = t!("help.rates.set"))]
+    Set {
+        from: Currency,
+        to: Currency,
+        #[arg(help = t!("help.rates.set.rate"))]
+        rate: Rate,
+        #[arg(long, help = t!("help.arg.rate_date"))]
+        date: Option<String>,
+    },
+    #[command(about = t!("help.rates.remove"))]
+    Remove {
+        from: Currency,
+        to: Currency,
+        #[arg(long, help = t!("help.arg.rate_date"))]
+        date: Option<String>,
+    },
+    #[command(about = t!("help.rates.import"))]
+    Import {
+        file: PathBuf,
+        #[arg(long, help = t!("help.arg.dry_run"))]
+        dry_run: bool,
+    },
+    #[command(about = t!("help.rates.report"))]
+    Report { currency: Currency },
+}
+
+/// Tanpa opsi, pengaturan yang berlaku ditampilkan beserta contohnya.
+#[derive(Args)]
+pub(super) struct DisplayArgs {
+    #[arg(long, value_enum, ignore_case = true, help = t!("help.display.language"))]
+    language: Option<Language>,
+    #[arg(long, value_enum, ignore_case = true, help = t!("help.display.locale"))]
+    locale: Option<Locale>,
+    #[arg(long, value_enum, help = t!("help.display.symbol"))]
+    symbol: Option<SymbolPosition>,
+    #[arg(long, value_enum, help = t!("help.display.negative"))]
+    negative: Option<NegativeStyle>,
+    #[arg(long, help = t!("help.display.compact"))]
+    compact: Option<bool>,
+}
+
+#[derive(Args)]
+pub(super) struct ReportArgs {
+    #[arg(long, value_enum, default_value_t, help = t!("help.report.by"))]
+    by: Period,
+    #[arg(long, help = t!("help.arg.from"))]
+    from: Option<String>,
+    #[arg(long, help = t!("help.arg.to"))]
+    to: Option<String>,
+}
+
+#[derive(Args)]
+pub(super) struct AuditArgs {
+    #[arg(long, help = t!("help.audit.actor"))]
+    actor: Option<String>,
+    #[arg(long, value_enum, ignore_case = true, help = t!("help.audit.action"))]
+    action: Option<AuditAction>,
+    #[arg(long, help = t!("help.audit.id"))]
+    id: Option<TransactionId>,
+    #[arg(long, help = t!("help.arg.from"))]
+    from: Option<String>,
+    #[arg(long, help = t!("help.arg.to"))]
+    to: Option<String>,
+    #[arg(long, help = t!("help.audit.limit"))]
+    limit: Option<usize>,
+}
+
+/// Tanpa sub-perintah, peristiwa yang tercatat ditampilkan.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(super) struct EventArgs {
+    #[command(subcommand)]
+    action: Option<EventAction>,
+    #[arg(long, help = t!("help.events.limit"))]
+    limit: Option<usize>,
+}
+
+#[derive(Subcommand)]
+pub(super) enum EventAction {
+    #[command(about = t!("help.events.balance"))]
+    Balance {
+        #[arg(long, help = t!("help.events.balance.at"))]
+        at: String,
+        #[arg(long, help = t!("help.arg.balance_account"))]
+        account: Option<String>,
+    },
+    #[command(about = t!("help.events.rebuild"))]
+    Rebuild {
+        #[arg(long, help = t!("help.events.rebuild.dry_run"))]
+        dry_run: bool,
+    },
+}
+
+/// Tanpa sub-perintah, pemakaian setiap anggaran ditampilkan.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(super) struct BudgetArgs {
+    #[command(subcommand)]
+    action: Option<BudgetAction>,
+    #[arg(long, help = t!("help.budget.month"))]
+    month: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub(super) enum BudgetAction {
+    #[command(about = t!("help.budget.set"))]
+    Set {
+        #[arg(help = t!("help.budget.set.category"))]
+        category: String,
+        amount: String,
+    },
+    #[command(about = t!("help.budget.remove"))]
+    Remove { category: String },
+    #[command(about = t!("help.budget.threshold"))]
+    Threshold { percent: u8 },
+}
+
+#[derive(Subcommand)]
+pub(super) enum RecurringAction {
+    #[command(about = t!("help.recurring.add"))]
+    Add(RecurringArgs),
+    #[command(about = t!("help.recurring.remove"))]
+    Remove { id: String },
+    #[command(about = t!("help.recurring.upcoming"))]
+    Upcoming {
+        #[arg(long, default_value_t = super::UPCOMING_DAYS, help = t!("help.recurring.upcoming.days"))]
+        days: u32,
+    },
+    #[command(about = t!("help.recurring.post"))]
+    Post {
+        #[arg(long, help = t!("help.recurring.post.dry_run"))]
+        dry_run: bool,
+    },
+    #[command(about = t!("help.recurring.skip"))]
+    Skip { id: String },
+}
+
+#[derive(Subcommand)]
+pub(super) enum GoalAction {
+    #[command(about = t!("help.goal.add"))]
+    Add {
+        name: String,
+        #[arg(long, help = t!("help.goal.add.target"))]
+        target: String,
+        #[arg(long, help = t!("help.goal.add.deadline"))]
+        deadline: Option<String>,
+        #[arg(long, required_unless_present = "tag", conflicts_with = "tag", help = t!("help.goal.add.account"))]
+        account: Option<String>,
+        #[arg(long, help = t!("help.goal.add.tag"))]
+        tag: Option<String>,
+    },
+    #[command(about = t!("help.goal.show"))]
+    Show { name: String },
+    #[command(about = t!("help.goal.remove"))]
+    Remove { name: String },
+}
+
+#[derive(Args)]
+pub(super) struct RecurringArgs {
+    #[arg(long = "type", value_enum, help = t!("help.arg.transaction_type"))]
+    transaction_type: TypeArg,
+    #[arg(long, help = t!("help.recurring.add.amount"))]
+    amount: String,
+    #[arg(long)]
+    desc: String,
+    #[arg(long, help = t!("help.recurring.add.category"))]
+    category: Option<String>,
+    #[arg(long = "tag", help = t!("help.arg.tags"))]
+    tags: Vec<String>,
+    #[arg(long, help = t!("help.arg.account"))]
+    account: Option<String>,
+    #[arg(long, value_enum, help = t!("help.recurring.add.every"))]
+    every: Frequency,
+    #[arg(long, default_value_t = 1, help = t!("help.recurring.add.interval"))]
+    interval: u32,
+    #[arg(long, help = t!("help.recurring.add.start"))]
+    start: Option<String>,
+    #[arg(long, help = t!("help.recurring.add.end"))]
+    end: Option<String>,
+    #[arg(long, help = t!("help.recurring.add.count"))]
+    count: Option<u32>,
+    #[arg(long, help = t!("help.recurring.add.last_business_day"))]
+    last_business_day: bool,
+}
+
+#[derive(Subcommand)]
+pub(super) enum AccountAction {
+    #[command(about = t!("help.accounts.add"))]
+    Add {
+        name: String,
+        #[arg(long, value_enum, default_value_t, help = t!("help.accounts.add.kind"))]
+        kind: AccountKind,
+        #[arg(long, default_value = "IDR", help = t!("help.accounts.add.currency"))]
+        currency: Currency,
+        #[arg(long, help = t!("help.accounts.add.opening"))]
+        opening: Option<String>,
+        #[arg(long, value_enum, help = t!("help.accounts.add.overdraft"))]
+        overdraft: Option<OverdraftMode>,
+        #[arg(long, help = t!("help.accounts.add.limit"))]
+        limit: Option<String>,
+    },
+    #[command(about = t!("help.accounts.overdraft"))]
+    Overdraft {
+        name: String,
+        #[arg(value_enum)]
+        mode: OverdraftMode,
+        #[arg(long, help = t!("help.accounts.overdraft.limit"))]
+        limit: Option<String>,
+    },
+    #[command(about = t!("help.accounts.rename"))]
+    Rename { name: String, new_name: String },
+    #[command(about = t!("help.accounts.remove"))]
+    Remove { name: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub(super) enum TypeArg {
+    #[value(alias = "pemasukan")]
+    Income,
+    #[value(alias = "pengeluaran")]
+    Expense,
+    Transfer,
+    #[value(alias = "jurnal")]
+    Journal,
+}
+
+impl From<TypeArg> for TypeTransaction {
+    fn from(value: TypeArg) -> Self {
+        match value {
+            TypeArg::Income => TypeTransaction::Income,
+            TypeArg::Expense => TypeTransaction::Expense,
+            TypeArg::Transfer => TypeTransaction::Transfer,
+            TypeArg::Journal => TypeTransaction::Journal,
+        }
+    }
+}
+
+#[derive(Args)]
+pub(super) struct AddArgs {
+    #[arg(long = "type", value_enum, help = t!("help.arg.transaction_type"))]
+    transaction_type: TypeArg,
+    #[arg(long, help = t!("help.add.amount"))]
+    amount: String,
+    #[arg(long, help = t!("help.add.desc"))]
+    desc: String,
+    #[arg(long, help = t!("help.arg.date"))]
+    date: Option<String>,
+    #[arg(long, help = t!("help.add.category"))]
+    category: Option<String>,
+    #[arg(long = "tag", help = t!("help.add.tags"))]
+    tags: Vec<String>,
+    #[arg(long, help = t!("help.arg.account"))]
+    account: Option<String>,
+    #[arg(long, value_enum, default_value_t, help = t!("help.add.on_duplicate"))]
+    on_duplicate: DuplicateAction,
+}
+
+#[derive(Args)]
+pub(super) struct UpdateArgs {
+    #[arg(help = t!("help.update.id"))]
+    id: String,
+    #[arg(long = "type", value_enum)]
+    transaction_type: Option<TypeArg>,
+    #[arg(long)]
+    amount: Option<String>,
+    #[arg(long)]
+    desc: Option<String>,
+    #[arg(long, help = t!("help.update.date"))]
+    date: Option<String>,
+    #[arg(long, help = t!("help.update.category"))]
+    category: Option<String>,
+    #[arg(long = "tag", help = t!("help.update.tags"))]
+    tags: Option<Vec<String>>,
+    #[arg(long, help = t!("help.update.account"))]
+    account: Option<String>,
+    #[arg(long, help = t!("help.update.to"))]
+    to: Option<String>,
+    #[arg(long, help = t!("help.update.to_amount"))]
+    to_amount: Option<String>,
+}
+
+#[derive(Args)]
+pub(super) struct TransferArgs {
+    #[arg(long, help = t!("help.transfer.from"))]
+    from: String,
+    #[arg(long, help = t!("help.transfer.to"))]
+    to: String,
+    #[arg(long, help = t!("help.transfer.amount"))]
+    amount: String,
+    #[arg(long, help = t!("help.transfer.to_amount"))]
+    to_amount: Option<String>,
+    #[arg(long, default_value = "Transfer")]
+    desc: String,
+    #[arg(long, help = t!("help.arg.date"))]
+    date: Option<String>,
+    #[arg(long = "tag", help = t!("help.arg.tags"))]
+    tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub(super) struct JournalArgs {
+    #[arg(long, help = t!("help.journal.debit"))]
+    debit: Vec<String>,
+    #[arg(long, help = t!("help.journal.credit"))]
+    credit: Vec<String>,
+    #[arg(long, help = t!("help.journal.currency"))]
+    currency: Option<Currency>,
+    #[arg(long)]
+    desc: String,
+    #[arg(long, help = t!("help.arg.date"))]
+    date: Option<String>,
+    #[arg(long = "tag", help = t!("help.arg.tags"))]
+    tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub(super) struct ListArgs {
+    #[arg(long = "type", value_enum, help = t!("help.list.transaction_type"))]
+    transaction_type: Option<TypeArg>,
+    #[arg(long = "tag", help = t!("help.list.tags"))]
+    tags: Vec<String>,
+    #[arg(long = "exclude-tag", help = t!("help.list.exclude_tags"))]
+    exclude_tags: Vec<String>,
+    #[arg(long, help = t!("help.list.account"))]
+    account: Option<String>,
+}
+
+#[derive(Args)]
+pub(super) struct ImportArgs {
+    #[arg(help = t!("help.import.file"))]
+    file: PathBuf,
+    #[arg(long, help = t!("help.import.mapping"))]
+    mapping: Option<PathBuf>,
+    #[arg(long, help = t!("help.import.date_col"))]
+    date_col: Option<String>,
+    #[arg(long, help = t!("help.import.desc_col"))]
+    desc_col: Option<String>,
+    #[arg(long, help = t!("help.import.debit_col"))]
+    debit_col: Option<String>,
+    #[arg(long, help = t!("help.import.credit_col"))]
+    credit_col: Option<String>,
+    #[arg(long, help = t!("help.import.amount_col"))]
+    amount_col: Option<String>,
+    #[arg(long, help = t!("help.import.date_format"))]
+    date_format: Option<String>,
+    #[arg(long, help = t!("help.import.delimiter"))]
+    delimiter: Option<char>,
+    #[arg(long, help = t!("help.import.decimal"))]
+    decimal: Option<char>,
+    #[arg(long, help = t!("help.import.no_header"))]
+    no_header: bool,
+    #[arg(long, help = t!("help.import.skip"))]
+    skip: Option<usize>,
+    #[arg(long, help = t!("help.import.category"))]
+    category: Option<String>,
+    #[arg(long = "tag", help = t!("help.import.tags"))]
+    tags: Vec<String>,
+    #[arg(long, help = t!("help.import.account"))]
+    account: Option<String>,
+    #[arg(long, value_enum, default_value_t, help = t!("help.import.on_duplicate"))]
+    on_duplicate: DuplicateAction,
+    #[arg(long, help = t!("help.arg.dry_run"))]
+    dry_run: bool,
+}
+
+impl ImportArgs {
+    fn mapping(&self) -> Result<ColumnMapping, WalletError> {
+        let mut mapping = match &self.mapping {
+            Some(path) => ColumnMapping::load(path)?,
+            None => ColumnMapping::default(),
+        };
+
+        let column = |value: &Option<String>| value.as_deref().map(Column::parse);
+        if self.date_col.is_some() {
+            mapping.date = column(&self.date_col);
+        }
+        if self.desc_col.is_some() {
+            mapping.description = column(&self.desc_col);
+        }
+        if self.debit_col.is_some() {
+            mapping.debit = column(&self.debit_col);
+        }
+        if self.credit_col.is_some() {
+            mapping.credit = column(&self.credit_col);
+        }
+        if self.amount_col.is_some() {
+            mapping.amount = column(&self.amount_col);
+        }
+        if let Some(date_format) = &self.date_format {
+            mapping.date_format = date_format.clone();
+        }
+        if let Some(delimiter) = self.delimiter {
+            mapping.delimiter = delimiter;
+        }
+        if let Some(decimal) = self.decimal {
+            mapping.decimal_separator = decimal;
+        }
+        if self.no_header {
+            mapping.has_header = false;
+        }
+        if let Some(skip) = self.skip {
+            mapping.skip_rows = skip;
+        }
+
+        Ok(mapping)
+    }
+}
+
+impl ListArgs {
+    fn is_filtered(&self) -> bool {
+        self.transaction_type.is_some() || !self.tags.is_empty() || !self.exclude_tags.is_empty()
+    }
+}
+
+impl WalletApp {
+    /// Akun dari opsi `--account`; tanpa opsi dipakai akun pertama.
+    fn account_arg(&self, name: Option<&str>) -> Result<AccountId, WalletError> {
+        match name {
+            Some(name) => self.wallet.accounts().find(name),
+            None => Ok(self.wallet.accounts().default_id()),
+        }
+    }
+
+    /// Melengkapi transfer antar mata uang dengan jumlah yang diterima: dari
+    /// `--to-amount`, atau dihitung dari tabel kurs pada tanggal transfer.
+    fn receiving_arg(
+        &self,
+        transfer: WalletItems,
+        to_amount: Option<&str>,
+    ) -> Result<WalletItems, WalletError> {
+        let to = transfer.to_account.unwrap_or_default();
+        let to_currency = self.wallet.accounts().currency(to);
+        if to_currency == transfer.currency {
+            return match to_amount {
+                Some(_) => Err(WalletError::InvalidInput(t!(
+                    "error.to_amount_same_currency"
+                ))),
+                None => Ok(transfer),
+            };
+        }
+
+        let amount = match to_amount {
+            Some(input) => parse_amount(input, to_currency)?,
+            None => self.wallet.rates().convert(
+                transfer.amount,
+                transfer.currency,
+                to_currency,
+                transfer.date,
+            )?,
+        };
+        Ok(transfer.receiving(amount, to_currency))
+    }
+
+    /// Menjalankan satu perintah non-interaktif. Kesalahan dikembalikan ke
+    /// pemanggil agar bisa dipetakan menjadi kode keluar.
+    pub(super) fn run_command(
+        &mut self,
+        command: Command,
+        format: OutputFormat,
+        allow_overdraft: bool,
+    ) -> Result<(), WalletError> {
+        match command {
+            Command::Add(args) => {
+                let category = args
+                    .category
+                    .as_deref()
+                    .map(|path| self.wallet.categories().find_path(path))
+                    .transpose()?;
+                let date = match args.date.as_deref() {
+                    Some(date) => parse_date(date)?,
+                    None => today(),
+                };
+
+                match args.transaction_type {
+                    TypeArg::Transfer => {
+                        return Err(WalletError::InvalidInput(t!("error.use_transfer_command")));
+                    }
+                    TypeArg::Journal => {
+                        return Err(WalletError::InvalidInput(t!("error.use_journal_command")));
+                    }
+                    TypeArg::Income | TypeArg::Expense => {}
+                }
+
+                let account = self.account_arg(args.account.as_deref())?;
+                let currency = self.wallet.accounts().currency(account);
+                let transaction = WalletItems::new(
+                    parse_description(&args.desc)?,
+                    parse_amount(&args.amount, currency)?,
+                    args.transaction_type.into(),
+                    date,
+                    category,
+                    parse_tags(&args.tags.join(" "))?,
+                    account,
+                )
+                .in_currency(currency);
+
+                match self.resolve_duplicate(&transaction, args.on_duplicate)? {
+                    Resolution::Add => {
+                        let id = self.wallet.add(transaction, allow_overdraft)?;
+                        outln!("   [SUCCESS] {}", t!("cli.added", id = id));
+                        self.print_budget_alerts(id);
+                    }
+                    Resolution::Skip(id) => {
+                        outln!("   [INFO] {}", t!("cli.skipped_duplicate", id = id));
+                    }
+                    Resolution::Merge(id) => {
+                        self.wallet.merge(id, transaction)?;
+                        outln!("   [SUCCESS] {}", t!("add.merged", id = id));
+                    }
+                }
+            }
+            Command::Update(args) => {
+                let id = self.wallet.resolve(&args.id)?;
+                let mut transaction = self.wallet.get(id).cloned().ok_or_else(|| {
+                    WalletError::InvalidInput(t!("error.transaction_not_found", id = id))
+                })?;
+
+                let is_journal = transaction.transaction_type == TypeTransaction::Journal;
+                if matches!(args.transaction_type, Some(TypeArg::Journal))
+                    || (is_journal
+                        && (args.transaction_type.is_some()
+                            || args.amount.is_some()
+                            || args.category.is_some()
+                            || args.account.is_some()
+                            || args.to.is_some()
+                            || args.to_amount.is_some()))
+                {
+                    return Err(WalletError::InvalidInput(t!("error.journal_immutable")));
+                }
+
+                if let Some(transaction_type) = args.transaction_type {
+                    transaction.transaction_type = transaction_type.into();
+                }
+                if let Some(desc) = args.desc.as_deref() {
+                    transaction.description = parse_description(desc)?;
+                }
+                if let Some(date) = args.date.as_deref() {
+                    transaction.date = parse_date(date)?;
+                }
+                match args.category.as_deref() {
+                    Some("-") => transaction.category = None,
+                    Some(path) => {
+                        transaction.category = Some(self.wallet.categories().find_path(path)?)
+                    }
+                    None => {}
+                }
+                if let Some(tags) = args.tags {
+                    transaction.tags = parse_tags(&tags.join(" "))?;
+                }
+                if let Some(account) = args.account.as_deref() {
+                    transaction.account = self.wallet.accounts().find(account)?;
+                }
+                if let Some(to) = args.to.as_deref() {
+                    transaction.to_account = Some(self.wallet.accounts().find(to)?);
+                }
+                // Jumlah dibaca dalam mata uang akun setelah akun (mungkin) dipindah.
+                if let Some(amount) = args.amount.as_deref() {
+                    let currency = self.wallet.accounts().currency(transaction.account);
+                    transaction.amount = parse_amount(amount, currency)?;
+                    transaction.currency = currency;
+                }
+                if transaction.transaction_type == TypeTransaction::Transfer
+                    && (args.amount.is_some()
+                        || args.account.is_some()
+                        || args.to.is_some()
+                        || args.to_amount.is_some())
+                {
+                    transaction = self.receiving_arg(transaction, args.to_amount.as_deref())?;
+                }
+
+                self.wallet.update(id, transaction, allow_overdraft)?;
+                outln!("   [SUCCESS] {}", t!("cli.updated", id = id));
+            }
+            Command::List(args) => {
+                let account = args
+                    .account
+                    .as_deref()
+                    .map(|name| self.wallet.accounts().find(name))
+                    .transpose()?;
+                let filter = TransactionFilter {
+                    transaction_type: args.transaction_type.map(Into::into),
+                    include_tags: parse_tags(&args.tags.join(" "))?,
+                    exclude_tags: parse_tags(&args.exclude_tags.join(" "))?,
+                    account,
+                };
+                match format {
+                    OutputFormat::Text if args.is_filtered() => self.print_filter_result(&filter),
+                    OutputFormat::Text => self.wallet.show(account)?,
+                    _ => self.write_transactions(&self.wallet.filter(&filter), account, format)?,
+                }
+            }
+            Command::Balance { account, as_of } => {
+                let account = account
+                    .as_deref()
+                    .map(|name| self.wallet.accounts().find(name))
+                    .transpose()?;
+                let as_of = as_of.as_deref().map(parse_date).transpose()?;
+                write_balance(&self.wallet, account, as_of, format)?;
+            }
+            Command::Accounts { action } => self.run_accounts(action, format)?,
+            Command::Transfer(args) => {
+                let accounts = self.wallet.accounts();
+                let from = accounts.find(&args.from)?;
+                let currency = accounts.currency(from);
+                let transfer = WalletItems::transfer(
+                    parse_description(&args.desc)?,
+                    parse_amount(&args.amount, currency)?,
+                    match args.date.as_deref() {
+                        Some(date) => parse_date(date)?,
+                        None => today(),
+                    },
+                    from,
+                    accounts.find(&args.to)?,
+                    parse_tags(&args.tags.join(" "))?,
+                )
+                .in_currency(currency);
+                let transfer = self.receiving_arg(transfer, args.to_amount.as_deref())?;
+                let id = self.wallet.add(transfer, allow_overdraft)?;
+                outln!("   [SUCCESS] {}", t!("cli.transfer_recorded", id = id));
+            }
+            Command::Journal(args) => {
+                let mut entries = Vec::new();
+                for (inputs, debit) in [(&args.debit, true), (&args.credit, false)] {
+                    for entry in inputs {
+                        let (account, amount) = entry.rsplit_once('=').ok_or_else(|| {
+                            WalletError::InvalidInput(t!("error.posting_format", posting = entry))
+                        })?;
+                        let account = self.wallet.parse_ledger_account(account)?;
+                        entries.push((account, amount, debit));
+                    }
+                }
+
+                // Jumlah baru bisa dibaca setelah mata uang jurnal diketahui.
+                let currency = args.currency.unwrap_or_else(|| {
+                    entries
+                        .iter()
+                        .find_map(|(account, _, _)| match account {
+                            LedgerAccount::Account(id) => {
+                                Some(self.wallet.accounts().currency(*id))
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or_else(|| self.wallet.reporting_currency())
+                });
+                let mut postings = Vec::new();
+                for (account, amount, debit) in entries {
+                    let amount = parse_amount(amount, currency)?;
+                    postings.push(if debit {
+                        Posting::debit(account, amount, currency)
+                    } else {
+                        Posting::credit(account, amount, currency)
+                    });
+                }
+
+                let journal = WalletItems::journal(
+                    parse_description(&args.desc)?,
+                    match args.date.as_deref() {
+                        Some(date) => parse_date(date)?,
+                        None => today(),
+                    },
+                    postings,
+                    parse_tags(&args.tags.join(" "))?,
+                );
+                let id = self.wallet.add(journal, allow_overdraft)?;
+                outln!("   [SUCCESS] {}", t!("cli.journal_recorded", id = id));
+            }
+            Command::TrialBalance => {
+                let records: Vec<TrialBalanceRecord> = self
+                    .wallet
+                    .trial_balance()?
+                    .iter()
+                    .map(|row| TrialBalanceRecord::new(&self.wallet, row))
+                    .collect();
+                match format {
+                    OutputFormat::Text => self.wallet.show_trial_balance()?,
+                    OutputFormat::Json => write_json(&records),
+                    OutputFormat::Csv => write_csv(&records),
+                }
+            }
+            Command::Summary => match format {
+                OutputFormat::Text => self.show_summary()?,
+                OutputFormat::Json => write_json(&SummaryRecord::new(&self.wallet)?),
+                OutputFormat::Csv => write_csv(&SummaryRecord::new(&self.wallet)?.csv_rows()),
+            },
+            Command::Report(args) => {
+                let range = DateRange::new(
+                    args.from.as_deref().map(parse_date).transpose()?,
+                    args.to.as_deref().map(parse_date).transpose()?,
+                )?;
+                match format {
+                    OutputFormat::Text => self.show_period_report(args.by, range)?,
+                    OutputFormat::Json => {
+                        write_json(&PeriodRecord::all(&self.wallet, args.by, range)?)
+                    }
+                    OutputFormat::Csv => {
+                        write_csv(&PeriodRecord::all(&self.wallet, args.by, range)?)
+                    }
+                }
+            }
+            Command::Budget(args) => self.run_budget(args, format)?,
+            Command::Recurring { action } => self.run_recurring(action, format, allow_overdraft)?,
+            Command::Goal { action } => self.run_goal(action, format)?,
+            Command::Tags => {
+                let currency = self.wallet.reporting_currency();
+                let records = self
+                    .wallet
+                    .tag_report()?
+                    .iter()
+                    .map(|total| TagRecord::new(total, currency, &self.wallet.number_format()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match format {
+                    OutputFormat::Text => self.show_tag_report()?,
+                    OutputFormat::Json => write_json(&records),
+                    OutputFormat::Csv => write_csv(&records),
+                }
+            }
+            Command::Delete { id } => {
+                let id = self.wallet.resolve(&id)?;
+                let removed = self.wallet.remove_transaction(id, allow_overdraft)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!(
+                        "delete.success",
+                        id = removed.id,
+                        description = removed.description
+                    )
+                );
+            }
+            Command::Undo { dry_run } => self.run_history(true, dry_run, allow_overdraft)?,
+            Command::Redo { dry_run } => self.run_history(false, dry_run, allow_overdraft)?,
+            Command::Audit(args) => self.run_audit(args, format)?,
+            Command::Events(args) => self.run_events(args, format)?,
+            Command::Search { keyword } => match format {
+                OutputFormat::Text => self.print_search_result(&keyword),
+                _ => self.write_transactions(
+                    &self.wallet.search_transaction(&keyword),
+                    None,
+                    format,
+                )?,
+            },
+            Command::Import(args) => self.run_import(args, allow_overdraft)?,
+            Command::Rates { action } => self.run_rates(action, format)?,
+            Command::Display(args) => self.run_display(args, format)?,
+        }
+
+        Ok(())
+    }
+
+    fn run_accounts(
+        &mut self,
+        action: Option<AccountAction>,
+        format: OutputFormat,
+    ) -> Result<(), WalletError> {
+        match action {
+            None => match format {
+                OutputFormat::Text => self.wallet.total(None)?,
+                OutputFormat::Json => write_json(&AccountRecord::all(&self.wallet)?),
+                OutputFormat::Csv => write_csv(&AccountRecord::all(&self.wallet)?),
+            },
+            Some(AccountAction::Add {
+                name,
+                kind,
+                currency,
+                opening,
+                overdraft,
+                limit,
+            }) => {
+                let parse = |input: &str| parse_amount(input, currency);
+                let opening = opening.as_deref().map(parse).transpose()?;
+                let limit = limit.as_deref().map(parse).transpose()?;
+                let overdraft = match (overdraft, limit) {
+                    (Some(mode), limit) => Some(OverdraftPolicy::new(mode, limit)?),
+                    (None, Some(_)) => Some(OverdraftPolicy::new(OverdraftMode::Allow, limit)?),
+                    (None, None) => None,
+                };
+                self.wallet.add_account(
+                    name,
+                    kind,
+                    currency,
+                    opening.unwrap_or_default(),
+                    overdraft,
+                )?;
+                outln!("   [SUCCESS] {}", t!("accounts.added"));
+            }
+            Some(AccountAction::Overdraft { name, mode, limit }) => {
+                let id = self.wallet.accounts().find(&name)?;
+                let currency = self.wallet.accounts().currency(id);
+                let limit = limit
+                    .as_deref()
+                    .map(|input| parse_amount(input, currency))
+                    .transpose()?;
+                let policy = OverdraftPolicy::new(mode, limit)?;
+                self.wallet.set_overdraft(id, policy)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!(
+                        "cli.overdraft_saved",
+                        account = name,
+                        policy = policy.describe(currency, &self.wallet.number_format())
+                    )
+                );
+            }
+            Some(AccountAction::Rename { name, new_name }) => {
+                let id = self.wallet.accounts().find(&name)?;
+                self.wallet.rename_account(id, new_name)?;
+                outln!("   [SUCCESS] {}", t!("accounts.renamed"));
+            }
+            Some(AccountAction::Remove { name }) => {
+                let id = self.wallet.accounts().find(&name)?;
+                self.wallet.remove_account(id)?;
+                outln!("   [SUCCESS] {}", t!("accounts.removed"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_import(&mut self, args: ImportArgs, allow_overdraft: bool) -> Result<(), WalletError> {
+        let category = args
+            .category
+            .as_deref()
+            .map(|path| self.wallet.categories().find_path(path))
+            .transpose()?;
+        let tags = parse_tags(&args.tags.join(" "))?;
+        let account = self.account_arg(args.account.as_deref())?;
+        let currency = self.wallet.accounts().currency(account);
+
+        let ImportResult {
+            mut accepted,
+            mut rejected,
+        } = read_statement(
+            &args.file,
+            &args.mapping()?,
+            category,
+            &tags,
+            account,
+            currency,
+        )?;
+        accepted.sort_by_key(|row| row.item.date);
+
+        let mut new_rows = Vec::new();
+        let mut merged = Vec::new();
+        let mut skipped = Vec::new();
+        for row in accepted {
+            let known = row
+                .item
+                .fingerprints
+                .iter()
+                .find_map(|f| self.wallet.find_fingerprint(f, row.item.account));
+            if let Some(id) = known {
+                skipped.push(RejectedRow {
+                    line: row.line,
+                    reason: t!("import.already_imported", id = id),
+                });
+                continue;
+            }
+
+            // Pratinjau tidak boleh bertanya; kemungkinan duplikat cukup dilaporkan.
+            if args.dry_run
+                && args.on_duplicate == DuplicateAction::Ask
+                && let Some(duplicate) = self.wallet.find_duplicates(&row.item).first()
+            {
+                skipped.push(RejectedRow {
+                    line: row.line,
+                    reason: t!("import.duplicate_unconfirmed", id = duplicate.id),
+                });
+                continue;
+            }
+
+            match self.resolve_duplicate(&row.item, args.on_duplicate)? {
+                Resolution::Add => new_rows.push(row),
+                Resolution::Skip(id) => skipped.push(RejectedRow {
+                    line: row.line,
+                    reason: t!("import.duplicate", id = id),
+                }),
+                Resolution::Merge(id) => merged.push((row.line, id, row.item)),
+            }
+        }
+
+        let items = new_rows.iter().map(|row| row.item.clone()).collect();
+        let results = if args.dry_run {
+            self.wallet.preview_many(items, allow_overdraft)
+        } else {
+            let merges = merged
+                .iter()
+                .map(|(_, id, item)| (*id, item.clone()))
+                .collect();
+            self.wallet.import(items, merges, allow_overdraft)?
+        };
+
+        let mut imported = Vec::new();
+        for (row, result) in new_rows.into_iter().zip(results) {
+            match result {
+                Ok(_) => imported.push(row),
+                Err(e) => rejected.push(RejectedRow {
+                    line: row.line,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        rejected.sort_by_key(|row| row.line);
+        skipped.sort_by_key(|row| row.line);
+
+        outln!();
+        if args.dry_run {
+            outln!("   {}", t!("import.preview_title"));
+        } else {
+            outln!("   {}", t!("import.title"));
+        }
+        outln!("   {}", "─".repeat(80));
+
+        if !imported.is_empty() {
+            outln!(
+                "   {:>5} | {:>10} | {:>12} | {:>15} | {}",
+                t!("import.column_line"),
+                t!("column.date"),
+                t!("column.type"),
+                t!("column.amount"),
+                t!("column.description")
+            );
+            for row in &imported {
+                outln!(
+                    "   {:>5} | {:>10} | {:>12} | {:>15} | {}",
+                    row.line,
+                    row.item.date.format(DATE_FORMAT).to_string(),
+                    row.item.transaction_type,
+                    row.item.amount_label(None, &self.wallet.number_format()),
+                    row.item.description
+                );
+            }
+            outln!();
+        }
+
+        if !merged.is_empty() {
+            outln!("   {}", t!("import.merged"));
+            for (line, id, _) in &merged {
+                outln!(
+                    "   {}",
+                    t!("import.line", line = format!("{:>4}", line), reason = id)
+                );
+            }
+            outln!();
+        }
+
+        if !skipped.is_empty() {
+            outln!("   {}", t!("import.skipped"));
+            for row in &skipped {
+                print_row(row);
+            }
+            outln!();
+        }
+
+        if !rejected.is_empty() {
+            outln!("   {}", t!("import.rejected"));
+            for row in &rejected {
+                print_row(row);
+            }
+            outln!();
+        }
+
+        let (imported, merged, skipped, rejected) =
+            (imported.len(), merged.len(), skipped.len(), rejected.len());
+        outln!(
+            "   {}",
+            if args.dry_run {
+                t!(
+                    "import.preview_totals",
+                    imported = imported,
+                    merged = merged,
+                    skipped = skipped,
+                    rejected = rejected
+                )
+            } else {
+                t!(
+                    "import.totals",
+                    imported = imported,
+                    merged = merged,
+                    skipped = skipped,
+                    rejected = rejected
+                )
+            }
+        );
+        outln!();
+
+        Ok(())
+    }
+
+    fn run_rates(
+        &mut self,
+        action: Option<RateAction>,
+        format: OutputFormat,
+    ) -> Result<(), WalletError> {
+        let date_arg = |date: Option<&str>| match date {
+            Some(date) => parse_date(date),
+            None => Ok(today()),
+        };
+
+        match action {
+            None => {
+                let records: Vec<RateRecord> =
+                    self.wallet.rates().iter().map(RateRecord::new).collect();
+                match format {
+                    OutputFormat::Text => {
+                        outln!();
+                        self.print_rate_list();
+                        outln!();
+                    }
+                    OutputFormat::Json => write_json(&records),
+                    OutputFormat::Csv => write_csv(&records),
+                }
+            }
+            Some(RateAction::Set {
+                from,
+                to,
+                rate,
+                date,
+            }) => {
+                let date = date_arg(date.as_deref())?;
+                let replaced = self
+                    .wallet
+                    .set_rate(ExchangeRate::new(date, from, to, rate)?)?;
+                let date = date.format(DATE_FORMAT);
+                outln!(
+                    "   [SUCCESS] {}: {}",
+                    if replaced {
+                        t!("rates.updated", from = from, to = to, date = date)
+                    } else {
+                        t!("rates.added", from = from, to = to, date = date)
+                    },
+                    self.wallet.number_format().rate(rate)
+                );
+            }
+            Some(RateAction::Remove { from, to, date }) => {
+                let date = date_arg(date.as_deref())?;
+                self.wallet.remove_rate(date, from, to)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!("cli.rate_removed", from = from, to = to)
+                );
+            }
+            Some(RateAction::Import { file, dry_run }) => {
+                let import = read_rates(&file)?;
+                let rates: Vec<ExchangeRate> =
+                    import.rates.into_iter().map(|(_, rate)| rate).collect();
+                let (added, replaced) = if dry_run {
+                    let replaced = rates
+                        .iter()
+                        .filter(|rate| {
+                            self.wallet.rates().iter().any(|r| {
+                                r.date == rate.date && r.from == rate.from && r.to == rate.to
+                            })
+                        })
+                        .count();
+                    (rates.len() - replaced, replaced)
+                } else {
+                    self.wallet.import_rates(rates)?
+                };
+
+                outln!();
+                if dry_run {
+                    outln!("   {}", t!("rates.preview_title"));
+                } else {
+                    outln!("   {}", t!("rates.import_title"));
+                }
+                outln!("   {}", "─".repeat(80));
+                if !import.rejected.is_empty() {
+                    outln!("   {}", t!("import.rejected"));
+                    for row in &import.rejected {
+                        print_row(row);
+                    }
+                    outln!();
+                }
+                let rejected = import.rejected.len();
+                outln!(
+                    "   {}",
+                    if dry_run {
+                        t!(
+                            "rates.preview_totals",
+                            added = added,
+                            replaced = replaced,
+                            rejected = rejected
+                        )
+                    } else {
+                        t!(
+                            "rates.totals",
+                            added = added,
+                            replaced = replaced,
+                            rejected = rejected
+                        )
+                    }
+                );
+                outln!();
+            }
+            Some(RateAction::Report { currency }) => {
+                self.wallet.set_reporting_currency(currency)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!("rates.reporting", currency = currency)
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_budget(&mut self, args: BudgetArgs, format: OutputFormat) -> Result<(), WalletError> {
+        match args.action {
+            None => {
+                let month = match args.month.as_deref() {
+                    Some(month) => parse_month(month)?,
+                    None => today(),
+                };
+                match format {
+                    OutputFormat::Text => self.show_budget_status(month)?,
+                    OutputFormat::Json => write_json(&BudgetRecord::all(&self.wallet, month)?),
+                    OutputFormat::Csv => write_csv(&BudgetRecord::all(&self.wallet, month)?),
+                }
+            }
+            Some(BudgetAction::Set { category, amount }) => {
+                let id = self.wallet.categories().find_path(&category)?;
+                let limit = parse_amount(&amount, self.wallet.reporting_currency())?;
+                let replaced = self.wallet.set_budget(id, limit)?;
+                let category = self.wallet.category_label(Some(id));
+                outln!(
+                    "   [SUCCESS] {}",
+                    if replaced {
+                        t!("budget.updated", category = category)
+                    } else {
+                        t!("budget.added", category = category)
+                    }
+                );
+            }
+            Some(BudgetAction::Remove { category }) => {
+                let id = self.wallet.categories().find_path(&category)?;
+                self.wallet.remove_budget(id)?;
+                outln!("   [SUCCESS] {}", t!("budget.removed"));
+            }
+            Some(BudgetAction::Threshold { percent }) => {
+                self.wallet.set_budget_threshold(percent)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!("budget.threshold_saved", percent = percent)
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_recurring(
+        &mut self,
+        action: Option<RecurringAction>,
+        format: OutputFormat,
+        allow_overdraft: bool,
+    ) -> Result<(), WalletError> {
+        match action {
+            None => match format {
+                OutputFormat::Text => {
+                    outln!();
+                    self.print_schedule_list();
+                    outln!();
+                }
+                OutputFormat::Json => write_json(&ScheduleRecord::all(&self.wallet)),
+                OutputFormat::Csv => write_csv(&ScheduleRecord::all(&self.wallet)),
+            },
+            Some(RecurringAction::Add(args)) => {
+                match args.transaction_type {
+                    TypeArg::Income | TypeArg::Expense => {}
+                    TypeArg::Transfer | TypeArg::Journal => {
+                        return Err(WalletError::InvalidInput(t!("error.recurring_type")));
+                    }
+                }
+                let start = match args.start.as_deref() {
+                    Some(date) => parse_date(date)?,
+                    None => today(),
+                };
+                let rule = Recurrence::new(
+                    args.every,
+                    args.interval,
+                    start,
+                    args.end.as_deref().map(parse_date).transpose()?,
+                    args.count,
+                    args.last_business_day,
+                )?;
+                let account = self.account_arg(args.account.as_deref())?;
+                let currency = self.wallet.accounts().currency(account);
+                let template = WalletItems::new(
+                    parse_description(&args.desc)?,
+                    parse_amount(&args.amount, currency)?,
+                    args.transaction_type.into(),
+                    start,
+                    args.category
+                        .as_deref()
+                        .map(|path| self.wallet.categories().find_path(path))
+                        .transpose()?,
+                    parse_tags(&args.tags.join(" "))?,
+                    account,
+                )
+                .in_currency(currency);
+                let id = self.wallet.add_schedule(template, rule)?;
+                outln!("   [SUCCESS] {}", t!("recurring.added", id = id));
+            }
+            Some(RecurringAction::Remove { id }) => {
+                let id = id.parse()?;
+                let removed = self.wallet.remove_schedule(id)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!(
+                        "recurring.removed",
+                        id = id,
+                        description = removed.template.description
+                    )
+                );
+            }
+            Some(RecurringAction::Upcoming { days }) => {
+                let until = today()
+                    .checked_add_days(chrono::Days::new(days.into()))
+                    .unwrap_or(NaiveDate::MAX);
+                let occurrences = self.wallet.scheduled_until(until);
+                match format {
+                    OutputFormat::Text => self.show_upcoming(days),
+                    OutputFormat::Json => {
+                        write_json(&OccurrenceRecord::all(&self.wallet, &occurrences))
+                    }
+                    OutputFormat::Csv => {
+                        write_csv(&OccurrenceRecord::all(&self.wallet, &occurrences))
+                    }
+                }
+            }
+            Some(RecurringAction::Post { dry_run }) => {
+                let due = self.wallet.scheduled_until(today());
+                if due.is_empty() {
+                    outln!("   [INFO] {}", t!("recurring.nothing_due"));
+                } else if dry_run {
+                    outln!();
+                    outln!("   {}", t!("recurring.due_title", count = due.len()));
+                    self.print_occurrences(&due);
+                    outln!();
+                } else {
+                    // Berhenti pada kejadian pertama yang gagal; kejadian yang
+                    // sudah tercatat tetap tersimpan dan sisanya dicoba lagi
+                    // pada pemanggilan berikutnya.
+                    for (id, date) in due {
+                        let transaction = self.wallet.post_occurrence(id, allow_overdraft)?;
+                        outln!(
+                            "   [SUCCESS] {}",
+                            t!(
+                                "recurring.posted",
+                                id = id,
+                                date = date.format(DATE_FORMAT),
+                                transaction = transaction
+                            )
+                        );
+                        self.print_budget_alerts(transaction);
+                    }
+                }
+            }
+            Some(RecurringAction::Skip { id }) => {
+                let id = id.parse()?;
+                let date = self.wallet.skip_occurrence(id)?;
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!(
+                        "recurring.skipped",
+                        id = id,
+                        date = date.format(DATE_FORMAT)
+                    )
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Membatalkan (`undo`) atau mengulang perubahan berikutnya.
+    fn run_history(
+        &mut self,
+        undo: bool,
+        dry_run: bool,
+        allow_overdraft: bool,
+    ) -> Result<(), WalletError> {
+        if dry_run {
+            let (next_undo, next_redo) = self.wallet.history();
+            let change = if undo { next_undo } else { next_redo }.ok_or_else(|| {
+                WalletError::InvalidInput(if undo {
+                    t!("error.nothing_to_undo")
+                } else {
+                    t!("error.nothing_to_redo")
+                })
+            })?;
+            outln!(
+                "   {}",
+                if undo {
+                    t!("undo.next_undo", change = change.describe())
+                } else {
+                    t!("undo.next_redo", change = change.describe())
+                }
+            );
+            self.print_change(change, undo);
+            return Ok(());
+        }
+
+        let change = if undo {
+            self.wallet.undo(allow_overdraft)?
+        } else {
+            self.wallet.redo(allow_overdraft)?
+        };
+        outln!(
+            "   [SUCCESS] {}",
+            if undo {
+                t!("undo.undone", change = change.describe())
+            } else {
+                t!("undo.redone", change = change.describe())
+            }
+        );
+        self.print_change(&change, undo);
+        Ok(())
+    }
+
+    fn run_goal(
+        &mut self,
+        action: Option<GoalAction>,
+        format: OutputFormat,
+    ) -> Result<(), WalletError> {
+        match action {
+            None => match format {
+                OutputFormat::Text => self.show_goals()?,
+                OutputFormat::Json => write_json(&GoalRecord::all(&self.wallet)?),
+                OutputFormat::Csv => write_csv(&GoalRecord::all(&self.wallet)?),
+            },
+            Some(GoalAction::Add {
+                name,
+                target,
+                deadline,
+                account,
+                tag,
+            }) => {
+                let (link, currency) = match (account, tag) {
+                    (Some(account), _) => {
+                        let id = self.wallet.accounts().find(&account)?;
+                        (GoalLink::Account(id), self.wallet.accounts().currency(id))
+                    }
+                    (None, tag) => (
+                        GoalLink::tag(tag.as_deref().unwrap_or_default())?,
+                        self.wallet.reporting_currency(),
+                    ),
+                };
+                let goal = Goal {
+                    name: name.trim().to_string(),
+                    target: parse_amount(&target, currency)?,
+                    currency,
+                    deadline: deadline.as_deref().map(parse_date).transpose()?,
+                    link,
+                };
+                let name = goal.name.clone();
+                self.wallet.add_goal(goal)?;
+                outln!("   [SUCCESS] {}", t!("goal.added", name = name));
+            }
+            Some(GoalAction::Show { name }) => {
+                let goal = self.wallet.goals().find(&name)?.clone();
+                match format {
+                    OutputFormat::Text => self.show_goal_detail(&goal)?,
+                    OutputFormat::Json => write_json(&GoalRecord::new(&self.wallet, &goal)?),
+                    OutputFormat::Csv => write_csv(&[GoalRecord::new(&self.wallet, &goal)?]),
+                }
+            }
+            Some(GoalAction::Remove { name }) => {
+                let removed = self.wallet.remove_goal(&name)?;
+                outln!("   [SUCCESS] {}", t!("goal.removed", name = removed.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_audit(&mut self, args: AuditArgs, format: OutputFormat) -> Result<(), WalletError> {
+        let filter = AuditFilter {
+            actor: args.actor,
+            action: args.action,
+            transaction: args.id,
+            from: args.from.as_deref().map(parse_date).transpose()?,
+            to: args.to.as_deref().map(parse_date).transpose()?,
+        };
+        let entries = self.wallet.audit_log(&filter)?;
+        let skip = args
+            .limit
+            .map_or(0, |limit| entries.len().saturating_sub(limit));
+        let entries = &entries[skip..];
+
+        match format {
+            OutputFormat::Text => self.print_audit_log(entries),
+            _ => {
+                let records: Vec<AuditRecord> = entries
+                    .iter()
+                    .map(|(seq, entry)| AuditRecord::new(&self.wallet, *seq, entry))
+                    .collect();
+                match format {
+                    OutputFormat::Json => write_json(&records),
+                    _ => write_csv(&records),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_events(&mut self, args: EventArgs, format: OutputFormat) -> Result<(), WalletError> {
+        match args.action {
+            None => {
+                let events = self.wallet.events()?;
+                let skip = args
+                    .limit
+                    .map_or(0, |limit| events.len().saturating_sub(limit));
+                let events = &events[skip..];
+                match format {
+                    OutputFormat::Text => self.print_events(events)?,
+                    _ => {
+                        let records: Vec<EventRecord> = events
+                            .iter()
+                            .map(|event| EventRecord::new(&self.wallet, event))
+                            .collect();
+                        match format {
+                            OutputFormat::Json => write_json(&records),
+                            _ => write_csv(&records),
+                        }
+                    }
+                }
+            }
+            Some(EventAction::Balance { at, account }) => {
+                let date = parse_date(&at)?;
+                let account = account
+                    .as_deref()
+                    .map(|name| self.wallet.accounts().find(name))
+                    .transpose()?;
+                let recorded = self.wallet.as_recorded(date)?;
+                if format == OutputFormat::Text {
+                    outln!();
+                    outln!(
+                        "   {}",
+                        t!("events.as_recorded", date = date.format(DATE_FORMAT))
+                    );
+                }
+                write_balance(&recorded, account, None, format)?;
+            }
+            Some(EventAction::Rebuild { dry_run }) => {
+                let revisions = self.wallet.rebuild(!dry_run)?;
+                self.print_rebuild(&revisions, !dry_run);
+            }
+        }
+        Ok(())
+    }
+
+    fn run_display(&mut self, args: DisplayArgs, format: OutputFormat) -> Result<(), WalletError> {
+        let current = self.wallet.number_format();
+        let updated = NumberFormat {
+            locale: args.locale.unwrap_or(current.locale),
+            symbol: args.symbol.unwrap_or(current.symbol),
+            negative: args.negative.unwrap_or(current.negative),
+            compact: args.compact.unwrap_or(current.compact),
+        };
+        if updated != current {
+            self.wallet.set_number_format(updated)?;
+        }
+        let language_changed = args
+            .language
+            .is_some_and(|language| self.wallet.language() != Some(language));
+        if let Some(language) = args.language {
+            self.wallet.set_language(language)?;
+        }
+
+        match format {
+            OutputFormat::Text => {
+                outln!();
+                if updated != current || language_changed {
+                    outln!("   [SUCCESS] {}", t!("display.saved"));
+                } else {
+                    outln!("   {}", t!("display.title"));
+                }
+                outln!("   {}", "─".repeat(48));
+                self.print_number_format();
+                outln!();
+            }
+            OutputFormat::Json => write_json(&updated),
+            OutputFormat::Csv => write_csv(&[updated]),
+        }
+        Ok(())
+    }
+
+    /// Transaksi beserta saldo berjalan `account` (atau saldo bersih semua
+    /// akun) setelah setiap baris, seperti kolom Saldo pada tabel riwayat.
+    fn write_transactions(
+        &self,
+        items: &[WalletItems],
+        account: Option<AccountId>,
+        format: OutputFormat,
+    ) -> Result<(), WalletError> {
+        let running = self.wallet.running_balances_by_id(account)?;
+        let currency = self.wallet.balance_currency(account);
+        let records: Vec<TransactionRecord> = items
+            .iter()
+            .map(|item| {
+                let record = TransactionRecord::new(&self.wallet, item);
+                match running.get(&item.id) {
+                    Some(balance) => record.with_running_balance(*balance, currency),
+                    None => record,
+                }
+            })
+            .collect();
+        match format {
+            OutputFormat::Csv => write_csv(&records),
+            _ => write_json(&records),
+        }
+        Ok(())
+    }
+}
+
+/// Satu baris file impor yang dilewati atau ditolak beserta alasannya.
+fn print_row(row: &RejectedRow) {
+    outln!(
+        "   {}",
+        t!(
+            "import.line",
+            line = format!("{:>4}", row.line),
+            reason = row.reason
+        )
+    );
+}
+
+/// Saldo satu akun, atau saldo semua akun dan saldo bersihnya jika
+/// `account` adalah `None`; per akhir tanggal `as_of` jika diisi.
+fn write_balance(
+    wallet: &WalletList,
+    account: Option<AccountId>,
+    as_of: Option<NaiveDate>,
+    format: OutputFormat,
+) -> Result<(), WalletError> {
+    let balance = match as_of {
+        Some(date) => wallet.balance_as_of(account, date)?,
+        None => wallet.calculate_balance(account)?,
+    };
+    let currency = wallet.balance_currency(account);
+    let balance_text = wallet.number_format().signed(balance, currency);
+    match (format, account) {
+        (OutputFormat::Text, None) => wallet.total(as_of)?,
+        (OutputFormat::Text, Some(id)) => match as_of {
+            Some(date) => outln!(
+                "   {}",
+                t!(
+                    "cli.account_balance_as_of",
+                    account = wallet.accounts().name(id),
+                    date = date.format(DATE_FORMAT),
+                    balance = balance_text
+                )
+            ),
+            None => outln!(
+                "   {}",
+                t!(
+                    "cli.account_balance",
+                    account = wallet.accounts().name(id),
+                    balance = balance_text
+                )
+            ),
+        },
+        (OutputFormat::Json, _) => write_json(&BalanceRecord::new(
+            balance,
+            currency,
+            as_of,
+            &wallet.number_format(),
+        )),
+        (OutputFormat::Csv, _) => write_csv(&[BalanceRecord::new(
+            balance,
+            currency,
+            as_of,
+            &wallet.number_format(),
+        )]),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::money::Money, *};
+
+    fn run(app: &mut WalletApp, args: &[&str]) -> Result<(), WalletError> {
+        let cli = Cli::try_parse_from([&["wallet"], args].concat()).unwrap();
+        app.run_command(cli.command.unwrap(), cli.format, cli.allow_overdraft)
+    }
+
+    #[test]
+    fn add_and_delete_commands_change_the_wallet() {
+        let mut app = WalletApp {
+            wallet: WalletList::new(),
+            active_account: None,
+        };
+        run(
+            &mut app,
+            &[
+                "add", "--type", "income", "--amount", "100000", "--desc", "Gaji",
+            ],
+        )
+        .unwrap();
+        run(
+            &mut app,
+            &[
+                "add",
+                "--type",
+                "expense",
+                "--amount",
+                "25.000",
+                "--desc",
+                "Makan siang",
+                "--date",
+                "01/03/2026",
+                "--tag",
+                "#Kantor",
+            ],
+        )
+        .unwrap();
+
+        let lunch = app.wallet.resolve("T2").unwrap();
+        let item = app.wallet.get(lunch).unwrap();
+        assert_eq!(item.transaction_type, TypeTransaction::Expense);
+        assert_eq!(
+            item.amount,
+            Money::from_major(25_000, Currency::IDR).unwrap()
+        );
+        assert_eq!(item.date, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(item.tags, ["kantor"]);
+
+        run(&mut app, &["delete", "T2"]).unwrap();
+        assert!(app.wallet.get(lunch).is_none());
+        assert!(run(&mut app, &["delete", "T2"]).is_err());
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert!(
+            Cli::try_parse_from(["wallet", "add", "--type", "expense", "--desc", "Kopi"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["wallet", "add", "--type", "gift"]).is_err());
+
+        let mut app = WalletApp {
+            wallet: WalletList::new(),
+            active_account: None,
+        };
+        let result = run(
+            &mut app,
+            &[
+                "add", "--type", "expense", "--amount", "abc", "--desc", "Kopi",
+            ],
+        );
+        assert!(matches!(result, Err(WalletError::ParseError)));
+        assert!(app.wallet.data.items.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use clap::ValueEnum;
+
+use super::{TransactionId, WalletItems, i18n::t};
+
+/// Selisih tanggal maksimum (dalam hari) agar dua transaksi dianggap berdekatan.
+const DATE_WINDOW_DAYS: i64 = 3;
+
+/// Tindakan terhadap transaksi baru yang mirip transaksi yang sudah ada.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub(super) enum DuplicateAction {
+    #[default]
+    #[value(help = t!("help.value.duplicate_action.ask"))]
+    Ask,
+    #[value(help = t!("help.value.duplicate_action.add"))]
+    Add,
+    #[value(help = t!("help.value.duplicate_action.skip"))]
+    Skip,
+    #[value(help = t!("help.value.duplicate_action.merge"))]
+    Merge,
+}
+
+/// Keputusan akhir untuk satu transaksi baru.
+pub(super) enum Resolution {
+    Add,
+    Skip(TransactionId),
+    Merge(TransactionId),
+}
+
+/// Dua transaksi dianggap kemungkinan duplikat jika akun, jenis dan
+/// jumlahnya sama, tanggalnya berdekatan, dan deskripsinya mirip.
+pub(super) fn is_likely_duplicate(existing: &WalletItems, candidate: &WalletItems) -> bool {
+    existing.account == candidate.account
+        && existing.to_account == candidate.to_account
+        && existing.transaction_type == candidate.transaction_type
+        && existing.amount == candidate.amount
+        && (existing.date - candidate.date).num_days().abs() <= DATE_WINDOW_DAYS
+        && similar_description(&existing.description, &candidate.description)
+}
+
+/// Deskripsi dianggap mirip jika salah satunya memuat yang lain (setelah
+/// dinormalisasi) atau minimal separuh kata-katanya sama.
+fn similar_description(a: &str, b: &str) -> bool {
+    let normalize = |text: &str| -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return a == b;
+    }
+
+    let (joined_a, joined_b) = (a.join(" "), b.join(" "));
+    if joined_a.contains(&joined_b) || joined_b.contains(&joined_a) {
+        return true;
+    }
+
+    let a: HashSet<_> = a.into_iter().collect();
+    let b: HashSet<_> = b.into_iter().collect();
+    let common = a.intersection(&b).count();
+    common * 2 >= a.union(&b).count()
+}
+
+/// Sidik satu baris file impor. `occurrence` membedakan baris yang isinya
+/// persis sama dalam satu file (misalnya dua kali beli kopi di hari yang sama).
+/// Memakai FNV-1a agar hasilnya stabil antar versi Rust.
+pub(super) fn fingerprint(fields: &[String], occurrence: usize) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for field in fields {
+        for byte in field.trim().bytes().chain([0x1f]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}-{}", hash, occurrence)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{
+        super::{TypeTransaction, money::Money},
+        *,
+    };
+
+    fn expense(description: &str, minor: i64, day: u32) -> WalletItems {
+        WalletItems::new(
+            description.to_string(),
+            Money::from_minor(minor),
+            TypeTransaction::Expense,
+            NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            None,
+            Vec::new(),
+            1,
+        )
+    }
+
+    #[test]
+    fn similar_expense_on_a_nearby_date_is_a_duplicate() {
+        let existing = expense("Makan siang warteg", 2_500_000, 10);
+        assert!(is_likely_duplicate(
+            &existing,
+            &expense("makan siang", 2_500_000, 12)
+        ));
+        assert!(is_likely_duplicate(
+            &existing,
+            &expense("Warteg: makan", 2_500_000, 7)
+        ));
+
+        assert!(!is_likely_duplicate(
+            &existing,
+            &expense("makan siang", 2_600_000, 10)
+        ));
+        assert!(!is_likely_duplicate(
+            &existing,
+            &expense("makan siang", 2_500_000, 14)
+        ));
+        assert!(!is_likely_duplicate(
+            &existing,
+            &expense("Bensin", 2_500_000, 10)
+        ));
+    }
+
+    #[test]
+    fn fingerprints_ignore_padding_and_count_repeats() {
+        let row = |desc: &str| vec!["01/03/2026".to_string(), desc.to_string()];
+        assert_eq!(fingerprint(&row("Kopi"), 1), fingerprint(&row(" Kopi "), 1));
+        assert_ne!(fingerprint(&row("Kopi"), 1), fingerprint(&row("Kopi"), 2));
+        assert_ne!(fingerprint(&row("Kopi"), 1), fingerprint(&row("Teh"), 1));
+    }
+}
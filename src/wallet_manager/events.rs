@@ -0,0 +1,338 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{TransactionId, WalletError, WalletItems, history::Revision, i18n::t};
+
+/// Jumlah peristiwa paling banyak di antara dua snapshot, sehingga replay
+/// tidak pernah perlu menerapkan lebih dari sekitar sebanyak ini.
+const SNAPSHOT_INTERVAL: u64 = 1000;
+
+/// Peristiwa domain untuk transaksi. Daftar transaksi di `WalletData` adalah
+/// hasil menerapkan seluruh peristiwa ini secara berurutan.
+// Nama lengkap dipertahankan karena juga menjadi nama peristiwa di log.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(super) enum EventKind {
+    TransactionAdded {
+        item: WalletItems,
+    },
+    /// `item` berisi keadaan lengkap transaksi setelah diubah.
+    TransactionAmended {
+        item: WalletItems,
+    },
+    TransactionDeleted {
+        id: TransactionId,
+    },
+}
+
+impl EventKind {
+    pub(super) fn id(&self) -> TransactionId {
+        match self {
+            EventKind::TransactionAdded { item } | EventKind::TransactionAmended { item } => {
+                item.id
+            }
+            EventKind::TransactionDeleted { id } => *id,
+        }
+    }
+
+    pub(super) fn label(&self) -> String {
+        match self {
+            EventKind::TransactionAdded { .. } => t!("events.added"),
+            EventKind::TransactionAmended { .. } => t!("events.amended"),
+            EventKind::TransactionDeleted { .. } => t!("events.deleted"),
+        }
+    }
+
+    /// Kode stabil untuk keluaran mesin.
+    pub(super) fn code(&self) -> &'static str {
+        match self {
+            EventKind::TransactionAdded { .. } => "transaction_added",
+            EventKind::TransactionAmended { .. } => "transaction_amended",
+            EventKind::TransactionDeleted { .. } => "transaction_deleted",
+        }
+    }
+
+    fn from_revision(revision: &Revision) -> Option<Self> {
+        match (&revision.before, &revision.after) {
+            (None, Some(item)) => Some(EventKind::TransactionAdded { item: item.clone() }),
+            (Some(_), Some(item)) => Some(EventKind::TransactionAmended { item: item.clone() }),
+            (Some(_), None) => Some(EventKind::TransactionDeleted { id: revision.id }),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Satu peristiwa di log. Nomor urut `seq` dimulai dari 1 dan tidak pernah
+/// dipakai ulang.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct WalletEvent {
+    pub(super) seq: u64,
+    pub(super) at: DateTime<Local>,
+    #[serde(flatten)]
+    pub(super) kind: EventKind,
+}
+
+/// Seluruh transaksi setelah peristiwa `seq` diterapkan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Snapshot {
+    pub(super) seq: u64,
+    /// Waktu peristiwa `seq`.
+    pub(super) at: DateTime<Local>,
+    pub(super) items: Vec<WalletItems>,
+}
+
+/// Mengubah `revisions` (hasil `changed_items(before, after)`) menjadi
+/// peristiwa bernomor setelah `*seq`, lalu memajukan `*seq`.
+///
+/// Jika log masih kosong, transaksi `before` yang sudah ada sejak sebelum
+/// log peristiwa dipakai dicatat lebih dulu sebagai `TransactionAdded` pada
+/// waktu pembuatannya. Snapshot `after` ikut dibuat setiap kali nomor urut
+/// melewati kelipatan `SNAPSHOT_INTERVAL`.
+pub(super) fn record(
+    seq: &mut u64,
+    before: &[WalletItems],
+    after: &[WalletItems],
+    revisions: &[Revision],
+) -> (Vec<WalletEvent>, Option<Snapshot>) {
+    let now = Local::now();
+    let mut existing: Vec<(DateTime<Local>, EventKind)> = if *seq == 0 {
+        before
+            .iter()
+            .map(|item| {
+                (
+                    item.created_at.min(now),
+                    EventKind::TransactionAdded { item: item.clone() },
+                )
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    existing.sort_by_key(|(at, _)| *at);
+
+    let first = *seq;
+    let events: Vec<WalletEvent> = existing
+        .into_iter()
+        .chain(
+            revisions
+                .iter()
+                .filter_map(EventKind::from_revision)
+                .map(|kind| (now, kind)),
+        )
+        .map(|(at, kind)| {
+            *seq += 1;
+            WalletEvent {
+                seq: *seq,
+                at,
+                kind,
+            }
+        })
+        .collect();
+
+    let snapshot = (*seq / SNAPSHOT_INTERVAL > first / SNAPSHOT_INTERVAL).then(|| Snapshot {
+        seq: *seq,
+        at: now,
+        items: after.to_vec(),
+    });
+    (events, snapshot)
+}
+
+/// Menerapkan satu peristiwa ke daftar transaksi yang urut tanggal, dengan
+/// aturan penempatan yang sama seperti `WalletList::add` dan `update`.
+pub(super) fn apply(items: &mut Vec<WalletItems>, event: &WalletEvent) -> Result<(), WalletError> {
+    let mismatch = || {
+        WalletError::Storage(t!(
+            "error.event_replay",
+            seq = event.seq,
+            id = event.kind.id()
+        ))
+    };
+    let position = items.iter().position(|item| item.id == event.kind.id());
+    match (&event.kind, position) {
+        (EventKind::TransactionAdded { item }, None) => {
+            let position = items.partition_point(|other| other.date <= item.date);
+            items.insert(position, item.clone());
+        }
+        (EventKind::TransactionAmended { item }, Some(position)) => {
+            items[position] = item.clone();
+            items.sort_by_key(|item| item.date);
+        }
+        (EventKind::TransactionDeleted { .. }, Some(position)) => {
+            items.remove(position);
+        }
+        _ => return Err(mismatch()),
+    }
+    Ok(())
+}
+
+/// Transaksi hasil menerapkan `events` di atas `snapshot` (atau dari nol),
+/// berhenti sebelum peristiwa pertama yang lebih baru dari `until`.
+pub(super) fn replay(
+    snapshot: Option<Snapshot>,
+    events: &[WalletEvent],
+    until: Option<DateTime<Local>>,
+) -> Result<Vec<WalletItems>, WalletError> {
+    let (seq, mut items) = snapshot.map_or((0, Vec::new()), |s| (s.seq, s.items));
+    for event in events
+        .iter()
+        .filter(|event| event.seq > seq)
+        .take_while(|event| until.is_none_or(|until| event.at <= until))
+    {
+        apply(&mut items, event)?;
+    }
+    Ok(items)
+}
+
+/// Membaca satu baris log peristiwa berformat JSON Lines.
+pub(super) fn parse_event(line: &str, number: usize) -> Result<WalletEvent, WalletError> {
+    serde_json::from_str(line)
+        .map_err(|e| WalletError::Storage(t!("error.corrupt_events", line = number, error = e)))
+}
+
+/// Membaca satu snapshot berformat JSON.
+pub(super) fn parse_snapshot(line: &str, number: usize) -> Result<Snapshot, WalletError> {
+    serde_json::from_str(line)
+        .map_err(|e| WalletError::Storage(t!("error.corrupt_snapshot", line = number, error = e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use super::*;
+    use crate::wallet_manager::{TypeTransaction, money::Money};
+
+    fn item(id: u64, day: u32) -> WalletItems {
+        let mut item = WalletItems::new(
+            format!("Transaksi {}", id),
+            Money::from_minor(100_000),
+            TypeTransaction::Expense,
+            NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            None,
+            Vec::new(),
+            1,
+        );
+        item.id = TransactionId(id);
+        item
+    }
+
+    fn event(seq: u64, kind: EventKind) -> WalletEvent {
+        WalletEvent {
+            seq,
+            at: Local::now(),
+            kind,
+        }
+    }
+
+    fn ids(items: &[WalletItems]) -> Vec<u64> {
+        items.iter().map(|item| item.id.0).collect()
+    }
+
+    #[test]
+    fn apply_keeps_items_in_date_order() {
+        let mut items = Vec::new();
+        for (seq, (id, day)) in [(1, 5), (2, 1), (3, 5)].into_iter().enumerate() {
+            let added = EventKind::TransactionAdded {
+                item: item(id, day),
+            };
+            apply(&mut items, &event(seq as u64 + 1, added)).unwrap();
+        }
+        assert_eq!(ids(&items), [2, 1, 3]);
+
+        let amended = EventKind::TransactionAmended { item: item(2, 9) };
+        apply(&mut items, &event(4, amended)).unwrap();
+        assert_eq!(ids(&items), [1, 3, 2]);
+
+        let deleted = EventKind::TransactionDeleted {
+            id: TransactionId(1),
+        };
+        apply(&mut items, &event(5, deleted)).unwrap();
+        assert_eq!(ids(&items), [3, 2]);
+    }
+
+    #[test]
+    fn apply_rejects_events_that_do_not_match() {
+        let mut items = vec![item(1, 1)];
+        let added = EventKind::TransactionAdded { item: item(1, 2) };
+        assert!(apply(&mut items, &event(2, added)).is_err());
+        let deleted = EventKind::TransactionDeleted {
+            id: TransactionId(7),
+        };
+        assert!(apply(&mut items, &event(2, deleted)).is_err());
+        let amended = EventKind::TransactionAmended { item: item(7, 1) };
+        assert!(apply(&mut items, &event(2, amended)).is_err());
+    }
+
+    #[test]
+    fn replay_starts_at_snapshot_and_stops_at_until() {
+        let now = Local::now();
+        let mut events = vec![
+            event(1, EventKind::TransactionAdded { item: item(1, 1) }),
+            event(2, EventKind::TransactionAdded { item: item(2, 2) }),
+            event(
+                3,
+                EventKind::TransactionDeleted {
+                    id: TransactionId(1),
+                },
+            ),
+        ];
+        for (event, hours) in events.iter_mut().zip([-2, -1, 1]) {
+            event.at = now + Duration::hours(hours);
+        }
+
+        assert_eq!(ids(&replay(None, &events, None).unwrap()), [2]);
+        assert_eq!(ids(&replay(None, &events, Some(now)).unwrap()), [1, 2]);
+
+        // Peristiwa sampai nomor snapshot sudah termasuk di dalamnya.
+        let snapshot = Snapshot {
+            seq: 2,
+            at: events[1].at,
+            items: vec![item(1, 1), item(2, 2)],
+        };
+        assert_eq!(ids(&replay(Some(snapshot), &events, None).unwrap()), [2]);
+    }
+
+    #[test]
+    fn record_logs_existing_items_when_the_log_starts() {
+        let mut older = item(1, 1);
+        older.created_at = Local::now() - Duration::days(2);
+        let mut newer = item(2, 2);
+        newer.created_at = Local::now() - Duration::days(1);
+        let before = vec![newer.clone(), older.clone()];
+        let mut after = before.clone();
+        after.push(item(3, 3));
+        let revisions = [Revision {
+            id: TransactionId(3),
+            before: None,
+            after: Some(item(3, 3)),
+        }];
+
+        let mut seq = 0;
+        let (events, snapshot) = record(&mut seq, &before, &after, &revisions);
+        assert_eq!(seq, 3);
+        let logged: Vec<u64> = events.iter().map(|e| e.kind.id().0).collect();
+        assert_eq!(logged, [1, 2, 3]);
+        assert_eq!(events[0].at, older.created_at);
+        assert!(snapshot.is_none());
+        assert_eq!(ids(&replay(None, &events, None).unwrap()), [1, 2, 3]);
+    }
+
+    #[test]
+    fn record_takes_a_snapshot_when_crossing_the_interval() {
+        let after = vec![item(1, 1)];
+        let revisions = [Revision {
+            id: TransactionId(1),
+            before: None,
+            after: Some(item(1, 1)),
+        }];
+
+        let mut seq = SNAPSHOT_INTERVAL - 2;
+        assert!(record(&mut seq, &[], &after, &revisions).1.is_none());
+        let (events, snapshot) = record(&mut seq, &[], &after, &revisions);
+        assert_eq!(events[0].seq, SNAPSHOT_INTERVAL);
+        let snapshot = snapshot.unwrap();
+        assert_eq!(snapshot.seq, SNAPSHOT_INTERVAL);
+        assert_eq!(ids(&snapshot.items), [1]);
+    }
+}
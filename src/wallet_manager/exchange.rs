@@ -0,0 +1,432 @@
+use std::{fs, path::Path, str::FromStr};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    DATE_FORMAT, WalletError,
+    i18n::t,
+    import::{RejectedRow, parse_csv},
+    money::{Currency, Money},
+};
+
+/// Jumlah digit pecahan yang disimpan untuk sebuah kurs.
+const RATE_DIGITS: u32 = 10;
+const RATE_SCALE: i64 = 10i64.pow(RATE_DIGITS);
+
+/// Nilai tukar positif dengan presisi tetap 10 digit di belakang koma,
+/// misalnya 16250 (IDR per USD) atau 0,0000615 (USD per IDR). Disimpan
+/// sebagai teks desimal seperti `"16250.5"` agar tidak ada pembulatan biner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub(super) struct Rate(i64);
+
+impl Rate {
+    /// Membaca kurs seperti "16250", "16.250,50", "0,0000615" atau "1.35".
+    /// Satu pemisah yang diikuti tepat tiga digit ("16.250") ditolak karena
+    /// bisa berarti ribuan maupun desimal.
+    pub(super) fn parse(input: &str) -> Result<Self, WalletError> {
+        let text = input.trim();
+        let invalid = || WalletError::InvalidInput(t!("error.rate_invalid", rate = text));
+
+        let decimal = match (text.rfind('.'), text.rfind(',')) {
+            (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+            (Some(position), None) | (None, Some(position)) => {
+                let separator = text[position..].chars().next().ok_or_else(invalid)?;
+                let whole = &text[..position];
+                if text.matches(separator).count() > 1 {
+                    None
+                } else if text.len() - position - 1 == 3
+                    && !whole.trim_start_matches('0').is_empty()
+                {
+                    return Err(WalletError::InvalidInput(t!(
+                        "error.rate_ambiguous",
+                        rate = text,
+                        whole = text.replace(separator, ""),
+                        fraction = format!("{},{}0", whole, &text[position + 1..])
+                    )));
+                } else {
+                    Some(separator)
+                }
+            }
+            (None, None) => None,
+        };
+
+        let (whole, fraction) = match decimal {
+            Some(decimal) => text.rsplit_once(decimal).unwrap_or((text, "")),
+            None => (text, ""),
+        };
+        let whole: String = whole.chars().filter(|c| !matches!(c, '.' | ',')).collect();
+        if whole.is_empty()
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        if fraction.len() > RATE_DIGITS as usize {
+            return Err(WalletError::InvalidInput(t!(
+                "error.rate_digits",
+                digits = RATE_DIGITS
+            )));
+        }
+
+        let fraction = format!("{:0<width$}", fraction, width = RATE_DIGITS as usize);
+        let value = whole
+            .parse::<i64>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(RATE_SCALE))
+            .and_then(|whole| whole.checked_add(fraction.parse().ok()?))
+            .ok_or(WalletError::Overflow)?;
+        if value == 0 {
+            return Err(WalletError::InvalidInput(t!("error.rate_not_positive")));
+        }
+        Ok(Rate(value))
+    }
+
+    /// Kurs untuk keluaran mesin, misalnya "16250.5".
+    pub(super) fn to_decimal_string(self) -> String {
+        let (whole, fraction) = self.parts();
+        if fraction.is_empty() {
+            whole.to_string()
+        } else {
+            format!("{}.{}", whole, fraction)
+        }
+    }
+
+    /// Bagian bulat dan pecahan tanpa nol di belakang.
+    pub(super) fn parts(self) -> (u64, String) {
+        let value = self.0.unsigned_abs();
+        let scale = RATE_SCALE.unsigned_abs();
+        let fraction = format!("{:0width$}", value % scale, width = RATE_DIGITS as usize);
+        (value / scale, fraction.trim_end_matches('0').to_string())
+    }
+}
+
+impl FromStr for Rate {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rate::parse(s)
+    }
+}
+
+impl TryFrom<String> for Rate {
+    type Error = WalletError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Rate> for String {
+    fn from(rate: Rate) -> Self {
+        rate.to_decimal_string()
+    }
+}
+
+/// Kurs pada satu tanggal: 1 `from` = `rate` `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct ExchangeRate {
+    pub(super) date: NaiveDate,
+    pub(super) from: Currency,
+    pub(super) to: Currency,
+    pub(super) rate: Rate,
+}
+
+impl ExchangeRate {
+    pub(super) fn new(
+        date: NaiveDate,
+        from: Currency,
+        to: Currency,
+        rate: Rate,
+    ) -> Result<Self, WalletError> {
+        if from == to {
+            return Err(WalletError::InvalidInput(t!("error.rate_same_currency")));
+        }
+        Ok(Self {
+            date,
+            from,
+            to,
+            rate,
+        })
+    }
+}
+
+/// Kurs yang dipakai untuk satu konversi.
+struct Quote {
+    date: NaiveDate,
+    rate: Rate,
+    /// Kurs tercatat untuk arah sebaliknya (`to` ke `from`), jadi jumlahnya dibagi.
+    inverse: bool,
+}
+
+/// Tabel kurs bertanggal yang diisi pengguna sendiri, manual atau dari file.
+/// Konversi memakai kurs terakhir pada atau sebelum tanggal transaksi.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct RateTable {
+    rates: Vec<ExchangeRate>,
+}
+
+impl RateTable {
+    pub(super) fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    /// Kurs diurutkan per pasangan mata uang lalu tanggal.
+    pub(super) fn iter(&self) -> impl Iterator<Item = &ExchangeRate> {
+        self.rates.iter()
+    }
+
+    /// Menambah kurs, atau mengganti kurs pasangan yang sama pada tanggal
+    /// yang sama. Mengembalikan `true` jika kurs lama diganti.
+    pub(super) fn set(&mut self, rate: ExchangeRate) -> bool {
+        let existing = self
+            .rates
+            .iter_mut()
+            .find(|r| r.date == rate.date && r.from == rate.from && r.to == rate.to);
+        let replaced = match existing {
+            Some(existing) => {
+                existing.rate = rate.rate;
+                true
+            }
+            None => {
+                self.rates.push(rate);
+                false
+            }
+        };
+        self.rates.sort_by_key(|r| (r.from, r.to, r.date));
+        replaced
+    }
+
+    pub(super) fn remove(
+        &mut self,
+        date: NaiveDate,
+        from: Currency,
+        to: Currency,
+    ) -> Result<ExchangeRate, WalletError> {
+        let position = self
+            .rates
+            .iter()
+            .position(|r| r.date == date && r.from == from && r.to == to)
+            .ok_or_else(|| {
+                WalletError::InvalidInput(t!(
+                    "error.rate_not_found",
+                    from = from,
+                    to = to,
+                    date = date.format(DATE_FORMAT)
+                ))
+            })?;
+        Ok(self.rates.remove(position))
+    }
+
+    /// Mengubah `amount` dalam `from` menjadi `to` memakai kurs pada
+    /// `date`. Jika tidak ada kurs langsung (atau kebalikannya), dicoba
+    /// lewat satu mata uang perantara, misalnya SGD → IDR → USD.
+    pub(super) fn convert(
+        &self,
+        amount: Money,
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    ) -> Result<Money, WalletError> {
+        if from == to || amount.is_zero() {
+            return Ok(amount);
+        }
+        if let Some(quote) = self.quote(from, to, date) {
+            return apply(amount, from, to, &quote);
+        }
+
+        let mut via: Vec<Currency> = self.rates.iter().flat_map(|r| [r.from, r.to]).collect();
+        via.sort();
+        via.dedup();
+        for middle in via.into_iter().filter(|c| *c != from && *c != to) {
+            if let (Some(first), Some(second)) =
+                (self.quote(from, middle, date), self.quote(middle, to, date))
+            {
+                let amount = apply(amount, from, middle, &first)?;
+                return apply(amount, middle, to, &second);
+            }
+        }
+
+        Err(WalletError::MissingRate(t!(
+            "error.no_rate",
+            from = from,
+            to = to,
+            date = date.format(DATE_FORMAT)
+        )))
+    }
+
+    /// Kurs terakhir pada atau sebelum `date` untuk pasangan ini, baik yang
+    /// tercatat searah maupun kebalikannya.
+    fn quote(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<Quote> {
+        self.rates
+            .iter()
+            .filter(|r| r.date <= date)
+            .filter_map(|r| {
+                let inverse = match (r.from, r.to) {
+                    (f, t) if f == from && t == to => false,
+                    (f, t) if f == to && t == from => true,
+                    _ => return None,
+                };
+                Some(Quote {
+                    date: r.date,
+                    rate: r.rate,
+                    inverse,
+                })
+            })
+            // Pada tanggal yang sama, kurs searah lebih diutamakan.
+            .max_by_key(|q| (q.date, !q.inverse))
+    }
+}
+
+/// Konversi dengan aritmetika 128-bit, dibulatkan ke satuan terkecil
+/// terdekat (setengah menjauhi nol).
+fn apply(amount: Money, from: Currency, to: Currency, quote: &Quote) -> Result<Money, WalletError> {
+    let pow = |currency: Currency| 10i128.pow(currency.minor_units());
+    let rate = i128::from(quote.rate.0);
+    let (numerator, denominator) = if quote.inverse {
+        (
+            i128::from(amount.minor()) * i128::from(RATE_SCALE) * pow(to),
+            rate * pow(from),
+        )
+    } else {
+        (
+            i128::from(amount.minor()) * rate * pow(to),
+            i128::from(RATE_SCALE) * pow(from),
+        )
+    };
+
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    let rounded = if remainder.abs() * 2 >= denominator {
+        quotient + numerator.signum()
+    } else {
+        quotient
+    };
+    i64::try_from(rounded)
+        .map(Money::from_minor)
+        .map_err(|_| WalletError::Overflow)
+}
+
+/// Hasil membaca file kurs.
+pub(super) struct RateImport {
+    pub(super) rates: Vec<(usize, ExchangeRate)>,
+    pub(super) rejected: Vec<RejectedRow>,
+}
+
+/// Membaca file CSV kurs dengan kolom `tanggal,dari,ke,kurs`, misalnya
+/// `01/07/2025,USD,IDR,16250`. Tanggal boleh DD/MM/YYYY atau YYYY-MM-DD;
+/// pemisah kolom boleh koma atau titik koma. Baris pertama yang tanggalnya
+/// tidak terbaca dianggap header.
+pub(super) fn read_rates(path: &Path) -> Result<RateImport, WalletError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        WalletError::InvalidInput(t!("error.read_file", path = path.display(), error = e))
+    })?;
+    let first_line = content.lines().find(|line| !line.trim().is_empty());
+    let delimiter = if first_line.is_some_and(|line| line.contains(';')) {
+        ';'
+    } else {
+        ','
+    };
+
+    let mut result = RateImport {
+        rates: Vec::new(),
+        rejected: Vec::new(),
+    };
+    for (i, (line, row)) in parse_csv(&content, delimiter).into_iter().enumerate() {
+        match parse_rate_row(&row) {
+            Ok(rate) => result.rates.push((line, rate)),
+            Err(_) if i == 0 => {}
+            Err(e) => result.rejected.push(RejectedRow {
+                line,
+                reason: e.to_string(),
+            }),
+        }
+    }
+    Ok(result)
+}
+
+fn parse_rate_row(row: &[String]) -> Result<ExchangeRate, WalletError> {
+    let [date, from, to, rate] = row else {
+        return Err(WalletError::InvalidInput(t!("error.rate_row")));
+    };
+    let date = date.trim();
+    let date = NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .map_err(|_| WalletError::InvalidInput(t!("error.date", date = date)))?;
+    ExchangeRate::new(date, from.parse()?, to.parse()?, rate.parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn currency(code: &str) -> Currency {
+        code.parse().unwrap()
+    }
+
+    fn table(rates: &[(u32, &str, &str, &str)]) -> RateTable {
+        let mut table = RateTable::default();
+        for (day, from, to, rate) in rates {
+            let rate = Rate::parse(rate).unwrap();
+            table.set(ExchangeRate::new(date(*day), currency(from), currency(to), rate).unwrap());
+        }
+        table
+    }
+
+    #[test]
+    fn rates_accept_both_decimal_separators() {
+        assert_eq!(
+            Rate::parse("16.250,50").unwrap(),
+            Rate::parse("16250.5").unwrap()
+        );
+        assert_eq!(
+            Rate::parse("0,0000615").unwrap().to_decimal_string(),
+            "0.0000615"
+        );
+        assert!(Rate::parse("16.250").is_err());
+        assert!(Rate::parse("-1").is_err());
+    }
+
+    #[test]
+    fn conversion_uses_the_latest_rate_on_or_before_the_date() {
+        let table = table(&[(1, "USD", "IDR", "16000"), (10, "USD", "IDR", "16500")]);
+        let (usd, idr) = (currency("USD"), currency("IDR"));
+        let ten_dollars = Money::from_major(10, usd).unwrap();
+
+        let converted = table.convert(ten_dollars, usd, idr, date(5)).unwrap();
+        assert_eq!(converted, Money::from_major(160_000, idr).unwrap());
+        let converted = table.convert(ten_dollars, usd, idr, date(10)).unwrap();
+        assert_eq!(converted, Money::from_major(165_000, idr).unwrap());
+
+        // Kurs kebalikannya dipakai untuk arah sebaliknya.
+        let rupiah = Money::from_major(16_000, idr).unwrap();
+        let converted = table.convert(rupiah, idr, usd, date(5)).unwrap();
+        assert_eq!(converted, Money::from_major(1, usd).unwrap());
+    }
+
+    #[test]
+    fn conversion_goes_through_one_intermediate_currency() {
+        let table = table(&[(1, "USD", "IDR", "16000"), (1, "SGD", "IDR", "12000")]);
+        let (sgd, usd) = (currency("SGD"), currency("USD"));
+        let converted = table
+            .convert(Money::from_major(100, sgd).unwrap(), sgd, usd, date(2))
+            .unwrap();
+        assert_eq!(converted, Money::from_major(75, usd).unwrap());
+    }
+
+    #[test]
+    fn missing_rate_is_reported() {
+        let table = table(&[(10, "USD", "IDR", "16000")]);
+        let amount = Money::from_major(10, currency("USD")).unwrap();
+        let result = table.convert(amount, currency("USD"), currency("IDR"), date(9));
+        assert!(matches!(result, Err(WalletError::MissingRate(_))));
+        let result = table.convert(amount, currency("USD"), currency("JPY"), date(10));
+        assert!(matches!(result, Err(WalletError::MissingRate(_))));
+    }
+}
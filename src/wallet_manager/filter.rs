@@ -0,0 +1,95 @@
+use super::{
+    TypeTransaction, WalletItems,
+    account::{AccountId, AccountList},
+    i18n::t,
+    tag::format_tags,
+};
+
+/// Kriteria penyaringan transaksi. Kriteria yang kosong tidak membatasi hasil.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TransactionFilter {
+    pub(super) transaction_type: Option<TypeTransaction>,
+    /// Transaksi harus memiliki semua tag ini.
+    pub(super) include_tags: Vec<String>,
+    /// Transaksi tidak boleh memiliki satu pun tag ini.
+    pub(super) exclude_tags: Vec<String>,
+    /// Transaksi harus tercatat di akun ini, sebagai akun asal maupun tujuan.
+    pub(super) account: Option<AccountId>,
+}
+
+impl TransactionFilter {
+    pub(super) fn matches(&self, item: &WalletItems) -> bool {
+        if self
+            .transaction_type
+            .as_ref()
+            .is_some_and(|transaction_type| &item.transaction_type != transaction_type)
+        {
+            return false;
+        }
+        if self.account.is_some_and(|id| !item.involves(id)) {
+            return false;
+        }
+
+        self.include_tags.iter().all(|tag| item.tags.contains(tag))
+            && !self.exclude_tags.iter().any(|tag| item.tags.contains(tag))
+    }
+
+    pub(super) fn describe(&self, accounts: &AccountList) -> String {
+        let mut parts = Vec::new();
+        match &self.transaction_type {
+            Some(transaction_type) => parts.push(transaction_type.to_string()),
+            None => parts.push(t!("type.all")),
+        }
+        if let Some(id) = self.account {
+            parts.push(t!("filter.account", account = accounts.name(id)));
+        }
+        if !self.include_tags.is_empty() {
+            parts.push(t!(
+                "filter.with_tags",
+                tags = format_tags(&self.include_tags)
+            ));
+        }
+        if !self.exclude_tags.is_empty() {
+            parts.push(t!(
+                "filter.without_tags",
+                tags = format_tags(&self.exclude_tags)
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{
+        super::{money::Money, tag::parse_tags},
+        *,
+    };
+
+    fn tagged(tags: &str) -> WalletItems {
+        WalletItems::new(
+            "Makan".to_string(),
+            Money::from_minor(2_500_000),
+            TypeTransaction::Expense,
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            None,
+            parse_tags(tags).unwrap(),
+            1,
+        )
+    }
+
+    #[test]
+    fn tags_are_included_and_excluded() {
+        let filter = TransactionFilter {
+            include_tags: parse_tags("kantor").unwrap(),
+            exclude_tags: parse_tags("reimburse").unwrap(),
+            ..TransactionFilter::default()
+        };
+        assert!(filter.matches(&tagged("#kantor #lembur")));
+        assert!(!filter.matches(&tagged("#kantor #reimburse")));
+        assert!(!filter.matches(&tagged("#lembur")));
+        assert!(TransactionFilter::default().matches(&tagged("")));
+    }
+}
@@ -0,0 +1,327 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    TransactionId, WalletError,
+    account::AccountId,
+    i18n::t,
+    money::{Currency, Money},
+    tag::parse_tags,
+};
+
+/// Jumlah bulan terakhir yang dipakai untuk menghitung laju setoran.
+pub(super) const RECENT_MONTHS: u32 = 3;
+
+/// Sumber setoran sebuah tujuan tabungan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum GoalLink {
+    /// Setiap perubahan saldo akun ini, termasuk saldo awal dan penarikan.
+    Account(AccountId),
+    /// Jumlah setiap transaksi yang memiliki tag ini.
+    Tag(String),
+}
+
+impl GoalLink {
+    /// Membaca tepat satu tag, misalnya "#liburan".
+    pub(super) fn tag(input: &str) -> Result<Self, WalletError> {
+        match parse_tags(input)?.as_slice() {
+            [tag] => Ok(GoalLink::Tag(tag.clone())),
+            _ => Err(WalletError::InvalidInput(t!("error.goal_tag"))),
+        }
+    }
+}
+
+/// Tujuan tabungan bernama, misalnya "Dana Darurat" Rp30.000.000.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Goal {
+    pub(super) name: String,
+    pub(super) target: Money,
+    /// Mata uang target; setoran dikonversi ke mata uang ini.
+    pub(super) currency: Currency,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) deadline: Option<NaiveDate>,
+    pub(super) link: GoalLink,
+}
+
+/// Daftar tujuan tabungan. Nama tujuan unik tanpa membedakan huruf besar/kecil.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct GoalList {
+    goals: Vec<Goal>,
+}
+
+impl GoalList {
+    pub(super) fn is_empty(&self) -> bool {
+        self.goals.is_empty()
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Goal> {
+        self.goals.iter()
+    }
+
+    pub(super) fn find(&self, name: &str) -> Result<&Goal, WalletError> {
+        self.goals
+            .iter()
+            .find(|g| g.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                WalletError::InvalidInput(t!("error.goal_not_found", name = name.trim()))
+            })
+    }
+
+    pub(super) fn add(&mut self, mut goal: Goal) -> Result<(), WalletError> {
+        goal.name = goal.name.trim().to_string();
+        if goal.name.is_empty() {
+            return Err(WalletError::InvalidInput(t!("error.goal_name_empty")));
+        }
+        if self.find(&goal.name).is_ok() {
+            return Err(WalletError::InvalidInput(t!(
+                "error.goal_exists",
+                name = goal.name
+            )));
+        }
+        if !goal.target.is_positive() {
+            return Err(WalletError::InvalidInput(t!("error.goal_target")));
+        }
+        self.goals.push(goal);
+        Ok(())
+    }
+
+    pub(super) fn remove(&mut self, name: &str) -> Result<Goal, WalletError> {
+        let name = self.find(name)?.name.clone();
+        let position = self.goals.iter().position(|g| g.name == name).unwrap_or(0);
+        Ok(self.goals.remove(position))
+    }
+}
+
+/// Satu transaksi yang menambah (atau mengurangi) sebuah tujuan tabungan.
+pub(super) struct Contribution {
+    pub(super) date: NaiveDate,
+    pub(super) transaction: TransactionId,
+    pub(super) description: String,
+    /// Dalam mata uang tujuan; negatif untuk penarikan.
+    pub(super) amount: Money,
+    /// Saldo awal akun: ikut dana terkumpul, tetapi bukan bagian dari laju
+    /// setoran.
+    pub(super) opening: bool,
+}
+
+/// Apakah tujuan dengan tenggat akan tercapai tepat waktu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GoalStatus {
+    Achieved,
+    OnTrack,
+    /// Laju setoran sekarang tidak cukup untuk mencapai tenggat.
+    Behind,
+}
+
+impl GoalStatus {
+    /// Kode stabil untuk keluaran mesin.
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            GoalStatus::Achieved => "achieved",
+            GoalStatus::OnTrack => "on_track",
+            GoalStatus::Behind => "behind",
+        }
+    }
+}
+
+impl Display for GoalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoalStatus::Achieved => f.pad(&t!("goal.status_achieved")),
+            GoalStatus::OnTrack => f.pad(&t!("goal.status_on_track")),
+            GoalStatus::Behind => f.pad(&t!("goal.status_behind")),
+        }
+    }
+}
+
+/// Kemajuan sebuah tujuan pada tanggal `today`.
+pub(super) struct GoalProgress {
+    pub(super) target: Money,
+    pub(super) deadline: Option<NaiveDate>,
+    pub(super) today: NaiveDate,
+    /// Total semua setoran.
+    pub(super) saved: Money,
+    /// Total setoran selama `RECENT_MONTHS` bulan terakhir.
+    pub(super) recent: Money,
+    pub(super) contributions: Vec<Contribution>,
+}
+
+impl GoalProgress {
+    pub(super) fn new(
+        goal: &Goal,
+        contributions: Vec<Contribution>,
+        today: NaiveDate,
+    ) -> Result<Self, WalletError> {
+        let since = today
+            .checked_sub_months(Months::new(RECENT_MONTHS))
+            .unwrap_or(NaiveDate::MIN);
+        Ok(Self {
+            target: goal.target,
+            deadline: goal.deadline,
+            today,
+            saved: Money::sum(contributions.iter().map(|c| c.amount))?,
+            recent: Money::sum(
+                contributions
+                    .iter()
+                    .filter(|c| !c.opening && c.date > since && c.date <= today)
+                    .map(|c| c.amount),
+            )?,
+            contributions,
+        })
+    }
+
+    /// Kekurangan menuju target; nol jika target sudah tercapai.
+    pub(super) fn remaining(&self) -> Result<Money, WalletError> {
+        Ok(self.target.checked_sub(self.saved)?.max(Money::ZERO))
+    }
+
+    pub(super) fn percent(&self) -> f64 {
+        self.saved.minor() as f64 / self.target.minor() as f64 * 100.0
+    }
+
+    /// Rata-rata setoran per bulan selama `RECENT_MONTHS` bulan terakhir.
+    pub(super) fn pace(&self) -> Money {
+        Money::from_minor(self.recent.minor() / i64::from(RECENT_MONTHS))
+    }
+
+    /// Setoran per bulan yang dibutuhkan agar target tercapai pada tenggat.
+    /// Tenggat yang sudah lewat dihitung sebagai satu bulan tersisa.
+    pub(super) fn required_monthly(&self) -> Result<Option<Money>, WalletError> {
+        let remaining = self.remaining()?;
+        let Some(deadline) = self.deadline.filter(|_| remaining.is_positive()) else {
+            return Ok(None);
+        };
+        let months = i64::from(months_between(self.today, deadline).max(1));
+        Ok(Some(Money::from_minor(ceil_div(remaining.minor(), months))))
+    }
+
+    /// Perkiraan tanggal tercapai dengan laju setoran sekarang. `None` jika
+    /// target sudah tercapai atau laju setoran tidak positif.
+    pub(super) fn projected(&self) -> Result<Option<NaiveDate>, WalletError> {
+        let remaining = self.remaining()?;
+        let pace = self.pace();
+        if !remaining.is_positive() || !pace.is_positive() {
+            return Ok(None);
+        }
+        let months = u32::try_from(ceil_div(remaining.minor(), pace.minor())).ok();
+        Ok(months.and_then(|months| self.today.checked_add_months(Months::new(months))))
+    }
+
+    /// Kosong untuk tujuan tanpa tenggat yang belum tercapai.
+    pub(super) fn status(&self) -> Result<Option<GoalStatus>, WalletError> {
+        if !self.remaining()?.is_positive() {
+            return Ok(Some(GoalStatus::Achieved));
+        }
+        let Some(deadline) = self.deadline else {
+            return Ok(None);
+        };
+        let on_track = deadline >= self.today
+            && self
+                .projected()?
+                .is_some_and(|projected| projected <= deadline);
+        Ok(Some(if on_track {
+            GoalStatus::OnTrack
+        } else {
+            GoalStatus::Behind
+        }))
+    }
+}
+
+/// Jumlah bulan penuh dari `from` sampai `to`; nol jika `to` sudah lewat.
+fn months_between(from: NaiveDate, to: NaiveDate) -> u32 {
+    let months = (to.year() - from.year()) * 12 + to.month() as i32
+        - from.month() as i32
+        - i32::from(to.day() < from.day());
+    months.max(0) as u32
+}
+
+/// Pembagian bilangan positif yang dibulatkan ke atas.
+fn ceil_div(value: i64, divisor: i64) -> i64 {
+    value / divisor + i64::from(value % divisor != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn goal(target: i64, deadline: Option<NaiveDate>) -> Goal {
+        Goal {
+            name: "Dana Darurat".to_string(),
+            target: Money::from_minor(target),
+            currency: Currency::IDR,
+            deadline,
+            link: GoalLink::Tag("darurat".to_string()),
+        }
+    }
+
+    /// Saldo awal 100.000 lalu setoran 60.000 setiap bulan sejak Agustus.
+    fn progress(goal: &Goal) -> GoalProgress {
+        let contribution = |date, amount, opening| Contribution {
+            date,
+            transaction: TransactionId(1),
+            description: String::new(),
+            amount: Money::from_minor(amount),
+            opening,
+        };
+        let contributions = vec![
+            contribution(date(2026, 1, 1), 100_000, true),
+            contribution(date(2026, 8, 1), 60_000, false),
+            contribution(date(2026, 9, 1), 60_000, false),
+            contribution(date(2026, 10, 1), 60_000, false),
+        ];
+        GoalProgress::new(goal, contributions, date(2026, 10, 17)).unwrap()
+    }
+
+    #[test]
+    fn progress_uses_recent_contributions_for_the_pace() {
+        let progress = progress(&goal(1_000_000, Some(date(2027, 4, 17))));
+        assert_eq!(progress.saved, Money::from_minor(280_000));
+        assert!((progress.percent() - 28.0).abs() < 1e-9);
+        assert_eq!(progress.pace(), Money::from_minor(60_000));
+        assert_eq!(
+            progress.required_monthly().unwrap(),
+            Some(Money::from_minor(120_000))
+        );
+        assert_eq!(progress.projected().unwrap(), Some(date(2027, 10, 17)));
+        assert_eq!(progress.status().unwrap(), Some(GoalStatus::Behind));
+    }
+
+    #[test]
+    fn status_depends_on_deadline_and_target() {
+        let later = progress(&goal(1_000_000, Some(date(2027, 12, 31))));
+        assert_eq!(later.status().unwrap(), Some(GoalStatus::OnTrack));
+
+        let open_ended = progress(&goal(1_000_000, None));
+        assert_eq!(open_ended.status().unwrap(), None);
+        assert_eq!(open_ended.required_monthly().unwrap(), None);
+
+        let reached = progress(&goal(250_000, Some(date(2026, 1, 1))));
+        assert_eq!(reached.status().unwrap(), Some(GoalStatus::Achieved));
+        assert_eq!(reached.remaining().unwrap(), Money::ZERO);
+        assert_eq!(reached.projected().unwrap(), None);
+    }
+
+    #[test]
+    fn goal_names_are_unique_and_targets_positive() {
+        let mut list = GoalList::default();
+        list.add(goal(1_000, None)).unwrap();
+        assert!(list.add(goal(2_000, None)).is_err());
+        let mut other = goal(0, None);
+        other.name = "Liburan".to_string();
+        assert!(list.add(other).is_err());
+        assert!(list.find("dana darurat").is_ok());
+        assert!(GoalLink::tag("#a #b").is_err());
+        assert_eq!(
+            GoalLink::tag("#Liburan").unwrap(),
+            GoalLink::Tag("liburan".to_string())
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{TransactionId, WalletError, WalletItems, i18n::t};
+
+/// Jumlah perubahan paling banyak yang bisa dibatalkan; yang lebih lama
+/// dibuang.
+const HISTORY_LIMIT: usize = 50;
+
+/// Jenis perubahan transaksi yang bisa dibatalkan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum ChangeKind {
+    /// Transaksi, transfer atau jurnal baru.
+    Add,
+    /// Perubahan isi transaksi, termasuk penggabungan duplikat.
+    Update,
+    Delete,
+    /// Satu kali impor mutasi rekening, termasuk baris yang digabungkan.
+    Import,
+}
+
+/// Keadaan satu transaksi sebelum dan sesudah perubahan. `None` berarti
+/// transaksi belum ada atau sudah dihapus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Revision {
+    pub(super) id: TransactionId,
+    pub(super) before: Option<WalletItems>,
+    pub(super) after: Option<WalletItems>,
+}
+
+/// Revisi setiap transaksi yang ditambah, diubah atau dihapus antara dua
+/// keadaan, urut ID.
+pub(super) fn changed_items(before: &[WalletItems], after: &[WalletItems]) -> Vec<Revision> {
+    let mut pairs: BTreeMap<TransactionId, (Option<&WalletItems>, Option<&WalletItems>)> =
+        BTreeMap::new();
+    for item in before {
+        pairs.entry(item.id).or_default().0 = Some(item);
+    }
+    for item in after {
+        pairs.entry(item.id).or_default().1 = Some(item);
+    }
+    pairs
+        .into_iter()
+        .filter(|(_, (old, new))| match (old, new) {
+            (Some(old), Some(new)) => old != new,
+            _ => true,
+        })
+        .map(|(id, (old, new))| Revision {
+            id,
+            before: old.cloned(),
+            after: new.cloned(),
+        })
+        .collect()
+}
+
+/// Satu langkah yang dibatalkan atau diulang sekaligus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Change {
+    pub(super) kind: ChangeKind,
+    pub(super) at: DateTime<Local>,
+    pub(super) revisions: Vec<Revision>,
+}
+
+impl Change {
+    /// Uraian singkat, misalnya `Hapus T7 "Makan siang"`.
+    pub(super) fn describe(&self) -> String {
+        let revision = &self.revisions[0];
+        let description = revision
+            .after
+            .as_ref()
+            .or(revision.before.as_ref())
+            .map(|item| item.description.as_str())
+            .unwrap_or_default();
+        let id = revision.id;
+        match self.kind {
+            ChangeKind::Add => t!("undo.add", id = id, description = description),
+            ChangeKind::Update => t!("undo.update", id = id, description = description),
+            ChangeKind::Delete => t!("undo.delete", id = id, description = description),
+            ChangeKind::Import => t!("undo.import", count = self.revisions.len()),
+        }
+    }
+}
+
+/// Tumpukan perubahan untuk batalkan (undo) dan ulangi (redo). Disimpan
+/// bersama data sehingga tetap tersedia di sesi berikutnya.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct History {
+    #[serde(default)]
+    undo: Vec<Change>,
+    #[serde(default)]
+    redo: Vec<Change>,
+}
+
+impl History {
+    pub(super) fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    /// Perubahan yang akan dibatalkan berikutnya.
+    pub(super) fn next_undo(&self) -> Option<&Change> {
+        self.undo.last()
+    }
+
+    /// Perubahan yang akan diulang berikutnya.
+    pub(super) fn next_redo(&self) -> Option<&Change> {
+        self.redo.last()
+    }
+
+    /// Mencatat perubahan baru. Perubahan yang sudah dibatalkan tidak bisa
+    /// diulang lagi setelah ini.
+    pub(super) fn record(&mut self, kind: ChangeKind, revisions: Vec<Revision>) {
+        if revisions.is_empty() {
+            return;
+        }
+        self.undo.push(Change {
+            kind,
+            at: Local::now(),
+            revisions,
+        });
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Memindahkan perubahan terakhir ke tumpukan ulangi.
+    pub(super) fn undo(&mut self) -> Result<Change, WalletError> {
+        let change = self
+            .undo
+            .pop()
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.nothing_to_undo")))?;
+        self.redo.push(change.clone());
+        Ok(change)
+    }
+
+    /// Memindahkan perubahan yang terakhir dibatalkan kembali ke tumpukan
+    /// batalkan.
+    pub(super) fn redo(&mut self) -> Result<Change, WalletError> {
+        let change = self
+            .redo
+            .pop()
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.nothing_to_redo")))?;
+        self.undo.push(change.clone());
+        Ok(change)
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{
+        LazyLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Bahasa antarmuka. Setiap bahasa memiliki katalog pesan `<kode>.txt` di
+/// folder ini; menambah bahasa cukup dengan menambah varian dan katalognya.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Language {
+    /// Bahasa Indonesia
+    #[default]
+    #[value(alias = "indonesia")]
+    Id,
+    /// English
+    #[value(alias = "english")]
+    En,
+}
+
+impl Language {
+    const ALL: [Language; 2] = [Language::Id, Language::En];
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Language::Id => include_str!("id.txt"),
+            Language::En => include_str!("en.txt"),
+        }
+    }
+
+    /// Bahasa dari variabel lingkungan `WALLET_LANG`, misalnya `en` atau
+    /// `en_US.UTF-8`. Nilai yang tidak dikenal diabaikan.
+    pub(super) fn from_env() -> Option<Language> {
+        std::env::var("WALLET_LANG")
+            .ok()
+            .and_then(|code| Language::from_code(&code))
+    }
+
+    /// Membaca kode bahasa seperti "en", "EN" atau "en_US.UTF-8".
+    fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().get(..2)?.to_ascii_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Language::Id => "id",
+            Language::En => "en",
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::Id => f.pad("Bahasa Indonesia"),
+            Language::En => f.pad("English"),
+        }
+    }
+}
+
+/// Katalog bahasa Indonesia menjadi cadangan untuk kunci yang belum
+/// diterjemahkan di bahasa lain.
+const FALLBACK: Language = Language::Id;
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+static CATALOGS: LazyLock<Vec<HashMap<&'static str, &'static str>>> = LazyLock::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|language| parse_catalog(language.catalog()))
+        .collect()
+});
+
+pub(super) fn set_language(language: Language) {
+    ACTIVE.store(language as usize, Ordering::Relaxed);
+}
+
+pub(super) fn language() -> Language {
+    Language::ALL[ACTIVE.load(Ordering::Relaxed)]
+}
+
+/// Teks untuk `key` dalam bahasa aktif dengan `{nama}` diganti nilai dari
+/// `args`. Kunci yang tidak ada memakai teks bahasa Indonesia, lalu kunci
+/// itu sendiri, sehingga katalog yang belum lengkap tidak membuat gagal.
+pub(super) fn text(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = [language(), FALLBACK]
+        .into_iter()
+        .find_map(|language| CATALOGS[language as usize].get(key))
+        .copied()
+        .unwrap_or(key);
+    if args.is_empty() {
+        return template.to_string();
+    }
+
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                output.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Katalog berisi baris `kunci = teks`; baris kosong dan baris berawalan
+/// `#` diabaikan.
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, text)| (key.trim(), text))
+        .collect()
+}
+
+/// Teks terjemahan: `t!("menu.title")` atau `t!("add.merged", id = id)`.
+macro_rules! t {
+    ($key:literal) => {
+        $crate::wallet_manager::i18n::text($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::wallet_manager::i18n::text(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+
+pub(super) use t;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// Nama penampung `{...}` dalam sebuah teks.
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys_and_placeholders() {
+        let fallback = &CATALOGS[FALLBACK as usize];
+        for language in Language::ALL {
+            let catalog = &CATALOGS[language as usize];
+            for (key, text) in catalog {
+                let original = fallback.get(key);
+                assert!(original.is_some(), "{}: {} tidak ada di id", language, key);
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(original.unwrap()),
+                    "{}: {}",
+                    language,
+                    key
+                );
+            }
+            for key in fallback.keys() {
+                assert!(
+                    catalog.contains_key(key),
+                    "{}: {} belum diterjemahkan",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_keys_and_values_are_left_as_is() {
+        let text = text("halo {nama}, {lain}", &[("nama", &"Budi")]);
+        assert_eq!(text, "halo Budi, {lain}");
+    }
+
+    #[test]
+    fn language_codes_are_read_from_locale_names() {
+        assert_eq!(Language::from_code("en_US.UTF-8"), Some(Language::En));
+        assert_eq!(Language::from_code("ID"), Some(Language::Id));
+        assert_eq!(Language::from_code("fr"), None);
+        assert_eq!(Language::from_code("e"), None);
+    }
+}
@@ -0,0 +1,448 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    DATE_FORMAT, TypeTransaction, WalletError, WalletItems,
+    account::AccountId,
+    category::CategoryId,
+    duplicate::fingerprint,
+    i18n::t,
+    money::{Currency, Money},
+    parse_description, validate_amount,
+};
+
+/// Penunjuk kolom CSV: nama header (tidak peka huruf besar/kecil) atau
+/// nomor kolom mulai dari 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(super) enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl Column {
+    pub(super) fn parse(value: &str) -> Self {
+        match value.trim().parse::<usize>() {
+            Ok(number) => Column::Index(number),
+            Err(_) => Column::Name(value.trim().to_string()),
+        }
+    }
+
+    fn resolve(&self, header: Option<&[String]>) -> Result<usize, WalletError> {
+        match self {
+            Column::Index(0) => Err(WalletError::InvalidInput(t!("error.column_zero"))),
+            Column::Index(number) => Ok(number - 1),
+            Column::Name(name) => header
+                .and_then(|header| {
+                    header
+                        .iter()
+                        .position(|h| h.trim().eq_ignore_ascii_case(name))
+                })
+                .ok_or_else(|| {
+                    WalletError::InvalidInput(t!("error.column_not_found", name = name))
+                }),
+        }
+    }
+}
+
+/// Pemetaan kolom file mutasi rekening ke field transaksi. Bisa disimpan
+/// sebagai file JSON per bank lalu dipakai ulang dengan `--mapping`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(super) struct ColumnMapping {
+    pub(super) date: Option<Column>,
+    pub(super) description: Option<Column>,
+    /// Kolom uang keluar (pengeluaran).
+    pub(super) debit: Option<Column>,
+    /// Kolom uang masuk (pemasukan).
+    pub(super) credit: Option<Column>,
+    /// Satu kolom jumlah bertanda: negatif atau berakhiran `DB` berarti
+    /// pengeluaran, selain itu pemasukan.
+    pub(super) amount: Option<Column>,
+    /// Format tanggal chrono, default `%d/%m/%Y`.
+    pub(super) date_format: String,
+    pub(super) delimiter: char,
+    /// Pemisah desimal pada kolom jumlah: `,` (format Indonesia, default)
+    /// atau `.` (format Inggris, misalnya "1,250,000.00").
+    pub(super) decimal_separator: char,
+    pub(super) has_header: bool,
+    /// Jumlah baris yang dilewati sebelum header/data (judul laporan bank, dsb.).
+    pub(super) skip_rows: usize,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            date: None,
+            description: None,
+            debit: None,
+            credit: None,
+            amount: None,
+            date_format: DATE_FORMAT.to_string(),
+            delimiter: ',',
+            decimal_separator: ',',
+            has_header: true,
+            skip_rows: 0,
+        }
+    }
+}
+
+impl ColumnMapping {
+    pub(super) fn load(path: &Path) -> Result<Self, WalletError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            WalletError::InvalidInput(t!("error.read_file", path = path.display(), error = e))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| WalletError::InvalidInput(t!("error.mapping_file", error = e)))
+    }
+}
+
+struct ResolvedColumns {
+    date: usize,
+    description: usize,
+    amount: AmountColumns,
+}
+
+enum AmountColumns {
+    DebitCredit {
+        debit: Option<usize>,
+        credit: Option<usize>,
+    },
+    Signed(usize),
+}
+
+impl ResolvedColumns {
+    fn new(mapping: &ColumnMapping, header: Option<&[String]>) -> Result<Self, WalletError> {
+        let required = |column: &Option<Column>, name: &str| {
+            column
+                .as_ref()
+                .ok_or_else(|| WalletError::InvalidInput(t!("error.column_required", name = name)))?
+                .resolve(header)
+        };
+        let optional =
+            |column: &Option<Column>| column.as_ref().map(|c| c.resolve(header)).transpose();
+
+        let amount = match (&mapping.amount, &mapping.debit, &mapping.credit) {
+            (Some(amount), None, None) => AmountColumns::Signed(amount.resolve(header)?),
+            (None, debit, credit) if debit.is_some() || credit.is_some() => {
+                AmountColumns::DebitCredit {
+                    debit: optional(debit)?,
+                    credit: optional(credit)?,
+                }
+            }
+            (None, _, _) => {
+                return Err(WalletError::InvalidInput(t!(
+                    "error.amount_column_required"
+                )));
+            }
+            (Some(_), _, _) => {
+                return Err(WalletError::InvalidInput(t!(
+                    "error.amount_column_conflict"
+                )));
+            }
+        };
+
+        Ok(Self {
+            date: required(&mapping.date, &t!("column.date").to_lowercase())?,
+            description: required(
+                &mapping.description,
+                &t!("column.description").to_lowercase(),
+            )?,
+            amount,
+        })
+    }
+}
+
+/// Memecah isi CSV menjadi baris dan kolom. Mendukung kolom berpetik ganda
+/// (termasuk `""` di dalamnya dan baris baru di dalam petik).
+pub(super) fn parse_csv(content: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            }
+            '\r' if !in_quotes => {}
+            c if c == delimiter && !in_quotes => row.push(std::mem::take(&mut field)),
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+
+    rows.retain(|(_, row)| row.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+/// Membaca angka seperti "1.250.000,50", "Rp 25.000", "-50.000", "(50.000)"
+/// atau "1.250.000,00 DB". `decimal` adalah pemisah desimal (`,` untuk format
+/// Indonesia, `.` untuk format Inggris); pemisah lainnya dianggap pemisah
+/// ribuan. Tanda minus, kurung dan akhiran `DB` masing-masing berarti uang
+/// keluar. Hasilnya jumlah bertanda dalam `currency`, termasuk pecahannya.
+pub(super) fn parse_statement_amount(
+    input: &str,
+    decimal: char,
+    currency: Currency,
+) -> Result<Money, WalletError> {
+    let invalid = || WalletError::InvalidInput(t!("error.amount_invalid", amount = input.trim()));
+
+    let mut text = input.trim();
+    let mut negative = false;
+
+    if let Some(suffix) = text.get(text.len().saturating_sub(2)..) {
+        if suffix.eq_ignore_ascii_case("DB") {
+            negative = true;
+            text = text[..text.len() - 2].trim_end();
+        } else if suffix.eq_ignore_ascii_case("CR") {
+            text = text[..text.len() - 2].trim_end();
+        }
+    }
+    if let Some(inner) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        negative = true;
+        text = inner.trim();
+    }
+    if let Some(rest) = text.strip_prefix('-') {
+        negative = true;
+        text = rest.trim_start();
+    }
+    let text = text
+        .trim_start_matches(currency.symbol())
+        .trim_start_matches(currency.code())
+        .trim();
+    if text.matches(decimal).count() > 1 {
+        return Err(invalid());
+    }
+
+    let amount = match Money::parse_number(text, Some(decimal), currency) {
+        Err(WalletError::ParseError) => return Err(invalid()),
+        result => result?,
+    };
+    Ok(if negative { -amount } else { amount })
+}
+
+/// Baris yang ditolak beserta alasannya; `line` adalah nomor baris di file.
+pub(super) struct RejectedRow {
+    pub(super) line: usize,
+    pub(super) reason: String,
+}
+
+pub(super) struct ImportedRow {
+    pub(super) line: usize,
+    pub(super) item: WalletItems,
+}
+
+pub(super) struct ImportResult {
+    pub(super) accepted: Vec<ImportedRow>,
+    pub(super) rejected: Vec<RejectedRow>,
+}
+
+/// Membaca file CSV dan mengubah setiap baris menjadi transaksi dengan
+/// validasi yang sama seperti input manual. Jumlah dibaca dalam `currency`,
+/// yaitu mata uang `account`. Setiap transaksi membawa sidik baris sumbernya.
+pub(super) fn read_statement(
+    path: &Path,
+    mapping: &ColumnMapping,
+    category: Option<CategoryId>,
+    tags: &[String],
+    account: AccountId,
+    currency: Currency,
+) -> Result<ImportResult, WalletError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        WalletError::InvalidInput(t!("error.read_file", path = path.display(), error = e))
+    })?;
+
+    let mut rows = parse_csv(&content, mapping.delimiter)
+        .into_iter()
+        .skip(mapping.skip_rows);
+    let header = if mapping.has_header {
+        rows.next().map(|(_, header)| header)
+    } else {
+        None
+    };
+    let columns = ResolvedColumns::new(mapping, header.as_deref())?;
+
+    let mut result = ImportResult {
+        accepted: Vec::new(),
+        rejected: Vec::new(),
+    };
+
+    let mut occurrences: HashMap<Vec<String>, usize> = HashMap::new();
+    for (line, row) in rows {
+        let occurrence = occurrences.entry(row.clone()).or_default();
+        *occurrence += 1;
+
+        match parse_row(&row, &columns, mapping, currency) {
+            Ok((date, description, transaction_type, amount)) => {
+                let mut item = WalletItems::new(
+                    description,
+                    amount,
+                    transaction_type,
+                    date,
+                    category,
+                    tags.to_vec(),
+                    account,
+                )
+                .in_currency(currency);
+                item.fingerprints.push(fingerprint(&row, *occurrence));
+                result.accepted.push(ImportedRow { line, item });
+            }
+            Err(e) => result.rejected.push(RejectedRow {
+                line,
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
+fn parse_row(
+    row: &[String],
+    columns: &ResolvedColumns,
+    mapping: &ColumnMapping,
+    currency: Currency,
+) -> Result<(NaiveDate, String, TypeTransaction, Money), WalletError> {
+    let field = |index: usize| -> Result<&str, WalletError> {
+        row.get(index).map(|f| f.trim()).ok_or_else(|| {
+            WalletError::InvalidInput(t!("error.column_missing", column = index + 1))
+        })
+    };
+
+    let raw_date = field(columns.date)?;
+    let date = NaiveDate::parse_from_str(raw_date, &mapping.date_format).map_err(|_| {
+        WalletError::InvalidInput(t!(
+            "error.date_format",
+            date = raw_date,
+            format = mapping.date_format
+        ))
+    })?;
+
+    let description = parse_description(field(columns.description)?)?;
+
+    let signed = match columns.amount {
+        AmountColumns::Signed(index) => {
+            parse_statement_amount(field(index)?, mapping.decimal_separator, currency)?
+        }
+        AmountColumns::DebitCredit { debit, credit } => {
+            let read = |index: Option<usize>| -> Result<Money, WalletError> {
+                match index.map(field).transpose()? {
+                    Some(value) if !value.is_empty() && value != "-" => {
+                        parse_statement_amount(value, mapping.decimal_separator, currency)
+                            .map(Money::abs)
+                    }
+                    _ => Ok(Money::ZERO),
+                }
+            };
+            match (read(debit)?, read(credit)?) {
+                (debit, credit) if debit.is_zero() => credit,
+                (debit, credit) if credit.is_zero() => -debit,
+                _ => {
+                    return Err(WalletError::InvalidInput(t!("error.debit_and_credit")));
+                }
+            }
+        }
+    };
+
+    let transaction_type = if signed.is_negative() {
+        TypeTransaction::Expense
+    } else {
+        TypeTransaction::Income
+    };
+    let amount = validate_amount(signed.abs())?;
+
+    Ok((date, description, transaction_type, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(input: &str, decimal: char) -> Result<i64, WalletError> {
+        parse_statement_amount(input, decimal, Currency::IDR).map(Money::minor)
+    }
+
+    fn fields(row: &[&str]) -> Vec<String> {
+        row.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn csv_handles_quotes_and_line_numbers() {
+        let content = "\u{feff}Tanggal,Keterangan\r\n\
+                       01/03/2026,\"Makan, \"\"siang\"\"\"\r\n\
+                       \r\n\
+                       02/03/2026,\"Dua\nbaris\"\n\
+                       03/03/2026,Kopi";
+        let rows = parse_csv(content, ',');
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], (1, fields(&["Tanggal", "Keterangan"])));
+        assert_eq!(rows[1], (2, fields(&["01/03/2026", "Makan, \"siang\""])));
+        assert_eq!(rows[2], (4, fields(&["02/03/2026", "Dua\nbaris"])));
+        assert_eq!(rows[3], (6, fields(&["03/03/2026", "Kopi"])));
+    }
+
+    #[test]
+    fn statement_amount_markers_mean_money_out() {
+        assert_eq!(amount("1.250.000,00 DB", ',').unwrap(), -125_000_000);
+        assert_eq!(amount("(50.000)", ',').unwrap(), -5_000_000);
+        assert_eq!(amount("-Rp 25.000", ',').unwrap(), -2_500_000);
+        assert_eq!(amount("1.250.000,00 CR", ',').unwrap(), 125_000_000);
+        assert_eq!(amount("Rp 25.000", ',').unwrap(), 2_500_000);
+    }
+
+    #[test]
+    fn statement_amount_uses_the_given_decimal_separator() {
+        assert_eq!(amount("1,250,000.50", '.').unwrap(), 125_000_050);
+        assert_eq!(amount("1,250", '.').unwrap(), 125_000);
+        // Tidak ditebak seperti `Money::parse`: tiga digit pecahan ditolak.
+        assert!(amount("1,250", ',').is_err());
+        assert!(amount("1,2,3", ',').is_err());
+        assert!(amount("abc", ',').is_err());
+    }
+
+    #[test]
+    fn debit_and_credit_columns_set_the_type() {
+        let mapping = ColumnMapping {
+            date: Some(Column::parse("tanggal")),
+            description: Some(Column::parse("2")),
+            debit: Some(Column::parse("debit")),
+            credit: Some(Column::parse("kredit")),
+            ..ColumnMapping::default()
+        };
+        let header = fields(&["Tanggal", "Keterangan", "Debit", "Kredit"]);
+        let columns = ResolvedColumns::new(&mapping, Some(&header)).unwrap();
+        let parse = |row: &[&str]| parse_row(&fields(row), &columns, &mapping, Currency::IDR);
+
+        let (_, _, kind, money) = parse(&["01/03/2026", "Kopi", "25.000", ""]).unwrap();
+        assert_eq!(kind, TypeTransaction::Expense);
+        assert_eq!(money.minor(), 2_500_000);
+        let (_, _, kind, _) = parse(&["01/03/2026", "Gaji", "-", "5.000.000"]).unwrap();
+        assert_eq!(kind, TypeTransaction::Income);
+        assert!(parse(&["01/03/2026", "Aneh", "1.000", "2.000"]).is_err());
+        assert!(parse(&["2026-03-01", "Kopi", "1.000", ""]).is_err());
+    }
+}
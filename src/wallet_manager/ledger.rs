@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    WalletError,
+    account::AccountId,
+    category::CategoryId,
+    i18n::t,
+    locale::NumberFormat,
+    money::{Currency, Money},
+};
+
+/// Akun buku besar yang bisa menerima posting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum LedgerAccount {
+    /// Akun milik pengguna (aset atau liabilitas), lihat `AccountList`.
+    Account(AccountId),
+    /// Pendapatan per kategori; `None` untuk pendapatan tanpa kategori.
+    Income(Option<CategoryId>),
+    /// Beban per kategori; `None` untuk beban tanpa kategori.
+    Expense(Option<CategoryId>),
+    /// Ekuitas pemilik, lawan dari saldo awal akun.
+    Equity,
+    /// Akun perantara untuk transfer antar mata uang. Setiap sisi transfer
+    /// seimbang dalam mata uangnya sendiri melalui akun ini.
+    Exchange,
+}
+
+/// Satu baris jurnal. `amount` positif berarti debit, negatif berarti kredit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct Posting {
+    pub(super) account: LedgerAccount,
+    pub(super) amount: Money,
+    #[serde(default)]
+    pub(super) currency: Currency,
+}
+
+impl Posting {
+    pub(super) fn debit(account: LedgerAccount, amount: Money, currency: Currency) -> Self {
+        Self {
+            account,
+            amount,
+            currency,
+        }
+    }
+
+    pub(super) fn credit(account: LedgerAccount, amount: Money, currency: Currency) -> Self {
+        Self {
+            account,
+            amount: -amount,
+            currency,
+        }
+    }
+}
+
+/// Golongan akun pada neraca saldo, dalam urutan tampilan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum AccountClass {
+    Asset,
+    Liability,
+    Equity,
+    Income,
+    Expense,
+}
+
+impl Display for AccountClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountClass::Asset => f.pad(&t!("class.asset")),
+            AccountClass::Liability => f.pad(&t!("class.liability")),
+            AccountClass::Equity => f.pad(&t!("class.equity")),
+            AccountClass::Income => f.pad(&t!("class.income")),
+            AccountClass::Expense => f.pad(&t!("class.expense")),
+        }
+    }
+}
+
+/// Saldo satu akun buku besar dalam satu mata uang pada neraca saldo.
+/// Hanya salah satu dari `debit` atau `credit` yang terisi.
+pub(super) struct TrialBalanceRow {
+    pub(super) account: LedgerAccount,
+    pub(super) currency: Currency,
+    pub(super) class: AccountClass,
+    pub(super) debit: Money,
+    pub(super) credit: Money,
+}
+
+/// Memastikan jurnal seimbang: minimal dua posting, tidak ada posting nol,
+/// dan total debit sama dengan total kredit di setiap mata uang.
+pub(super) fn check_balanced(
+    postings: &[Posting],
+    format: &NumberFormat,
+) -> Result<(), WalletError> {
+    if postings.len() < 2 {
+        return Err(WalletError::InvalidInput(t!("error.journal_postings")));
+    }
+    if postings.iter().any(|p| p.amount.is_zero()) {
+        return Err(WalletError::InvalidInput(t!("error.posting_zero")));
+    }
+
+    for currency in postings.iter().map(|p| p.currency) {
+        let total = Money::sum(
+            postings
+                .iter()
+                .filter(|p| p.currency == currency)
+                .map(|p| p.amount),
+        )?;
+        if !total.is_zero() {
+            return Err(WalletError::InvalidInput(t!(
+                "error.journal_unbalanced",
+                difference = format.money(total.abs(), currency)
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,323 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    exchange::Rate,
+    i18n::t,
+    money::{Currency, Money},
+};
+
+/// Bahasa dan wilayah yang menentukan pemisah angka, pengelompokan digit
+/// dan singkatan jumlah besar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+pub(super) enum Locale {
+    #[default]
+    #[serde(rename = "id-ID")]
+    #[value(name = "id-ID", alias = "id", help = t!("help.value.locale.id_id"))]
+    IdId,
+    #[serde(rename = "en-US")]
+    #[value(name = "en-US", alias = "en", help = t!("help.value.locale.en_us"))]
+    EnUs,
+    #[serde(rename = "en-IN")]
+    #[value(name = "en-IN", help = t!("help.value.locale.en_in"))]
+    EnIn,
+}
+
+impl Locale {
+    fn decimal_separator(self) -> char {
+        match self {
+            Locale::IdId => ',',
+            Locale::EnUs | Locale::EnIn => '.',
+        }
+    }
+
+    fn group_separator(self) -> char {
+        match self {
+            Locale::IdId => '.',
+            Locale::EnUs | Locale::EnIn => ',',
+        }
+    }
+
+    /// Singkatan jumlah besar dari yang terbesar, sebagai pangkat sepuluh
+    /// dan akhirannya.
+    fn compact_units(self) -> &'static [(u32, &'static str)] {
+        match self {
+            Locale::IdId => &[(12, "T"), (9, "M"), (6, "jt"), (3, "rb")],
+            Locale::EnUs => &[(12, "T"), (9, "B"), (6, "M"), (3, "K")],
+            Locale::EnIn => &[(7, "Cr"), (5, "L"), (3, "K")],
+        }
+    }
+
+    /// Apakah akhiran singkatan dipisah spasi dari angkanya ("1,2 jt" vs "1.2M").
+    fn compact_spaced(self) -> bool {
+        !matches!(self, Locale::EnUs)
+    }
+
+    /// Angka bulat dengan pemisah ribuan, misalnya "1.250.000" (id-ID),
+    /// "1,250,000" (en-US) atau "12,50,000" (en-IN: tiga digit terakhir,
+    /// lalu per dua digit).
+    pub(super) fn group(self, value: u64) -> String {
+        let digits = value.to_string();
+        let len = digits.len();
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            let remaining = len - i;
+            let boundary = match self {
+                Locale::EnIn => remaining >= 3 && (remaining - 3).is_multiple_of(2),
+                Locale::IdId | Locale::EnUs => remaining.is_multiple_of(3),
+            };
+            if i > 0 && boundary {
+                grouped.push(self.group_separator());
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Locale::IdId => "id-ID",
+            Locale::EnUs => "en-US",
+            Locale::EnIn => "en-IN",
+        };
+        f.pad(code)
+    }
+}
+
+/// Letak simbol mata uang terhadap angkanya.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SymbolPosition {
+    #[default]
+    #[value(alias = "depan", help = t!("help.value.symbol_position.before"))]
+    Before,
+    #[value(alias = "belakang", help = t!("help.value.symbol_position.after"))]
+    After,
+}
+
+impl Display for SymbolPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolPosition::Before => f.pad(&t!("symbol.before")),
+            SymbolPosition::After => f.pad(&t!("symbol.after")),
+        }
+    }
+}
+
+/// Cara menulis jumlah negatif.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum NegativeStyle {
+    #[default]
+    #[value(help = t!("help.value.negative_style.minus"))]
+    Minus,
+    #[value(alias = "kurung", help = t!("help.value.negative_style.parentheses"))]
+    Parentheses,
+    #[value(alias = "belakang", help = t!("help.value.negative_style.trailing"))]
+    Trailing,
+}
+
+impl Display for NegativeStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NegativeStyle::Minus => f.pad(&t!("negative.minus")),
+            NegativeStyle::Parentheses => f.pad(&t!("negative.parentheses")),
+            NegativeStyle::Trailing => f.pad(&t!("negative.trailing")),
+        }
+    }
+}
+
+/// Pengaturan tampilan angka dan uang milik pengguna, disimpan bersama
+/// data dompet. Keluaran mesin (JSON/CSV) tidak terpengaruh kecuali field
+/// `*_formatted`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub(super) struct NumberFormat {
+    #[serde(default)]
+    pub(super) locale: Locale,
+    #[serde(default)]
+    pub(super) symbol: SymbolPosition,
+    #[serde(default)]
+    pub(super) negative: NegativeStyle,
+    /// Tabel riwayat dan laporan memakai bentuk ringkas seperti "Rp1,2 jt".
+    #[serde(default)]
+    pub(super) compact: bool,
+}
+
+impl NumberFormat {
+    /// Pengaturan yang sama tanpa bentuk ringkas, untuk tempat yang harus
+    /// menampilkan jumlah lengkap.
+    pub(super) fn exact(self) -> Self {
+        NumberFormat {
+            compact: false,
+            ..self
+        }
+    }
+
+    /// Angka tanpa tanda dan simbol, misalnya "12.500" atau "12.500,50".
+    /// Pecahan hanya ditampilkan jika bukan nol.
+    pub(super) fn number(&self, amount: Money, currency: Currency) -> String {
+        let minor_units = currency.minor_units();
+        let scale = 10u64.pow(minor_units);
+        let value = amount.minor().unsigned_abs();
+        let (whole, fraction) = (value / scale, value % scale);
+
+        let grouped = self.locale.group(whole);
+        if fraction == 0 {
+            grouped
+        } else {
+            format!(
+                "{}{}{:0width$}",
+                grouped,
+                self.locale.decimal_separator(),
+                fraction,
+                width = minor_units as usize
+            )
+        }
+    }
+
+    /// Jumlah lengkap dengan simbol mata uang; jumlah negatif mengikuti
+    /// gaya `negative`, misalnya "-Rp12.500" atau "(S$12,50)".
+    pub(super) fn money(&self, amount: Money, currency: Currency) -> String {
+        self.signed_text(amount, self.number(amount, currency), currency, false)
+    }
+
+    /// Seperti `money`, tetapi jumlah positif diawali "+".
+    pub(super) fn signed(&self, amount: Money, currency: Currency) -> String {
+        self.signed_text(amount, self.number(amount, currency), currency, true)
+    }
+
+    /// Bentuk ringkas satu angka di belakang koma, misalnya "Rp1,2 jt",
+    /// "US$3.4M" atau "₹12.5 L". Jumlah di bawah seribu ditulis lengkap.
+    pub(super) fn compact(&self, amount: Money, currency: Currency) -> String {
+        self.compact_text(amount, currency, false)
+    }
+
+    /// `compact` jika pengguna memilih tampilan ringkas, selain itu `money`.
+    pub(super) fn brief(&self, amount: Money, currency: Currency) -> String {
+        if self.compact {
+            self.compact_text(amount, currency, false)
+        } else {
+            self.money(amount, currency)
+        }
+    }
+
+    /// Seperti `brief`, tetapi jumlah positif diawali "+".
+    pub(super) fn brief_signed(&self, amount: Money, currency: Currency) -> String {
+        if self.compact {
+            self.compact_text(amount, currency, true)
+        } else {
+            self.signed(amount, currency)
+        }
+    }
+
+    /// Kurs tanpa nol di belakang, misalnya "16.250" atau "0,0000615".
+    pub(super) fn rate(&self, rate: Rate) -> String {
+        let (whole, fraction) = rate.parts();
+        let grouped = self.locale.group(whole);
+        if fraction.is_empty() {
+            grouped
+        } else {
+            format!("{}{}{}", grouped, self.locale.decimal_separator(), fraction)
+        }
+    }
+
+    /// Persentase satu angka di belakang koma, misalnya "12,5%".
+    pub(super) fn percent(&self, value: f64) -> String {
+        let text =
+            format!("{:.1}", value).replace('.', &self.locale.decimal_separator().to_string());
+        format!("{}%", text)
+    }
+
+    fn compact_text(&self, amount: Money, currency: Currency, plus: bool) -> String {
+        let scale = 10u128.pow(currency.minor_units());
+        let value = u128::from(amount.minor().unsigned_abs());
+        let units = self.locale.compact_units();
+        let Some(mut index) = units
+            .iter()
+            .position(|(exponent, _)| value >= 10u128.pow(*exponent) * scale)
+        else {
+            return self.signed_text(amount, self.number(amount, currency), currency, plus);
+        };
+
+        // Dibulatkan ke persepuluhan terdekat, setengah ke atas.
+        let tenths = |exponent: u32| {
+            let divisor = 10u128.pow(exponent) * scale;
+            (value * 10 + divisor / 2) / divisor
+        };
+        // Pembulatan bisa mencapai satuan berikutnya: 999.999 menjadi
+        // "1 jt", bukan "1.000 rb".
+        if index > 0 {
+            let (exponent, _) = units[index];
+            let (larger, _) = units[index - 1];
+            if tenths(exponent) >= 10u128.pow(larger - exponent) * 10 {
+                index -= 1;
+            }
+        }
+        let (exponent, suffix) = units[index];
+        let tenths = tenths(exponent);
+        let (whole, tenth) = ((tenths / 10) as u64, tenths % 10);
+        let mut text = self.locale.group(whole);
+        if tenth != 0 {
+            text.push(self.locale.decimal_separator());
+            text.push_str(&tenth.to_string());
+        }
+        if self.locale.compact_spaced() {
+            text.push(' ');
+        }
+        text.push_str(suffix);
+        self.signed_text(amount, text, currency, plus)
+    }
+
+    fn signed_text(&self, amount: Money, number: String, currency: Currency, plus: bool) -> String {
+        let symbol = currency.symbol();
+        let body = match self.symbol {
+            SymbolPosition::Before if symbol == currency.code() => format!("{} {}", symbol, number),
+            SymbolPosition::Before => format!("{}{}", symbol, number),
+            SymbolPosition::After => format!("{} {}", number, symbol),
+        };
+        if amount.is_negative() {
+            match self.negative {
+                NegativeStyle::Minus => format!("-{}", body),
+                NegativeStyle::Parentheses => format!("({})", body),
+                NegativeStyle::Trailing => format!("{}-", body),
+            }
+        } else if plus && amount.is_positive() {
+            format!("+{}", body)
+        } else {
+            body
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compact(locale: Locale, rupiah: i64) -> String {
+        let format = NumberFormat {
+            locale,
+            ..NumberFormat::default()
+        };
+        format.compact(Money::from_minor(rupiah * 100), Currency::IDR)
+    }
+
+    #[test]
+    fn compact_rounds_to_tenths() {
+        assert_eq!(compact(Locale::IdId, 999), "Rp999");
+        assert_eq!(compact(Locale::IdId, 1_250_000), "Rp1,3 jt");
+        assert_eq!(compact(Locale::EnUs, 1_240_000), "Rp1.2M");
+        assert_eq!(compact(Locale::IdId, -2_000_000), "-Rp2 jt");
+    }
+
+    #[test]
+    fn compact_moves_to_next_unit_after_rounding() {
+        assert_eq!(compact(Locale::IdId, 999_999), "Rp1 jt");
+        assert_eq!(compact(Locale::IdId, 999_949), "Rp999,9 rb");
+        assert_eq!(compact(Locale::EnUs, 999_999_999), "Rp1B");
+        assert_eq!(compact(Locale::EnIn, 99_999), "Rp1 L");
+    }
+}
@@ -1,781 +1,5734 @@
+mod account;
+mod audit;
+mod budget;
+mod category;
+mod cli;
+mod duplicate;
+mod events;
+mod exchange;
+mod filter;
+mod goal;
+mod history;
+mod i18n;
+mod import;
+mod ledger;
+mod locale;
+mod money;
+mod output;
+mod period;
+mod schedule;
+mod storage;
+mod tag;
+
 use std::{
+    collections::BTreeMap,
     error::Error,
     fmt::Display,
-    io::{self, Write},
+    io::{self, IsTerminal},
+    process::ExitCode,
+    str::FromStr,
     thread,
     time::Duration,
 };
 
+use account::{AccountId, AccountKind, AccountList, OverdraftMode, OverdraftPolicy};
+use audit::{AuditAction, AuditEntry, AuditFilter};
+use budget::{Budget, BudgetLevel, BudgetList, BudgetStatus, progress_bar};
+use category::{CategoryId, CategoryList};
+use chrono::{DateTime, Local, NaiveDate};
+use clap::ValueEnum;
+use duplicate::{DuplicateAction, Resolution, is_likely_duplicate};
+use events::{EventKind, WalletEvent};
+use exchange::{ExchangeRate, RateTable};
+use filter::TransactionFilter;
+use goal::{Contribution, Goal, GoalLink, GoalList, GoalProgress, RECENT_MONTHS};
+use history::{Change, ChangeKind, Revision, changed_items};
+use i18n::{Language, t};
+use ledger::{AccountClass, LedgerAccount, Posting, TrialBalanceRow, check_balanced};
+use locale::NumberFormat;
+use money::{Currency, Money};
+use output::{out, outln};
+use period::{DateRange, Period, PeriodTotal};
+use schedule::{Frequency, Recurrence, Schedule, ScheduleId, ScheduleList};
+use serde::{Deserialize, Serialize};
+use storage::{LogEntries, MemoryStorage, Storage, StorageConfig, WalletData};
+use tag::{TagTotal, format_tags, parse_tags};
+
 #[derive(Debug)]
 enum WalletError {
     InvalidInput(String),
-    InsufficientFunds,
+    InsufficientFunds(String),
+    /// Saldo akun berkebijakan `OverdraftPolicy::Confirm` akan menjadi
+    /// minus; ulangi dengan konfirmasi pengguna untuk melanjutkan.
+    OverdraftConfirmation(String),
     ParseError,
+    /// Hasil perhitungan jumlah uang melampaui batas angka yang bisa disimpan.
+    Overflow,
+    /// Konversi mata uang membutuhkan kurs yang belum ada di tabel kurs.
+    MissingRate(String),
+    Storage(String),
 }
 
 impl Display for WalletError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            WalletError::InvalidInput(msg) => write!(f, "Input Tidak Valid: {}", msg),
-            WalletError::InsufficientFunds => write!(f, "Saldo Tidak Cukup"),
-            WalletError::ParseError => write!(f, "Error Parsing"),
+            WalletError::InvalidInput(msg) => write!(f, "{}", t!("error.invalid_input", msg = msg)),
+            WalletError::InsufficientFunds(msg) => {
+                write!(f, "{}", t!("error.insufficient_funds", msg = msg))
+            }
+            WalletError::OverdraftConfirmation(msg) => {
+                write!(f, "{}", t!("error.overdraft_confirmation", msg = msg))
+            }
+            WalletError::ParseError => write!(f, "{}", t!("error.parse")),
+            WalletError::Overflow => write!(f, "{}", t!("error.overflow")),
+            WalletError::MissingRate(msg) => write!(f, "{}", t!("error.missing_rate", msg = msg)),
+            WalletError::Storage(msg) => write!(f, "{}", t!("error.storage", msg = msg)),
         }
     }
 }
 
 impl Error for WalletError {}
 
-#[derive(Debug, Clone, PartialEq)]
+impl WalletError {
+    /// Kode keluar proses untuk mode perintah (`wallet add`, `wallet list`, ...).
+    fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            WalletError::InvalidInput(_) => 2,
+            WalletError::ParseError => 3,
+            WalletError::InsufficientFunds(_) => 4,
+            WalletError::Storage(_) => 5,
+            WalletError::OverdraftConfirmation(_) => 6,
+            WalletError::Overflow => 7,
+            WalletError::MissingRate(_) => 8,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum TypeTransaction {
     Income,
     Expense,
+    /// Pemindahan dana antar akun; tidak dihitung sebagai pemasukan maupun
+    /// pengeluaran.
+    Transfer,
+    /// Jurnal dengan posting eksplisit, misalnya saldo awal atau transaksi
+    /// yang dipecah ke beberapa akun.
+    Journal,
 }
 
 impl Display for TypeTransaction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TypeTransaction::Income => write!(f, "Pemasukan"),
-            TypeTransaction::Expense => write!(f, "Pengeluaran"),
+            TypeTransaction::Income => f.pad(&t!("type.income")),
+            TypeTransaction::Expense => f.pad(&t!("type.expense")),
+            TypeTransaction::Transfer => f.pad(&t!("type.transfer")),
+            TypeTransaction::Journal => f.pad(&t!("type.journal")),
         }
     }
 }
 
-#[derive(Debug, Clone)]
-struct WalletItems {
-    description: String,
-    amount: i64,
-    transaction_type: TypeTransaction,
+const DATE_FORMAT: &str = "%d/%m/%Y";
+
+/// Jangkauan bawaan daftar transaksi berulang yang akan datang, dalam hari.
+const UPCOMING_DAYS: u32 = 30;
+
+/// ID transaksi yang tetap sama walaupun transaksi lain dihapus atau urutan
+/// riwayat berubah. Ditampilkan dengan awalan `T`, misalnya `T7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct TransactionId(u64);
+
+impl Display for TransactionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("T{}", self.0))
+    }
 }
 
-impl WalletItems {
-    #[allow(dead_code)]
-    fn new(description: String, amount: i64, transaction_type: TypeTransaction) -> Self {
-        Self {
-            description,
-            amount,
-            transaction_type,
+impl FromStr for TransactionId {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || WalletError::InvalidInput(t!("error.transaction_id", id = s));
+        let number = s
+            .trim()
+            .strip_prefix(['T', 't'])
+            .ok_or_else(invalid)?
+            .parse::<u64>()
+            .map_err(|_| invalid())?;
+        if number == 0 {
+            return Err(invalid());
         }
+        Ok(TransactionId(number))
     }
 }
 
-struct WalletList {
-    items: Vec<WalletItems>,
+fn today() -> NaiveDate {
+    Local::now().date_naive()
 }
 
-impl WalletList {
-    fn new() -> Self {
-        Self { items: Vec::new() }
-    }
-
-    fn get_checked(&mut self, index: usize) -> Option<&mut WalletItems> {
-        if index == 0 || index > self.items.len() {
-            println!("\n   [ERROR] Nomor transaksi tidak valid!");
-            None
-        } else {
-            Some(&mut self.items[index - 1])
-        }
-    }
+/// Mengubah input jumlah seperti "Rp25.000" atau "12,50" menjadi jumlah
+/// positif dalam `currency`. Lihat `Money::parse` untuk aturan pemisah desimal.
+fn parse_amount(input: &str, currency: Currency) -> Result<Money, WalletError> {
+    validate_amount(Money::parse(input, currency)?)
+}
 
-    fn calculate_balance(&self) -> i64 {
-        self.items
-            .iter()
-            .fold(0, |acc, items| match items.transaction_type {
-                TypeTransaction::Income => acc + items.amount,
-                TypeTransaction::Expense => acc - items.amount,
-            })
+fn validate_amount(amount: Money) -> Result<Money, WalletError> {
+    if !amount.is_positive() {
+        return Err(WalletError::InvalidInput(t!("error.amount_not_positive")));
     }
 
-    fn show(&self) {
-        println!();
-        println!("   RIWAYAT TRANSAKSI");
-        println!("   {}", "─".repeat(70));
+    Ok(amount)
+}
 
-        if self.items.is_empty() {
-            println!("   Tidak ada transaksi ditemukan.");
-            println!("   Tambahkan transaksi pertama Anda untuk memulai.");
-        } else {
-            println!(
-                "   {:>3} | {:>12} | {:>15} | Deskripsi",
-                "No", "Jenis", "Jumlah"
-            );
-            println!("   {}", "─".repeat(70));
+fn parse_description(input: &str) -> Result<String, WalletError> {
+    let description = input.trim().to_string();
 
-            for (i, items) in self.items.iter().enumerate() {
-                let type_symbol = match items.transaction_type {
-                    TypeTransaction::Expense => "-",
-                    TypeTransaction::Income => "+",
-                };
+    if description.is_empty() {
+        return Err(WalletError::InvalidInput(t!("error.description_empty")));
+    }
 
-                let amount_str =
-                    format!("{}Rp{}", type_symbol, Self::format_currency(items.amount));
-                let desc_truncated = if items.description.len() > 35 {
-                    format!("{}...", &items.description[..32])
-                } else {
-                    items.description.clone()
-                };
+    Ok(description)
+}
 
-                println!(
-                    "   {:>3} | {:>12} | {:>15} | {}",
-                    i + 1,
-                    items.transaction_type,
-                    amount_str,
-                    desc_truncated
-                );
-            }
+fn parse_date(input: &str) -> Result<NaiveDate, WalletError> {
+    let input = input.trim();
+    NaiveDate::parse_from_str(input, DATE_FORMAT)
+        .map_err(|_| WalletError::InvalidInput(t!("error.date", date = input)))
+}
 
-            let balance = self.calculate_balance();
-            println!("   {}", "─".repeat(70));
+/// Memotong teks berdasarkan jumlah karakter (bukan byte) agar aman untuk UTF-8.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        let cut: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    } else {
+        text.to_string()
+    }
+}
 
-            let balance_status = if balance > 0 {
-                "SURPLUS"
-            } else if balance == 0 {
-                "SEIMBANG"
-            } else {
-                "DEFISIT"
-            };
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct WalletItems {
+    /// Diisi oleh `WalletList::add`; `T0` berarti belum tersimpan.
+    #[serde(default)]
+    id: TransactionId,
+    description: String,
+    /// Jumlah dalam satuan terkecil `currency`, selalu positif; arah uang
+    /// ditentukan jenisnya.
+    amount: Money,
+    /// Mata uang `amount`, selalu sama dengan mata uang akun transaksi.
+    #[serde(default)]
+    currency: Currency,
+    transaction_type: TypeTransaction,
+    /// Tanggal pembukuan yang dipilih pengguna.
+    #[serde(default = "today")]
+    date: NaiveDate,
+    #[serde(default = "Local::now")]
+    created_at: DateTime<Local>,
+    #[serde(default = "Local::now")]
+    modified_at: DateTime<Local>,
+    #[serde(default)]
+    category: Option<CategoryId>,
+    /// Tag bebas tanpa awalan `#`, sudah dalam huruf kecil.
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Sidik baris sumber impor, agar mengimpor ulang file yang sama tidak
+    /// menggandakan transaksi. Bisa lebih dari satu jika hasil penggabungan.
+    #[serde(default)]
+    fingerprints: Vec<String>,
+    /// Akun asal transaksi. Data lama tanpa akun dipindahkan ke akun pertama
+    /// saat dimuat.
+    #[serde(default)]
+    account: AccountId,
+    /// Akun tujuan, hanya untuk transfer.
+    #[serde(default)]
+    to_account: Option<AccountId>,
+    /// Jumlah yang diterima akun tujuan dalam `to_currency`, hanya untuk
+    /// transfer antar mata uang.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_amount: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_currency: Option<Currency>,
+    /// Posting jurnal, hanya disimpan untuk `TypeTransaction::Journal`.
+    /// Jenis lain dibentuk dari field di atas oleh `postings()`.
+    #[serde(default)]
+    postings: Vec<Posting>,
+}
 
-            println!(
-                "   TOTAL SALDO: Rp{} [{}]",
-                Self::format_currency(balance.abs()),
-                balance_status
-            );
+impl WalletItems {
+    fn new(
+        description: String,
+        amount: Money,
+        transaction_type: TypeTransaction,
+        date: NaiveDate,
+        category: Option<CategoryId>,
+        tags: Vec<String>,
+        account: AccountId,
+    ) -> Self {
+        let now = Local::now();
+        Self {
+            id: TransactionId::default(),
+            description,
+            amount,
+            currency: Currency::IDR,
+            transaction_type,
+            date,
+            created_at: now,
+            modified_at: now,
+            category,
+            tags,
+            fingerprints: Vec::new(),
+            account,
+            to_account: None,
+            to_amount: None,
+            to_currency: None,
+            postings: Vec::new(),
         }
-        println!();
     }
 
-    fn add(&mut self, items: WalletItems) -> Result<(), WalletError> {
-        if let TypeTransaction::Expense = items.transaction_type {
-            let current_balance = self.calculate_balance();
-            if current_balance < items.amount {
-                return Err(WalletError::InsufficientFunds);
-            }
-        }
-
-        self.items.push(items);
-        Ok(())
+    /// Menandai `amount` sebagai jumlah dalam `currency` (bawaan IDR).
+    fn in_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
     }
 
-    fn update(&mut self, index: usize, items: WalletItems) -> Result<(), WalletError> {
-        if let Some(item) = self.get_checked(index) {
-            item.description = items.description;
-            item.amount = items.amount;
-            item.transaction_type = items.transaction_type;
-            Ok(())
-        } else {
-            Err(WalletError::InvalidInput(format!(
-                "Indeks tidak valid: {}",
-                index
-            )))
-        }
+    /// Untuk transfer antar mata uang: jumlah yang diterima akun tujuan.
+    fn receiving(mut self, amount: Money, currency: Currency) -> Self {
+        self.to_amount = Some(amount);
+        self.to_currency = Some(currency);
+        self
     }
 
-    fn filter_by_type(&self, transaction_type: TypeTransaction) -> Vec<WalletItems> {
-        self.items
+    /// Jurnal dengan posting eksplisit dalam satu mata uang. Jumlahnya
+    /// adalah total debit.
+    fn journal(
+        description: String,
+        date: NaiveDate,
+        postings: Vec<Posting>,
+        tags: Vec<String>,
+    ) -> Self {
+        let amount = total_debit(&postings).unwrap_or(Money::ZERO);
+        let account = postings
             .iter()
-            .filter(|item| item.transaction_type == transaction_type)
-            .cloned()
-            .collect()
+            .find_map(|p| match p.account {
+                LedgerAccount::Account(id) => Some(id),
+                _ => None,
+            })
+            .unwrap_or(0);
+        let currency = postings.first().map(|p| p.currency).unwrap_or_default();
+        let mut items = Self::new(
+            description,
+            amount,
+            TypeTransaction::Journal,
+            date,
+            None,
+            tags,
+            account,
+        )
+        .in_currency(currency);
+        items.postings = postings;
+        items
     }
 
-    fn total(&self) {
-        let balance = self.calculate_balance();
+    /// Transaksi sebagai posting berpasangan yang selalu seimbang. Transaksi
+    /// sederhana hanya memiliki satu sisi yang diisi pengguna; sisi lawannya
+    /// adalah akun pendapatan/beban sesuai kategorinya. Transfer antar mata
+    /// uang melewati akun konversi agar setiap mata uang tetap seimbang.
+    fn postings(&self) -> Vec<Posting> {
+        let account = LedgerAccount::Account(self.account);
+        let currency = self.currency;
+        match self.transaction_type {
+            TypeTransaction::Income => vec![
+                Posting::debit(account, self.amount, currency),
+                Posting::credit(LedgerAccount::Income(self.category), self.amount, currency),
+            ],
+            TypeTransaction::Expense => vec![
+                Posting::debit(LedgerAccount::Expense(self.category), self.amount, currency),
+                Posting::credit(account, self.amount, currency),
+            ],
+            TypeTransaction::Transfer => {
+                let to = LedgerAccount::Account(self.to_account.unwrap_or_default());
+                match (self.to_amount, self.to_currency) {
+                    (Some(to_amount), Some(to_currency)) => vec![
+                        Posting::debit(LedgerAccount::Exchange, self.amount, currency),
+                        Posting::credit(account, self.amount, currency),
+                        Posting::debit(to, to_amount, to_currency),
+                        Posting::credit(LedgerAccount::Exchange, to_amount, to_currency),
+                    ],
+                    _ => vec![
+                        Posting::debit(to, self.amount, currency),
+                        Posting::credit(account, self.amount, currency),
+                    ],
+                }
+            }
+            TypeTransaction::Journal => self.postings.clone(),
+        }
+    }
 
-        println!();
-        println!("   INFORMASI SALDO");
-        println!("   {}", "─".repeat(50));
-        println!();
+    /// Total pendapatan dan beban dari posting transaksi ini, dalam
+    /// `currency`. Transfer dan saldo awal tidak menghasilkan keduanya.
+    fn income_expense(&self) -> Result<(Money, Money), WalletError> {
+        self.postings()
+            .iter()
+            .try_fold((Money::ZERO, Money::ZERO), |(income, expense), p| {
+                match p.account {
+                    LedgerAccount::Income(_) => Ok((income.checked_sub(p.amount)?, expense)),
+                    LedgerAccount::Expense(_) => Ok((income, expense.checked_add(p.amount)?)),
+                    _ => Ok((income, expense)),
+                }
+            })
+    }
 
-        let (status_text, symbol) = if balance > 0 {
-            ("UNTUNG", "+")
-        } else if balance == 0 {
-            ("SEIMBANG", "")
-        } else {
-            ("RUGI", "-")
-        };
+    /// Apakah transaksi menyentuh akun `id`.
+    fn involves(&self, id: AccountId) -> bool {
+        self.postings()
+            .iter()
+            .any(|p| p.account == LedgerAccount::Account(id))
+    }
 
-        println!(
-            "   Saldo Saat Ini: {}Rp{}",
-            symbol,
-            Self::format_currency(balance.abs())
+    /// Transfer dari akun `from` ke akun `to`.
+    fn transfer(
+        description: String,
+        amount: Money,
+        date: NaiveDate,
+        from: AccountId,
+        to: AccountId,
+        tags: Vec<String>,
+    ) -> Self {
+        let mut items = Self::new(
+            description,
+            amount,
+            TypeTransaction::Transfer,
+            date,
+            None,
+            tags,
+            from,
         );
-        println!("   Status: {}", status_text);
-        println!();
+        items.to_account = Some(to);
+        items
     }
 
-    fn format_currency(amount: i64) -> String {
-        let mut result = amount.to_string();
-        let mut chars: Vec<char> = result.chars().collect();
+    /// Pengaruh transaksi terhadap saldo satu akun, dalam mata uang akun itu.
+    fn signed_amount(&self, account: AccountId) -> Result<Money, WalletError> {
+        Money::sum(
+            self.postings()
+                .iter()
+                .filter(|p| p.account == LedgerAccount::Account(account))
+                .map(|p| p.amount),
+        )
+    }
 
-        if chars.len() > 3 {
-            let mut i = chars.len() - 3;
-            while i > 0 {
-                chars.insert(i, '.');
-                if i > 3 {
-                    i -= 3;
-                } else {
-                    break;
-                }
-            }
-            result = chars.into_iter().collect();
+    /// Pengaruh transaksi terhadap saldo gabungan semua akun (aset
+    /// dikurangi liabilitas). Transfer tidak mengubahnya.
+    fn net_change(&self) -> Result<Money, WalletError> {
+        if self.transaction_type == TypeTransaction::Transfer {
+            return Ok(Money::ZERO);
         }
-
-        result
+        Money::sum(
+            self.postings()
+                .iter()
+                .filter(|p| matches!(p.account, LedgerAccount::Account(_)))
+                .map(|p| p.amount),
+        )
     }
 
-    fn get_summary(&self) -> (i64, i64, i64) {
-        let mut total_income = 0;
-        let mut total_expense = 0;
-
-        for transaction in &self.items {
-            match transaction.transaction_type {
-                TypeTransaction::Income => total_income += transaction.amount,
-                TypeTransaction::Expense => total_expense += transaction.amount,
+    /// Jumlah dengan tanda dilihat dari `account`, misalnya "-Rp25.000".
+    /// Transfer pada tampilan gabungan ditampilkan tanpa tanda; transfer
+    /// antar mata uang menampilkan kedua sisinya, misalnya "Rp1.000.000 > S$85".
+    fn amount_label(&self, account: Option<AccountId>, format: &NumberFormat) -> String {
+        let change = match account {
+            Some(id) => self.signed_amount(id),
+            None => self.net_change(),
+        };
+        let label = |amount: Money, currency: Currency| match change {
+            Ok(change) if change.is_negative() => format.brief(-amount, currency),
+            Ok(change) if change.is_positive() => format.brief_signed(amount, currency),
+            _ => format.brief(amount, currency),
+        };
+        match (self.to_amount, self.to_currency) {
+            (Some(to_amount), Some(to_currency)) if account == self.to_account => {
+                label(to_amount, to_currency)
             }
+            (Some(to_amount), Some(to_currency)) if account.is_none() => format!(
+                "{} > {}",
+                format.brief(self.amount, self.currency),
+                format.brief(to_amount, to_currency)
+            ),
+            _ => label(self.amount, self.currency),
         }
-
-        (total_income, total_expense, total_income - total_expense)
     }
 
-    fn remove_transaction(&mut self, index: usize) {
-        if let Some(_) = self.get_checked(index) {
-            self.items.remove(index - 1);
-            println!("\n   [SUCCESS] Transaksi berhasil dihapus.");
+    /// Deskripsi diikuti tag-nya, misalnya "Makan siang #kantor".
+    fn description_with_tags(&self) -> String {
+        if self.tags.is_empty() {
+            self.description.clone()
+        } else {
+            format!("{} {}", self.description, format_tags(&self.tags))
         }
     }
+}
+
+/// Total satu kategori utama beserta rincian sub-kategorinya.
+struct CategoryTotal {
+    category: Option<CategoryId>,
+    total: Money,
+    children: Vec<(CategoryId, Money)>,
+}
+
+/// Total pemasukan dan pengeluaran dalam satu mata uang asli.
+struct CurrencyTotal {
+    currency: Currency,
+    income: Money,
+    expense: Money,
+}
 
-    fn search_transaction(&mut self, description: &str) -> Vec<WalletItems> {
-        self.items
+/// Total sisi debit sekumpulan posting.
+fn total_debit(postings: &[Posting]) -> Result<Money, WalletError> {
+    Money::sum(
+        postings
             .iter()
-            .filter(|item| {
-                item.description
-                    .to_lowercase()
-                    .contains(&description.to_lowercase())
-            })
-            .cloned()
-            .collect()
-    }
+            .map(|p| p.amount)
+            .filter(|amount| amount.is_positive()),
+    )
 }
 
-struct WalletApp {
-    wallet: WalletList,
+/// Saldo satu akun dari `items`, dalam mata uang akun itu.
+fn account_total(items: &[WalletItems], account: AccountId) -> Result<Money, WalletError> {
+    items.iter().try_fold(Money::ZERO, |total, items| {
+        total.checked_add(items.signed_amount(account)?)
+    })
 }
 
-impl WalletApp {
-    fn new() -> Self {
-        Self {
-            wallet: WalletList::new(),
+/// Menambahkan perubahan saldo akun dari `items` ke `totals` per mata uang.
+fn add_net_change(
+    totals: &mut Vec<(Currency, Money)>,
+    items: &WalletItems,
+) -> Result<(), WalletError> {
+    for posting in items.postings() {
+        if !matches!(posting.account, LedgerAccount::Account(_)) {
+            continue;
+        }
+        match totals.iter_mut().find(|(c, _)| *c == posting.currency) {
+            Some((_, total)) => *total = total.checked_add(posting.amount)?,
+            None => totals.push((posting.currency, posting.amount)),
         }
     }
+    Ok(())
+}
 
-    fn clear_screen(&self) {
-        print!("\x1B[2J\x1B[1;1H");
-        io::stdout().flush().unwrap();
+/// Saldo bersih semua akun dari `items`, dipisah per mata uang dan
+/// diurutkan menurut kode mata uang.
+fn net_totals(items: &[WalletItems]) -> Result<Vec<(Currency, Money)>, WalletError> {
+    let mut totals: Vec<(Currency, Money)> = Vec::new();
+    for items in items {
+        add_net_change(&mut totals, items)?;
     }
+    totals.sort_by_key(|(currency, _)| *currency);
+    Ok(totals)
+}
 
-    fn print_header(&self) {
-        println!();
-        println!("   SISTEM MANAJEMEN DOMPET");
-        println!("   Alat Keuangan Profesional");
-        println!();
+impl WalletData {
+    /// Saldo satu akun dalam mata uang akun itu.
+    fn balance(&self, account: AccountId) -> Result<Money, WalletError> {
+        account_total(&self.items, account)
     }
 
-    fn print_loading(&self, message: &str) {
-        print!("   Memproses {}...", message);
-        io::stdout().flush().unwrap();
-        thread::sleep(Duration::from_millis(500));
-        println!(" Selesai!");
+    /// Saldo bersih semua akun (aset dikurangi liabilitas), dipisah per
+    /// mata uang dan diurutkan menurut kode mata uang.
+    fn net_balances(&self) -> Result<Vec<(Currency, Money)>, WalletError> {
+        net_totals(&self.items)
     }
 
-    fn pause(&self) {
-        println!("   Tekan Enter untuk melanjutkan...");
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+    /// Transaksi dengan tanggal pembukuan paling lambat `date`.
+    fn items_until(&self, date: NaiveDate) -> &[WalletItems] {
+        &self.items[..self.items.partition_point(|item| item.date <= date)]
     }
 
-    fn run(&mut self) {
-        self.clear_screen();
-        self.print_header();
-
-        println!("   Selamat datang di Sistem Manajemen Dompet!");
-        println!("   Kelola keuangan Anda dengan mudah dan tepat");
-        println!();
-
-        thread::sleep(Duration::from_millis(1000));
-
-        loop {
-            self.clear_screen();
-            self.print_header();
-            self.show_menu();
+    /// Memastikan akun transaksi ada, transfer memiliki akun tujuan yang
+    /// berbeda dari akun asal, jurnal seimbang, dan setiap jumlah memakai
+    /// mata uang akunnya.
+    fn check_accounts(&self, items: &mut WalletItems) -> Result<(), WalletError> {
+        let exists = |id: AccountId| self.accounts.get(id).is_some();
+        let mismatch = |id: AccountId, currency: Currency| {
+            WalletError::InvalidInput(t!(
+                "error.account_currency_mismatch",
+                account = self.accounts.name(id),
+                account_currency = self.accounts.currency(id),
+                currency = currency
+            ))
+        };
 
-            match self.get_user_choice() {
-                Ok(choice) => {
-                    if !self.handle_choice(&choice) {
-                        break;
+        if items.transaction_type == TypeTransaction::Journal {
+            check_balanced(&items.postings, &self.format)?;
+            let currency = items.postings[0].currency;
+            if items.postings.iter().any(|p| p.currency != currency) {
+                return Err(WalletError::InvalidInput(t!(
+                    "error.journal_mixed_currency"
+                )));
+            }
+            for posting in &items.postings {
+                if let LedgerAccount::Account(id) = posting.account {
+                    if !exists(id) {
+                        return Err(WalletError::InvalidInput(t!("error.account_not_found")));
+                    }
+                    if self.accounts.currency(id) != currency {
+                        return Err(mismatch(id, currency));
                     }
                 }
-                Err(e) => {
-                    println!("\n   [ERROR] {}", e);
-                    self.pause();
-                }
             }
+            items.amount = total_debit(&items.postings)?;
+            items.currency = currency;
+            items.to_account = None;
+            items.to_amount = None;
+            items.to_currency = None;
+            items.category = None;
+            if !exists(items.account) {
+                items.account = self.accounts.default_id();
+            }
+            return Ok(());
         }
-    }
-
-    fn show_menu(&self) {
-        let balance = self.wallet.calculate_balance();
-        let balance_symbol = if balance >= 0 { "+" } else { "-" };
-
-        println!("   MENU UTAMA");
-        println!("   {}", "─".repeat(50));
-        println!();
-        println!(
-            "   Saldo Saat Ini: {}Rp{}",
-            balance_symbol,
-            WalletList::format_currency(balance.abs())
-        );
-        println!();
-        println!("   1. Lihat Riwayat Transaksi");
-        println!("   2. Tambah Transaksi Baru");
-        println!("   3. Cek Saldo");
-        println!("   4. Ringkasan Keuangan");
-        println!("   5. Filter Berdasarkan Jenis");
-        println!("   6. Hapus Transaksi");
-        println!("   7. Perbarui Transaksi");
-        println!("   8. Cari Transaksi");
-        println!("   9. Keluar");
-        println!();
-        print!("   Pilih opsi (1-9): ");
-        io::stdout().flush().unwrap();
-    }
-
-    fn get_user_choice(&self) -> Result<String, WalletError> {
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+        items.postings.clear();
 
-        Ok(input.trim().to_string())
-    }
+        if !exists(items.account) {
+            return Err(WalletError::InvalidInput(t!("error.account_not_found")));
+        }
+        if self.accounts.currency(items.account) != items.currency {
+            return Err(mismatch(items.account, items.currency));
+        }
 
-    fn handle_choice(&mut self, choice: &str) -> bool {
-        match choice {
-            "1" => {
-                self.print_loading("riwayat transaksi");
-                self.wallet.show();
-                self.pause();
-            }
-            "2" => {
-                self.print_loading("formulir transaksi");
-                if let Err(e) = self.handle_add_transaction() {
-                    println!("\n   [ERROR] {}", e);
-                }
-                self.pause();
+        if items.transaction_type != TypeTransaction::Transfer {
+            items.to_account = None;
+            items.to_amount = None;
+            items.to_currency = None;
+            return Ok(());
+        }
+        let to = match items.to_account {
+            None => {
+                return Err(WalletError::InvalidInput(t!("error.transfer_no_target")));
             }
-            "3" => {
-                self.print_loading("perhitungan saldo");
-                self.wallet.total();
-                self.pause();
+            Some(to) if to == items.account => {
+                return Err(WalletError::InvalidInput(t!("error.transfer_same_account")));
             }
-            "4" => {
-                self.print_loading("ringkasan keuangan");
-                self.show_summary();
-                self.pause();
+            Some(to) if !exists(to) => {
+                return Err(WalletError::InvalidInput(t!(
+                    "error.target_account_not_found"
+                )));
             }
-            "5" => {
-                self.print_loading("filter transaksi");
-                if let Err(e) = self.handle_show_filter_type() {
-                    println!("   [ERROR] {}", e);
-                }
-                self.pause();
+            Some(to) => to,
+        };
+
+        let to_currency = self.accounts.currency(to);
+        if to_currency == items.currency {
+            items.to_amount = None;
+            items.to_currency = None;
+            return Ok(());
+        }
+        match items.to_amount {
+            Some(amount) if items.to_currency == Some(to_currency) => {
+                validate_amount(amount)?;
+                Ok(())
             }
-            "6" => {
-                self.print_loading("data transaksi");
-                if let Err(e) = self.handle_delete_transaction() {
-                    println!("   [ERROR] {}", e);
-                }
-                self.pause();
+            Some(_) => Err(mismatch(to, items.to_currency.unwrap_or_default())),
+            None => Err(WalletError::InvalidInput(t!(
+                "error.transfer_received_required",
+                from = items.currency,
+                to = to_currency
+            ))),
+        }
+    }
+
+    /// Memeriksa kebijakan cerukan setiap akun terhadap perubahan saldo
+    /// `change(akun)`. Perubahan yang tidak membuat saldo minus, atau justru
+    /// mengurangi minusnya, selalu diterima. `overdraft_confirmed` berarti
+    /// pengguna sudah menyetujui saldo minus pada akun berkebijakan konfirmasi.
+    fn check_funds(
+        &self,
+        change: impl Fn(AccountId) -> Result<Money, WalletError>,
+        overdraft_confirmed: bool,
+    ) -> Result<(), WalletError> {
+        for account in self.accounts.iter() {
+            let change = change(account.id)?;
+            let balance = self.balance(account.id)?.checked_add(change)?;
+            if !change.is_negative() || !balance.is_negative() {
+                continue;
             }
-            "7" => {
-                self.print_loading("pembaruan transaksi");
-                if let Err(e) = self.handle_update_transaction() {
-                    println!("   [ERROR] {}", e);
+
+            let shortfall = t!(
+                "error.shortfall",
+                account = account.name,
+                balance = self.format.money(balance, account.currency)
+            );
+            match account.overdraft() {
+                OverdraftPolicy::Forbid => return Err(WalletError::InsufficientFunds(shortfall)),
+                OverdraftPolicy::Confirm if !overdraft_confirmed => {
+                    return Err(WalletError::OverdraftConfirmation(shortfall));
                 }
-                self.pause();
-            }
-            "8" => {
-                self.print_loading("pencarian transaksi");
-                if let Err(e) = self.handle_search_transaction() {
-                    println!("   [ERROR] {}", e);
+                OverdraftPolicy::Allow { limit: Some(limit) } if balance < -limit => {
+                    return Err(WalletError::InsufficientFunds(t!(
+                        "error.over_limit",
+                        shortfall = shortfall,
+                        limit = self.format.money(limit, account.currency)
+                    )));
                 }
-            }
-            "9" => {
-                println!();
-                println!("   SELAMAT TINGGAL");
-                println!("   {}", "─".repeat(30));
-                println!();
-                println!("   Terima kasih telah menggunakan Sistem Manajemen Dompet");
-                println!("   Sampai jumpa lagi!");
-                println!();
-                thread::sleep(Duration::from_millis(1500));
-                return false;
-            }
-            _ => {
-                println!();
-                println!("   [ERROR] Perintah tidak valid");
-                println!("   Silakan pilih menu 1-9 saja");
-                println!();
-                self.pause();
+                OverdraftPolicy::Confirm | OverdraftPolicy::Allow { .. } => {}
             }
         }
-        true
+        Ok(())
     }
 
-    fn handle_add_transaction(&mut self) -> Result<(), WalletError> {
-        println!();
-        println!("   TAMBAH TRANSAKSI BARU");
-        println!("   {}", "─".repeat(30));
-        println!();
+    /// Memberi ID lalu menyisipkan transaksi sesuai urutan tanggal. Data
+    /// tidak berubah jika transaksi ditolak.
+    fn insert(
+        &mut self,
+        mut items: WalletItems,
+        overdraft_confirmed: bool,
+    ) -> Result<TransactionId, WalletError> {
+        self.check_accounts(&mut items)?;
+        self.check_funds(|id| items.signed_amount(id), overdraft_confirmed)?;
 
-        let transaction_type = self.get_type_transaction()?;
-        let amount = self.get_amount()?;
-        let description = self.get_description()?;
+        let id = TransactionId(self.next_id);
+        items.id = id;
 
-        let transaction = WalletItems {
-            transaction_type,
-            amount,
-            description,
-        };
+        // Transaksi dengan tanggal yang sama diletakkan setelah yang sudah ada.
+        let position = self.items.partition_point(|item| item.date <= items.date);
+        self.items.insert(position, items);
+        // Pastikan saldo gabungan masih bisa dihitung setelah transaksi ini.
+        if let Err(e) = self.net_balances() {
+            self.items.remove(position);
+            return Err(e);
+        }
+        self.next_id += 1;
+        Ok(id)
+    }
 
-        let message = match transaction.transaction_type {
-            TypeTransaction::Income => "Pemasukan",
-            TypeTransaction::Expense => "Pengeluaran",
-        };
+    /// Revisi transaksi `id` dari keadaan `before` ke keadaannya sekarang.
+    fn revision(&self, id: TransactionId, before: Option<WalletItems>) -> Revision {
+        Revision {
+            id,
+            before,
+            after: self.items.iter().find(|item| item.id == id).cloned(),
+        }
+    }
 
-        match self.wallet.add(transaction) {
-            Ok(_) => {
-                println!();
-                println!("   [SUCCESS] Transaksi berhasil ditambahkan");
-                println!("   Jenis: {}", message);
-                println!("   Jumlah: Rp{}", WalletList::format_currency(amount));
-                println!();
+    /// Menggabungkan `items` ke transaksi pada `position`. Data lama
+    /// dipertahankan; kategori diisi jika masih kosong, sedangkan tag dan
+    /// sidik impor ditambahkan.
+    fn merge_at(&mut self, position: usize, items: WalletItems) {
+        let item = &mut self.items[position];
+        if item.category.is_none() {
+            item.category = items.category;
+        }
+        for tag in items.tags {
+            if !item.tags.contains(&tag) {
+                item.tags.push(tag);
             }
-            Err(e) => {
-                println!();
-                println!("   [ERROR] Gagal menambahkan transaksi");
-                println!("   Alasan: {}", e);
-                println!();
+        }
+        for fingerprint in items.fingerprints {
+            if !item.fingerprints.contains(&fingerprint) {
+                item.fingerprints.push(fingerprint);
             }
         }
-
-        Ok(())
+        item.modified_at = Local::now();
     }
 
-    fn handle_update_transaction(&mut self) -> Result<(), WalletError> {
-        println!();
-        println!("   PERBARUI TRANSAKSI");
-        println!("   {}", "─".repeat(30));
-        println!();
-
-        self.wallet.show();
-
-        let index = self.get_index_transaction()?;
-        let description = self.get_description()?;
-        let transaction_type = self.get_type_transaction()?;
-        let amount = self.get_amount()?;
-
-        let transaction = WalletItems {
-            transaction_type,
-            amount,
-            description,
+    /// Mengubah transaksi `id` dari keadaan `from` ke keadaan `to` untuk
+    /// membatalkan atau mengulang perubahan. Ditolak jika transaksi itu sudah
+    /// berubah lagi sejak perubahan dicatat, atau jika akun atau kategorinya
+    /// sudah dihapus.
+    fn restore(
+        &mut self,
+        id: TransactionId,
+        from: Option<&WalletItems>,
+        to: Option<&WalletItems>,
+        overdraft_confirmed: bool,
+    ) -> Result<(), WalletError> {
+        let position = self.items.iter().position(|item| item.id == id);
+        let current = position.map(|position| &self.items[position]);
+        let unchanged = match (current, from) {
+            (None, None) => true,
+            (Some(current), Some(from)) => current.modified_at == from.modified_at,
+            _ => false,
         };
+        if !unchanged {
+            return Err(WalletError::InvalidInput(t!(
+                "error.history_conflict",
+                id = id
+            )));
+        }
 
-        match self.wallet.update(index, transaction) {
-            Ok(_) => {
-                println!();
-                println!("   [SUCCESS] Transaksi berhasil diperbarui");
-                println!();
-            }
-            Err(e) => {
-                println!();
-                println!("   [ERROR] Gagal memperbarui transaksi");
-                println!("   Alasan: {}", e);
-                println!();
+        if let Some(to) = to {
+            self.check_accounts(&mut to.clone())?;
+            if to
+                .category
+                .is_some_and(|category| self.categories.get(category).is_none())
+            {
+                return Err(WalletError::InvalidInput(t!("error.category_missing")));
             }
         }
+        self.check_funds(
+            |account| {
+                let after = match to {
+                    Some(to) => to.signed_amount(account)?,
+                    None => Money::ZERO,
+                };
+                let before = match current {
+                    Some(current) => current.signed_amount(account)?,
+                    None => Money::ZERO,
+                };
+                after.checked_sub(before)
+            },
+            overdraft_confirmed,
+        )?;
 
+        if let Some(position) = position {
+            self.items.remove(position);
+        }
+        if let Some(to) = to {
+            let position = self.items.partition_point(|item| item.date <= to.date);
+            self.items.insert(position, to.clone());
+        }
+        self.net_balances()?;
         Ok(())
     }
+}
 
-    fn handle_delete_transaction(&mut self) -> Result<(), WalletError> {
-        println!();
-        println!("   HAPUS TRANSAKSI");
-        println!("   {}", "─".repeat(30));
-        println!();
-
-        self.wallet.show();
-
-        let index = self.get_index_transaction()?;
-        self.wallet.remove_transaction(index);
+struct WalletList {
+    data: WalletData,
+    storage: Box<dyn Storage>,
+}
 
-        Ok(())
+impl WalletList {
+    #[allow(dead_code)]
+    fn new() -> Self {
+        Self {
+            data: WalletData {
+                categories: CategoryList::with_defaults(),
+                accounts: AccountList::with_defaults(),
+                next_id: 1,
+                ..WalletData::default()
+            },
+            storage: Box::new(MemoryStorage::default()),
+        }
     }
 
-    fn handle_show_filter_type(&mut self) -> Result<(), WalletError> {
-        println!();
-        println!("   FILTER TRANSAKSI");
-        println!("   {}", "─".repeat(30));
-        println!();
+    fn with_storage(mut storage: Box<dyn Storage>) -> Result<Self, WalletError> {
+        let mut data = storage.load()?;
 
-        let filter_type = self.get_filter_type()?;
-        let transactions = self.wallet.filter_by_type(filter_type.clone());
+        // Transaksi dibangun dari snapshot terakhir dan peristiwa sesudahnya;
+        // hanya data lama yang belum memiliki log peristiwa menyimpannya
+        // langsung.
+        if data.event_seq > 0 {
+            let snapshot = storage.snapshot(None)?;
+            let first = snapshot.as_ref().map_or(0, |s| s.seq);
+            let events = storage.events_after(first)?;
+            let last = events.last().map_or(first, |event| event.seq);
+            if last != data.event_seq {
+                return Err(WalletError::Storage(t!(
+                    "error.events_missing",
+                    seq = data.event_seq,
+                    last = last
+                )));
+            }
+            data.items = events::replay(snapshot, &events, None)?;
+        }
+
+        // Data dari versi lama belum memiliki ID; berikan sesuai urutan simpan.
+        let max_id = data.items.iter().map(|item| item.id.0).max().unwrap_or(0);
+        data.next_id = data.next_id.max(max_id + 1);
+        for item in data.items.iter_mut().filter(|item| item.id.0 == 0) {
+            item.id = TransactionId(data.next_id);
+            data.next_id += 1;
+        }
+
+        data.items.sort_by_key(|item| item.date);
+        if data.categories.is_empty() {
+            data.categories = CategoryList::with_defaults();
+        }
+
+        // Data dari versi lama belum memiliki akun; semuanya masuk akun pertama.
+        if data.accounts.is_empty() {
+            data.accounts = AccountList::with_defaults();
+        }
+        let default_account = data.accounts.default_id();
+        for item in data.items.iter_mut().filter(|item| item.account == 0) {
+            item.account = default_account;
+        }
+        Ok(Self { data, storage })
+    }
+
+    /// Menjalankan perubahan lalu menyimpannya bersama entri log audit
+    /// untuk `action`. Jika perubahan atau penyimpanan gagal, data di memori
+    /// dikembalikan seperti semula dan tidak ada yang dicatat.
+    ///
+    /// Setiap transaksi yang berubah juga dicatat sebagai peristiwa di log
+    /// peristiwa.
+    fn mutate<T>(
+        &mut self,
+        action: AuditAction,
+        change: impl FnOnce(&mut WalletData) -> Result<T, WalletError>,
+    ) -> Result<T, WalletError> {
+        let snapshot = self.data.clone();
+        let result = change(&mut self.data).and_then(|value| {
+            let revisions = changed_items(&snapshot.items, &self.data.items);
+            let (events, event_snapshot) = events::record(
+                &mut self.data.event_seq,
+                &snapshot.items,
+                &self.data.items,
+                &revisions,
+            );
+            let log = LogEntries {
+                audit: AuditEntry::new(action, revisions, &snapshot, &self.data),
+                events,
+                snapshot: event_snapshot,
+            };
+            self.storage.save(&self.data, &log).map(|_| value)
+        });
+        if result.is_err() {
+            self.data = snapshot;
+        }
+        result
+    }
+
+    /// Entri log audit yang cocok dengan `filter` beserta nomor urutnya
+    /// (mulai dari 1), dari yang terlama.
+    fn audit_log(&mut self, filter: &AuditFilter) -> Result<Vec<(usize, AuditEntry)>, WalletError> {
+        Ok(self
+            .storage
+            .audit_log()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| (index + 1, entry))
+            .filter(|(_, entry)| filter.matches(entry))
+            .collect())
+    }
+
+    /// Seluruh log peristiwa, dari yang terlama.
+    fn events(&mut self) -> Result<Vec<WalletEvent>, WalletError> {
+        self.storage.events_after(0)
+    }
+
+    /// Nomor peristiwa terakhir dan nomor peristiwa snapshot terbaru.
+    fn event_position(&mut self) -> Result<(u64, Option<u64>), WalletError> {
+        let snapshot = self.storage.snapshot(None)?;
+        Ok((self.data.event_seq, snapshot.map(|s| s.seq)))
+    }
+
+    /// Transaksi seperti yang tercatat pada waktu `until` (sekarang jika
+    /// `None`), dibangun ulang dari snapshot terakhir sebelum waktu itu dan
+    /// peristiwa sesudahnya.
+    fn replay(&mut self, until: Option<DateTime<Local>>) -> Result<Vec<WalletItems>, WalletError> {
+        if self.data.event_seq == 0 {
+            return Err(WalletError::InvalidInput(t!("error.no_events")));
+        }
+        let snapshot = self.storage.snapshot(until)?;
+        let events = self
+            .storage
+            .events_after(snapshot.as_ref().map_or(0, |s| s.seq))?;
+        events::replay(snapshot, &events, until)
+    }
+
+    /// Salinan dompet hanya-baca dengan transaksi seperti yang tercatat pada
+    /// akhir hari `date`. Akun, kurs dan pengaturan tetap memakai keadaan
+    /// sekarang karena hanya transaksi yang dicatat sebagai peristiwa.
+    fn as_recorded(&mut self, date: NaiveDate) -> Result<WalletList, WalletError> {
+        let until = date
+            .and_hms_milli_opt(23, 59, 59, 999)
+            .and_then(|end| end.and_local_timezone(Local).latest())
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.date", date = date)))?;
+        let items = self.replay(Some(until))?;
+        Ok(WalletList {
+            data: WalletData {
+                items,
+                history: Default::default(),
+                ..self.data.clone()
+            },
+            storage: Box::new(MemoryStorage::default()),
+        })
+    }
+
+    /// Membandingkan transaksi tersimpan dengan hasil replay seluruh log
+    /// peristiwa dan mengembalikan bedanya. Jika `apply`, transaksi diganti
+    /// dengan hasil replay tanpa menambah peristiwa baru.
+    fn rebuild(&mut self, apply: bool) -> Result<Vec<Revision>, WalletError> {
+        let items = self.replay(None)?;
+        let revisions = changed_items(&self.data.items, &items);
+        if !apply || revisions.is_empty() {
+            return Ok(revisions);
+        }
+
+        let snapshot = self.data.clone();
+        let max_id = items.iter().map(|item| item.id.0).max().unwrap_or(0);
+        self.data.next_id = self.data.next_id.max(max_id + 1);
+        self.data.items = items;
+        let log = LogEntries {
+            audit: AuditEntry::new(
+                AuditAction::Rebuild,
+                revisions.clone(),
+                &snapshot,
+                &self.data,
+            ),
+            ..Default::default()
+        };
+        if let Err(e) = self.storage.save(&self.data, &log) {
+            self.data = snapshot;
+            return Err(e);
+        }
+        Ok(revisions)
+    }
+
+    fn categories(&self) -> &CategoryList {
+        &self.data.categories
+    }
+
+    fn accounts(&self) -> &AccountList {
+        &self.data.accounts
+    }
+
+    /// Nama akun transaksi, misalnya "BCA" atau "BCA > GoPay" untuk transfer.
+    fn account_label(&self, items: &WalletItems) -> String {
+        let from = self.data.accounts.name(items.account);
+        match (&items.transaction_type, items.to_account) {
+            (TypeTransaction::Journal, _) => {
+                let mut names: Vec<&str> = Vec::new();
+                for posting in &items.postings {
+                    if let LedgerAccount::Account(id) = posting.account {
+                        let name = self.data.accounts.name(id);
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                }
+                names.join(", ")
+            }
+            (_, Some(to)) => format!("{} > {}", from, self.data.accounts.name(to)),
+            (_, None) => from.to_string(),
+        }
+    }
+
+    /// Nama akun buku besar, misalnya "BCA" atau "Beban: Makanan".
+    fn ledger_label(&self, account: LedgerAccount) -> String {
+        match account {
+            LedgerAccount::Account(id) => self.data.accounts.name(id).to_string(),
+            LedgerAccount::Income(category) => {
+                t!("ledger.income", category = self.category_label(category))
+            }
+            LedgerAccount::Expense(category) => {
+                t!("ledger.expense", category = self.category_label(category))
+            }
+            LedgerAccount::Equity => t!("ledger.equity"),
+            LedgerAccount::Exchange => t!("ledger.exchange"),
+        }
+    }
+
+    /// Mencari akun buku besar dari nama: nama akun ("BCA"), "pendapatan:
+    /// Gaji", "beban: Makanan > Jajan", atau "ekuitas". Kategori boleh
+    /// dikosongkan untuk pendapatan/beban tanpa kategori.
+    fn parse_ledger_account(&self, input: &str) -> Result<LedgerAccount, WalletError> {
+        let input = input.trim();
+        let category = |path: &str| match path.trim() {
+            "" | "-" => Ok(None),
+            path => self.data.categories.find_path(path).map(Some),
+        };
+
+        match input.split_once(':') {
+            Some((prefix, path)) => match prefix.trim().to_lowercase().as_str() {
+                "pendapatan" | "income" => Ok(LedgerAccount::Income(category(path)?)),
+                "beban" | "expense" => Ok(LedgerAccount::Expense(category(path)?)),
+                _ => Err(WalletError::InvalidInput(t!(
+                    "error.unknown_ledger_account",
+                    account = input
+                ))),
+            },
+            None if input.eq_ignore_ascii_case("ekuitas")
+                || input.eq_ignore_ascii_case("equity") =>
+            {
+                Ok(LedgerAccount::Equity)
+            }
+            None => self.data.accounts.find(input).map(LedgerAccount::Account),
+        }
+    }
+
+    fn category_label(&self, category: Option<CategoryId>) -> String {
+        category
+            .and_then(|id| self.data.categories.path(id))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    fn position_of(&self, id: TransactionId) -> Result<usize, WalletError> {
+        self.data
+            .items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.transaction_not_found", id = id)))
+    }
+
+    fn get(&self, id: TransactionId) -> Option<&WalletItems> {
+        self.data.items.iter().find(|item| item.id == id)
+    }
+
+    /// Menerjemahkan input pengguna menjadi ID transaksi. Input bisa berupa
+    /// ID (`T7`) atau nomor baris pada tabel riwayat (`3`).
+    fn resolve(&self, input: &str) -> Result<TransactionId, WalletError> {
+        let input = input.trim();
+        if let Ok(row) = input.parse::<usize>() {
+            return row
+                .checked_sub(1)
+                .and_then(|i| self.data.items.get(i))
+                .map(|item| item.id)
+                .ok_or_else(|| WalletError::InvalidInput(t!("error.transaction_row", row = row)));
+        }
+
+        let id = input.parse::<TransactionId>()?;
+        self.position_of(id)?;
+        Ok(id)
+    }
+
+    /// Saldo satu akun dalam mata uangnya, atau saldo bersih semua akun
+    /// dalam mata uang laporan (dengan kurs hari ini) jika `account` adalah
+    /// `None`.
+    fn calculate_balance(&self, account: Option<AccountId>) -> Result<Money, WalletError> {
+        match account {
+            Some(id) => self.data.balance(id),
+            None => self.data.net_balances()?.into_iter().try_fold(
+                Money::ZERO,
+                |total, (currency, amount)| {
+                    total.checked_add(self.to_reporting(amount, currency, today())?)
+                },
+            ),
+        }
+    }
+
+    /// Seperti `calculate_balance`, tetapi hanya dari transaksi dengan
+    /// tanggal pembukuan paling lambat `date`. Saldo bersih gabungan
+    /// dikonversi dengan kurs pada tanggal itu.
+    fn balance_as_of(
+        &self,
+        account: Option<AccountId>,
+        date: NaiveDate,
+    ) -> Result<Money, WalletError> {
+        let items = self.data.items_until(date);
+        match account {
+            Some(id) => account_total(items, id),
+            None => {
+                net_totals(items)?
+                    .into_iter()
+                    .try_fold(Money::ZERO, |total, (currency, amount)| {
+                        total.checked_add(self.to_reporting(amount, currency, date)?)
+                    })
+            }
+        }
+    }
+
+    /// Saldo setelah setiap transaksi, dalam urutan riwayat dan mata uang
+    /// yang sama dengan `calculate_balance(account)`, sehingga saldo baris
+    /// terakhir sama dengan saldo saat ini.
+    fn running_balances(&self, account: Option<AccountId>) -> Result<Vec<Money>, WalletError> {
+        let today = today();
+        let mut balance = Money::ZERO;
+        let mut totals: Vec<(Currency, Money)> = Vec::new();
+        self.data
+            .items
+            .iter()
+            .map(|items| match account {
+                Some(id) => {
+                    balance = balance.checked_add(items.signed_amount(id)?)?;
+                    Ok(balance)
+                }
+                None => {
+                    add_net_change(&mut totals, items)?;
+                    totals
+                        .iter()
+                        .try_fold(Money::ZERO, |total, (currency, amount)| {
+                            total.checked_add(self.to_reporting(*amount, *currency, today)?)
+                        })
+                }
+            })
+            .collect()
+    }
+
+    /// `running_balances` per ID transaksi, untuk daftar yang sudah difilter.
+    /// Kosong jika saldo gabungan membutuhkan kurs yang belum ada.
+    fn running_balances_by_id(
+        &self,
+        account: Option<AccountId>,
+    ) -> Result<BTreeMap<TransactionId, Money>, WalletError> {
+        match self.running_balances(account) {
+            Ok(running) => Ok(self
+                .data
+                .items
+                .iter()
+                .map(|items| items.id)
+                .zip(running)
+                .collect()),
+            Err(WalletError::MissingRate(_)) => Ok(BTreeMap::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Mata uang hasil `calculate_balance(account)`.
+    fn balance_currency(&self, account: Option<AccountId>) -> Currency {
+        match account {
+            Some(id) => self.data.accounts.currency(id),
+            None => self.data.reporting_currency,
+        }
+    }
+
+    fn reporting_currency(&self) -> Currency {
+        self.data.reporting_currency
+    }
+
+    fn set_reporting_currency(&mut self, currency: Currency) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Settings, |data| {
+            data.reporting_currency = currency;
+            Ok(())
+        })
+    }
+
+    /// Pengaturan tampilan angka dan uang milik pengguna.
+    fn number_format(&self) -> NumberFormat {
+        self.data.format
+    }
+
+    fn set_number_format(&mut self, format: NumberFormat) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Settings, |data| {
+            data.format = format;
+            Ok(())
+        })
+    }
+
+    /// Bahasa antarmuka yang disimpan, jika pengguna pernah memilihnya.
+    fn language(&self) -> Option<Language> {
+        self.data.language
+    }
+
+    /// Menyimpan bahasa antarmuka dan langsung memakainya.
+    fn set_language(&mut self, language: Language) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Settings, |data| {
+            data.language = Some(language);
+            Ok(())
+        })?;
+        i18n::set_language(language);
+        Ok(())
+    }
+
+    fn rates(&self) -> &RateTable {
+        &self.data.rates
+    }
+
+    /// Menambah atau mengganti kurs. Mengembalikan `true` jika kurs lama diganti.
+    fn set_rate(&mut self, rate: ExchangeRate) -> Result<bool, WalletError> {
+        self.mutate(AuditAction::Rate, |data| Ok(data.rates.set(rate)))
+    }
+
+    fn remove_rate(
+        &mut self,
+        date: NaiveDate,
+        from: Currency,
+        to: Currency,
+    ) -> Result<ExchangeRate, WalletError> {
+        self.mutate(AuditAction::Rate, |data| data.rates.remove(date, from, to))
+    }
+
+    /// Menyimpan banyak kurs sekaligus. Mengembalikan jumlah kurs baru dan
+    /// jumlah kurs lama yang diganti.
+    fn import_rates(&mut self, rates: Vec<ExchangeRate>) -> Result<(usize, usize), WalletError> {
+        self.mutate(AuditAction::Rate, |data| {
+            let total = rates.len();
+            let replaced = rates
+                .into_iter()
+                .map(|rate| data.rates.set(rate))
+                .filter(|replaced| *replaced)
+                .count();
+            Ok((total - replaced, replaced))
+        })
+    }
+
+    /// Mengubah jumlah ke mata uang laporan memakai kurs pada `date`.
+    fn to_reporting(
+        &self,
+        amount: Money,
+        currency: Currency,
+        date: NaiveDate,
+    ) -> Result<Money, WalletError> {
+        self.data
+            .rates
+            .convert(amount, currency, self.data.reporting_currency, date)
+    }
+
+    /// Menampilkan riwayat transaksi satu akun, atau semua akun jika
+    /// `account` adalah `None`. Nomor baris selalu mengikuti riwayat lengkap
+    /// agar tetap bisa dipakai untuk memperbarui atau menghapus transaksi.
+    fn show(&self, account: Option<AccountId>) -> Result<(), WalletError> {
+        let involves = |items: &WalletItems| account.is_none_or(|id| items.involves(id));
+
+        outln!();
+        match account {
+            Some(id) => outln!(
+                "   {}",
+                t!(
+                    "history.title_account",
+                    account = self.data.accounts.name(id).to_uppercase()
+                )
+            ),
+            None => outln!("   {}", t!("history.title")),
+        }
+        outln!("   {}", "─".repeat(158));
+
+        if !self.data.items.iter().any(involves) {
+            outln!("   {}", t!("history.empty"));
+            outln!("   {}", t!("history.empty_hint"));
+        } else {
+            // Saldo berjalan mengikuti saldo akun yang ditampilkan, atau saldo
+            // bersih semua akun; tanpa kurs untuk saldo gabungan kolomnya kosong.
+            let running = match self.running_balances(account) {
+                Ok(running) => Some(running),
+                Err(WalletError::MissingRate(_)) => None,
+                Err(e) => return Err(e),
+            };
+            let currency = self.balance_currency(account);
+
+            outln!(
+                "   {:>3} | {:>6} | {:>10} | {:>12} | {:>15} | {:>17} | {:<16} | {:<24} | {}",
+                t!("column.no"),
+                t!("column.id"),
+                t!("column.date"),
+                t!("column.type"),
+                t!("column.amount"),
+                t!("column.running_balance"),
+                t!("column.account"),
+                t!("column.category"),
+                t!("column.description")
+            );
+            outln!("   {}", "─".repeat(158));
+
+            for (i, items) in self.data.items.iter().enumerate() {
+                if !involves(items) {
+                    continue;
+                }
+
+                let desc_truncated = truncate(&items.description_with_tags(), 35);
+                let category = truncate(&self.category_label(items.category), 24);
+                let balance = running
+                    .as_ref()
+                    .map(|running| self.data.format.brief(running[i], currency))
+                    .unwrap_or_else(|| "-".to_string());
+
+                outln!(
+                    "   {:>3} | {:>6} | {:>10} | {:>12} | {:>15} | {:>17} | {:<16} | {:<24} | {}",
+                    i + 1,
+                    items.id,
+                    items.date.format(DATE_FORMAT).to_string(),
+                    items.transaction_type,
+                    items.amount_label(account, &self.data.format),
+                    balance,
+                    truncate(&self.account_label(items), 16),
+                    category,
+                    desc_truncated
+                );
+            }
+
+            outln!("   {}", "─".repeat(158));
+            let balance = match self.calculate_balance(account) {
+                Ok(balance) => balance,
+                // Riwayat tetap bisa dilihat meskipun kurs untuk saldo gabungan belum ada.
+                Err(WalletError::MissingRate(msg)) => {
+                    outln!("   {}", t!("history.total_unavailable", msg = msg));
+                    outln!();
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+
+            let balance_status = if balance.is_positive() {
+                t!("status.surplus")
+            } else if balance.is_zero() {
+                t!("status.balanced")
+            } else {
+                t!("status.deficit")
+            };
+
+            outln!(
+                "   {}",
+                t!(
+                    "history.total",
+                    balance = self
+                        .data
+                        .format
+                        .money(balance.abs(), self.balance_currency(account)),
+                    status = balance_status
+                )
+            );
+        }
+        outln!();
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        items: WalletItems,
+        overdraft_confirmed: bool,
+    ) -> Result<TransactionId, WalletError> {
+        self.mutate(AuditAction::Add, |data| {
+            let id = data.insert(items, overdraft_confirmed)?;
+            let revision = data.revision(id, None);
+            data.history.record(ChangeKind::Add, vec![revision]);
+            Ok(id)
+        })
+    }
+
+    /// Mengimpor banyak transaksi sekaligus dengan satu kali simpan, dan
+    /// menggabungkan baris duplikat ke transaksi `merges`. Hasil per
+    /// transaksi dikembalikan sesuai urutan masukan; transaksi yang gagal
+    /// (misalnya saldo tidak cukup) tidak menggagalkan yang lain. Seluruh
+    /// impor dibatalkan sebagai satu langkah.
+    fn import(
+        &mut self,
+        items: Vec<WalletItems>,
+        merges: Vec<(TransactionId, WalletItems)>,
+        overdraft_confirmed: bool,
+    ) -> Result<Vec<Result<TransactionId, WalletError>>, WalletError> {
+        let positions = merges
+            .iter()
+            .map(|(id, _)| self.position_of(*id))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.mutate(AuditAction::Import, |data| {
+            let mut revisions = Vec::new();
+            for (position, (id, items)) in positions.into_iter().zip(merges) {
+                let before = data.items[position].clone();
+                data.merge_at(position, items);
+                revisions.push(data.revision(id, Some(before)));
+            }
+            let results: Vec<_> = items
+                .into_iter()
+                .map(|item| data.insert(item, overdraft_confirmed))
+                .collect();
+            for id in results.iter().flatten() {
+                revisions.push(data.revision(*id, None));
+            }
+            data.history.record(ChangeKind::Import, revisions);
+            Ok(results)
+        })
+    }
+
+    /// Seperti `import` tanpa penggabungan, tetapi hanya mensimulasikan tanpa mengubah data.
+    fn preview_many(
+        &self,
+        items: Vec<WalletItems>,
+        overdraft_confirmed: bool,
+    ) -> Vec<Result<TransactionId, WalletError>> {
+        let mut data = self.data.clone();
+        items
+            .into_iter()
+            .map(|item| data.insert(item, overdraft_confirmed))
+            .collect()
+    }
+
+    fn update(
+        &mut self,
+        id: TransactionId,
+        mut items: WalletItems,
+        overdraft_confirmed: bool,
+    ) -> Result<(), WalletError> {
+        let position = self.position_of(id)?;
+        self.mutate(AuditAction::Update, |data| {
+            data.check_accounts(&mut items)?;
+            let before = data.items[position].clone();
+            let old = &data.items[position];
+            data.check_funds(
+                |account| {
+                    items
+                        .signed_amount(account)?
+                        .checked_sub(old.signed_amount(account)?)
+                },
+                overdraft_confirmed,
+            )?;
+
+            let item = &mut data.items[position];
+            item.description = items.description;
+            item.amount = items.amount;
+            item.currency = items.currency;
+            item.transaction_type = items.transaction_type;
+            item.date = items.date;
+            item.category = items.category;
+            item.tags = items.tags;
+            item.account = items.account;
+            item.to_account = items.to_account;
+            item.to_amount = items.to_amount;
+            item.to_currency = items.to_currency;
+            item.postings = items.postings;
+            item.modified_at = Local::now();
+            data.items.sort_by_key(|item| item.date);
+            let revision = data.revision(id, Some(before));
+            data.history.record(ChangeKind::Update, vec![revision]);
+            Ok(())
+        })
+    }
+
+    /// Menggabungkan transaksi baru ke transaksi yang sudah ada; lihat
+    /// `WalletData::merge_at`.
+    fn merge(&mut self, id: TransactionId, items: WalletItems) -> Result<(), WalletError> {
+        let position = self.position_of(id)?;
+        self.mutate(AuditAction::Update, |data| {
+            let before = data.items[position].clone();
+            data.merge_at(position, items);
+            let revision = data.revision(id, Some(before));
+            data.history.record(ChangeKind::Update, vec![revision]);
+            Ok(())
+        })
+    }
+
+    /// Perubahan yang akan dibatalkan dan yang akan diulang berikutnya.
+    fn history(&self) -> (Option<&Change>, Option<&Change>) {
+        (self.data.history.next_undo(), self.data.history.next_redo())
+    }
+
+    /// Membatalkan perubahan transaksi terakhir. Mengembalikan perubahan yang
+    /// dibatalkan.
+    fn undo(&mut self, overdraft_confirmed: bool) -> Result<Change, WalletError> {
+        self.mutate(AuditAction::Undo, |data| {
+            let change = data.history.undo()?;
+            for revision in change.revisions.iter().rev() {
+                data.restore(
+                    revision.id,
+                    revision.after.as_ref(),
+                    revision.before.as_ref(),
+                    overdraft_confirmed,
+                )?;
+            }
+            Ok(change)
+        })
+    }
+
+    /// Mengulang perubahan yang terakhir dibatalkan.
+    fn redo(&mut self, overdraft_confirmed: bool) -> Result<Change, WalletError> {
+        self.mutate(AuditAction::Redo, |data| {
+            let change = data.history.redo()?;
+            for revision in &change.revisions {
+                data.restore(
+                    revision.id,
+                    revision.before.as_ref(),
+                    revision.after.as_ref(),
+                    overdraft_confirmed,
+                )?;
+            }
+            Ok(change)
+        })
+    }
+
+    /// Transaksi yang kemungkinan sama dengan `items`, yang tanggalnya
+    /// paling dekat lebih dulu.
+    fn find_duplicates(&self, items: &WalletItems) -> Vec<&WalletItems> {
+        let mut duplicates: Vec<&WalletItems> = self
+            .data
+            .items
+            .iter()
+            .filter(|item| item.id != items.id && is_likely_duplicate(item, items))
+            .collect();
+        duplicates.sort_by_key(|item| (item.date - items.date).num_days().abs());
+        duplicates
+    }
+
+    /// Transaksi di `account` yang berasal dari baris impor bersidik
+    /// `fingerprint`. Baris yang sama boleh diimpor ke akun lain, misalnya
+    /// biaya administrasi yang identik di dua bank.
+    fn find_fingerprint(&self, fingerprint: &str, account: AccountId) -> Option<TransactionId> {
+        self.data
+            .items
+            .iter()
+            .filter(|item| item.account == account)
+            .find(|item| item.fingerprints.iter().any(|f| f == fingerprint))
+            .map(|item| item.id)
+    }
+
+    fn filter(&self, filter: &TransactionFilter) -> Vec<WalletItems> {
+        self.data
+            .items
+            .iter()
+            .filter(|item| filter.matches(item))
+            .cloned()
+            .collect()
+    }
+
+    /// Total per tag dalam mata uang laporan, diurutkan dari pengeluaran
+    /// terbesar. Satu transaksi dengan beberapa tag dihitung di setiap tag-nya.
+    fn tag_report(&self) -> Result<Vec<TagTotal>, WalletError> {
+        let mut totals: Vec<TagTotal> = Vec::new();
+
+        for item in &self.data.items {
+            for tag in &item.tags {
+                let entry = match totals.iter().position(|t| &t.tag == tag) {
+                    Some(i) => &mut totals[i],
+                    None => {
+                        totals.push(TagTotal {
+                            tag: tag.clone(),
+                            count: 0,
+                            income: Money::ZERO,
+                            expense: Money::ZERO,
+                        });
+                        totals.last_mut().unwrap()
+                    }
+                };
+                let (income, expense) = item.income_expense()?;
+                let income = self.to_reporting(income, item.currency, item.date)?;
+                let expense = self.to_reporting(expense, item.currency, item.date)?;
+                entry.count += 1;
+                entry.income = entry.income.checked_add(income)?;
+                entry.expense = entry.expense.checked_add(expense)?;
+            }
+        }
+
+        totals.sort_by(|a, b| b.expense.cmp(&a.expense).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(totals)
+    }
+
+    /// Menampilkan saldo semua akun saat ini, atau per akhir tanggal
+    /// pembukuan `as_of` untuk dicocokkan dengan rekening koran.
+    fn total(&self, as_of: Option<NaiveDate>) -> Result<(), WalletError> {
+        let balance_of = |account: Option<AccountId>| match as_of {
+            Some(date) => self.balance_as_of(account, date),
+            None => self.calculate_balance(account),
+        };
+        let balance = balance_of(None)?;
+
+        outln!();
+        match as_of {
+            Some(date) => outln!(
+                "   {}",
+                t!("balance.title_as_of", date = date.format(DATE_FORMAT))
+            ),
+            None => outln!("   {}", t!("balance.title")),
+        }
+        outln!("   {}", "─".repeat(50));
+        outln!();
+
+        for account in self.data.accounts.iter() {
+            let balance = balance_of(Some(account.id))?;
+            outln!(
+                "   {:<28} {:>19}",
+                truncate(&account.name, 28),
+                self.data.format.money(balance, account.currency)
+            );
+        }
+        outln!("   {}", "─".repeat(50));
+
+        // Dengan lebih dari satu mata uang, saldo bersih dihitung per mata
+        // uang lalu dikonversi dengan kurs hari ini (atau kurs `as_of`).
+        let net_balances = match as_of {
+            Some(date) => net_totals(self.data.items_until(date))?,
+            None => self.data.net_balances()?,
+        };
+        if net_balances.len() > 1 {
+            for (currency, amount) in &net_balances {
+                outln!(
+                    "   {:<28} {:>19}",
+                    t!("balance.net_currency", currency = currency),
+                    self.data.format.money(*amount, *currency)
+                );
+            }
+            outln!("   {}", "─".repeat(50));
+        }
+
+        let status_text = if balance.is_positive() {
+            t!("status.profit")
+        } else if balance.is_zero() {
+            t!("status.balanced")
+        } else {
+            t!("status.loss")
+        };
+
+        outln!(
+            "   {}",
+            t!(
+                "balance.net_all",
+                amount = self
+                    .data
+                    .format
+                    .signed(balance, self.data.reporting_currency)
+            )
+        );
+        outln!("   {}", t!("balance.status", status = status_text));
+        outln!();
+        Ok(())
+    }
+
+    /// Total pemasukan, total pengeluaran dan selisihnya dalam mata uang
+    /// laporan. Setiap transaksi dikonversi dengan kurs pada tanggalnya.
+    fn get_summary(&self) -> Result<(Money, Money, Money), WalletError> {
+        let mut total_income = Money::ZERO;
+        let mut total_expense = Money::ZERO;
+
+        for transaction in &self.data.items {
+            let (income, expense) = transaction.income_expense()?;
+            let (currency, date) = (transaction.currency, transaction.date);
+            total_income = total_income.checked_add(self.to_reporting(income, currency, date)?)?;
+            total_expense =
+                total_expense.checked_add(self.to_reporting(expense, currency, date)?)?;
+        }
+
+        Ok((
+            total_income,
+            total_expense,
+            total_income.checked_sub(total_expense)?,
+        ))
+    }
+
+    /// Total pemasukan dan pengeluaran per mata uang asli, tanpa konversi.
+    fn summary_by_currency(&self) -> Result<Vec<CurrencyTotal>, WalletError> {
+        let mut totals: Vec<CurrencyTotal> = Vec::new();
+        for transaction in &self.data.items {
+            let (income, expense) = transaction.income_expense()?;
+            if income.is_zero() && expense.is_zero() {
+                continue;
+            }
+            let entry = match totals
+                .iter()
+                .position(|t| t.currency == transaction.currency)
+            {
+                Some(i) => &mut totals[i],
+                None => {
+                    totals.push(CurrencyTotal {
+                        currency: transaction.currency,
+                        income: Money::ZERO,
+                        expense: Money::ZERO,
+                    });
+                    totals.last_mut().unwrap()
+                }
+            };
+            entry.income = entry.income.checked_add(income)?;
+            entry.expense = entry.expense.checked_add(expense)?;
+        }
+        totals.sort_by_key(|t| t.currency);
+        Ok(totals)
+    }
+
+    /// Total pemasukan dan pengeluaran per periode dalam mata uang laporan,
+    /// hanya dari transaksi dalam `range`. Periode kosong di antara periode
+    /// pertama dan terakhir tetap dicantumkan agar perubahannya terbaca.
+    fn period_report(
+        &self,
+        period: Period,
+        range: DateRange,
+    ) -> Result<Vec<PeriodTotal>, WalletError> {
+        let mut totals: BTreeMap<NaiveDate, (Money, Money)> = BTreeMap::new();
+        for transaction in self.data.items.iter().filter(|t| range.contains(t.date)) {
+            let (income, expense) = transaction.income_expense()?;
+            if income.is_zero() && expense.is_zero() {
+                continue;
+            }
+            let (currency, date) = (transaction.currency, transaction.date);
+            let entry = totals.entry(period.start_of(date)).or_default();
+            entry.0 = entry
+                .0
+                .checked_add(self.to_reporting(income, currency, date)?)?;
+            entry.1 = entry
+                .1
+                .checked_add(self.to_reporting(expense, currency, date)?)?;
+        }
+
+        let (Some(&first), Some(&last)) = (totals.keys().next(), totals.keys().next_back()) else {
+            return Ok(Vec::new());
+        };
+        let mut report = Vec::new();
+        let mut start = Some(first);
+        while let Some(current) = start.filter(|start| *start <= last) {
+            let (income, expense) = totals.get(&current).copied().unwrap_or_default();
+            report.push(PeriodTotal {
+                start: current,
+                income,
+                expense,
+            });
+            start = period.next(current);
+        }
+        Ok(report)
+    }
+
+    fn schedules(&self) -> &ScheduleList {
+        &self.data.schedules
+    }
+
+    /// Menambah transaksi berulang. Akun dan mata uang contohnya diperiksa
+    /// sekarang; saldo baru diperiksa saat setiap kejadian dicatat.
+    fn add_schedule(
+        &mut self,
+        mut template: WalletItems,
+        rule: Recurrence,
+    ) -> Result<ScheduleId, WalletError> {
+        self.mutate(AuditAction::Recurring, |data| {
+            data.check_accounts(&mut template)?;
+            template.id = TransactionId::default();
+            Ok(data
+                .schedules
+                .add(&mut data.next_schedule_id, template, rule))
+        })
+    }
+
+    /// Menghapus transaksi berulang; transaksi yang sudah dicatat tetap ada.
+    fn remove_schedule(&mut self, id: ScheduleId) -> Result<Schedule, WalletError> {
+        self.mutate(AuditAction::Recurring, |data| data.schedules.remove(id))
+    }
+
+    /// Kejadian yang belum diproses sampai `until` dari semua transaksi
+    /// berulang, urut tanggal.
+    fn scheduled_until(&self, until: NaiveDate) -> Vec<(ScheduleId, NaiveDate)> {
+        let mut occurrences: Vec<(ScheduleId, NaiveDate)> = self
+            .data
+            .schedules
+            .iter()
+            .flat_map(|s| s.dates_until(until).map(move |date| (s.id, date)))
+            .collect();
+        occurrences.sort_by_key(|(_, date)| *date);
+        occurrences
+    }
+
+    /// Mencatat kejadian berikutnya dari transaksi berulang `id` sebagai
+    /// transaksi biasa.
+    fn post_occurrence(
+        &mut self,
+        id: ScheduleId,
+        overdraft_confirmed: bool,
+    ) -> Result<TransactionId, WalletError> {
+        self.mutate(AuditAction::Recurring, |data| {
+            let schedule = data.schedules.get(id)?;
+            let date = schedule.next_date().ok_or_else(|| {
+                WalletError::InvalidInput(t!("error.recurring_finished", id = id))
+            })?;
+            let transaction = data.insert(schedule.transaction(date), overdraft_confirmed)?;
+            data.schedules.get_mut(id)?.done += 1;
+            Ok(transaction)
+        })
+    }
+
+    /// Melewati kejadian berikutnya tanpa mencatatnya. Mengembalikan tanggal
+    /// kejadian yang dilewati.
+    fn skip_occurrence(&mut self, id: ScheduleId) -> Result<NaiveDate, WalletError> {
+        self.mutate(AuditAction::Recurring, |data| {
+            let schedule = data.schedules.get_mut(id)?;
+            let date = schedule.next_date().ok_or_else(|| {
+                WalletError::InvalidInput(t!("error.recurring_finished", id = id))
+            })?;
+            schedule.done += 1;
+            Ok(date)
+        })
+    }
+
+    fn goals(&self) -> &GoalList {
+        &self.data.goals
+    }
+
+    fn add_goal(&mut self, goal: Goal) -> Result<(), WalletError> {
+        if let GoalLink::Account(id) = goal.link
+            && self.data.accounts.get(id).is_none()
+        {
+            return Err(WalletError::InvalidInput(t!("error.account_not_found")));
+        }
+        self.mutate(AuditAction::Goal, |data| data.goals.add(goal))
+    }
+
+    fn remove_goal(&mut self, name: &str) -> Result<Goal, WalletError> {
+        self.mutate(AuditAction::Goal, |data| data.goals.remove(name))
+    }
+
+    /// Transaksi yang menjadi setoran `goal`, urut tanggal, dengan jumlah
+    /// dalam mata uang tujuan. Untuk tujuan bertag, pemasukan menambah dan
+    /// pengeluaran mengurangi tabungan, sedangkan transfer dihitung sebagai
+    /// dana yang disisihkan ke akun tujuannya.
+    fn goal_contributions(&self, goal: &Goal) -> Result<Vec<Contribution>, WalletError> {
+        let mut contributions = Vec::new();
+        for item in &self.data.items {
+            let (amount, currency) = match &goal.link {
+                GoalLink::Account(id) if item.involves(*id) => {
+                    (item.signed_amount(*id)?, self.data.accounts.currency(*id))
+                }
+                GoalLink::Tag(tag) if item.tags.contains(tag) => {
+                    match (&item.transaction_type, item.to_account) {
+                        (TypeTransaction::Transfer, Some(to)) => {
+                            (item.signed_amount(to)?, self.data.accounts.currency(to))
+                        }
+                        _ => (item.net_change()?, item.currency),
+                    }
+                }
+                _ => continue,
+            };
+            if amount.is_zero() {
+                continue;
+            }
+            contributions.push(Contribution {
+                date: item.date,
+                transaction: item.id,
+                description: item.description.clone(),
+                amount: self
+                    .data
+                    .rates
+                    .convert(amount, currency, goal.currency, item.date)?,
+                opening: item
+                    .postings()
+                    .iter()
+                    .any(|p| p.account == LedgerAccount::Equity),
+            });
+        }
+        Ok(contributions)
+    }
+
+    fn goal_progress(&self, goal: &Goal) -> Result<GoalProgress, WalletError> {
+        GoalProgress::new(goal, self.goal_contributions(goal)?, today())
+    }
+
+    fn budgets(&self) -> &BudgetList {
+        &self.data.budgets
+    }
+
+    /// Menetapkan anggaran bulanan sebuah kategori dalam mata uang laporan.
+    /// Mengembalikan `true` jika anggaran lama diganti.
+    fn set_budget(&mut self, category: CategoryId, limit: Money) -> Result<bool, WalletError> {
+        let currency = self.data.reporting_currency;
+        self.mutate(AuditAction::Budget, |data| {
+            data.budgets.set(Budget {
+                category,
+                limit,
+                currency,
+            })
+        })
+    }
+
+    fn remove_budget(&mut self, category: CategoryId) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Budget, |data| {
+            data.budgets
+                .remove(category)
+                .map(|_| ())
+                .ok_or_else(|| WalletError::InvalidInput(t!("error.budget_missing")))
+        })
+    }
+
+    fn set_budget_threshold(&mut self, percent: u8) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Budget, |data| {
+            data.budgets.set_threshold(percent)
+        })
+    }
+
+    /// Pengeluaran `items` yang masuk ke anggaran `budget` dalam rentang
+    /// `range`, dikonversi ke mata uang anggaran.
+    fn budget_spending<'a>(
+        &self,
+        budget: &Budget,
+        items: impl IntoIterator<Item = &'a WalletItems>,
+        range: DateRange,
+    ) -> Result<Money, WalletError> {
+        let categories = &self.data.categories;
+        let counts = |id: CategoryId| {
+            id == budget.category
+                || categories.get(id).and_then(|c| c.parent) == Some(budget.category)
+        };
+
+        let mut spent = Money::ZERO;
+        for item in items.into_iter().filter(|item| range.contains(item.date)) {
+            for posting in item.postings() {
+                if let LedgerAccount::Expense(Some(id)) = posting.account
+                    && counts(id)
+                {
+                    let amount = self.data.rates.convert(
+                        posting.amount,
+                        posting.currency,
+                        budget.currency,
+                        item.date,
+                    )?;
+                    spent = spent.checked_add(amount)?;
+                }
+            }
+        }
+        Ok(spent)
+    }
+
+    /// Pemakaian setiap anggaran pada bulan yang memuat `month`, dalam
+    /// urutan pohon kategori.
+    fn budget_status(&self, month: NaiveDate) -> Result<Vec<BudgetStatus>, WalletError> {
+        let start = Period::Month.start_of(month);
+        let range = DateRange::new(Some(start), Some(Period::Month.end_of(start)))?;
+        let order = self.data.categories.tree();
+        let mut budgets: Vec<&Budget> = self.data.budgets.iter().collect();
+        budgets.sort_by_key(|b| order.iter().position(|c| c.id == b.category));
+
+        budgets
+            .into_iter()
+            .map(|budget| {
+                Ok(BudgetStatus {
+                    category: budget.category,
+                    limit: budget.limit,
+                    spent: self.budget_spending(budget, &self.data.items, range)?,
+                    currency: budget.currency,
+                })
+            })
+            .collect()
+    }
+
+    /// Anggaran yang baru saja melewati batas peringatan atau terlampaui
+    /// karena transaksi `item`, yang sudah tersimpan.
+    fn budget_alerts(&self, item: &WalletItems) -> Result<Vec<BudgetStatus>, WalletError> {
+        let threshold = self.data.budgets.threshold();
+        let range = DateRange::default();
+        let mut alerts = Vec::new();
+        for status in self.budget_status(item.date)? {
+            let Some(budget) = self
+                .data
+                .budgets
+                .iter()
+                .find(|b| b.category == status.category)
+            else {
+                continue;
+            };
+            let added = self.budget_spending(budget, [item], range)?;
+            if !added.is_positive() {
+                continue;
+            }
+            let before = BudgetStatus {
+                spent: status.spent.checked_sub(added)?,
+                ..status.clone()
+            };
+            let level = status.level(threshold);
+            if level == BudgetLevel::Over
+                || (level == BudgetLevel::Warning && before.level(threshold) == BudgetLevel::Safe)
+            {
+                alerts.push(status);
+            }
+        }
+        Ok(alerts)
+    }
+
+    /// Menghapus transaksi. Menghapus pemasukan juga bisa membuat saldo
+    /// minus, jadi kebijakan cerukan tetap diperiksa.
+    fn remove_transaction(
+        &mut self,
+        id: TransactionId,
+        overdraft_confirmed: bool,
+    ) -> Result<WalletItems, WalletError> {
+        let position = self.position_of(id)?;
+        self.mutate(AuditAction::Delete, |data| {
+            let item = &data.items[position];
+            data.check_funds(
+                |account| Ok(-item.signed_amount(account)?),
+                overdraft_confirmed,
+            )?;
+            let removed = data.items.remove(position);
+            data.history.record(
+                ChangeKind::Delete,
+                vec![Revision {
+                    id,
+                    before: Some(removed.clone()),
+                    after: None,
+                }],
+            );
+            Ok(removed)
+        })
+    }
+
+    /// Total per kategori utama untuk satu jenis transaksi dalam mata uang
+    /// laporan, diurutkan dari yang terbesar. Transaksi tanpa kategori
+    /// dikumpulkan di `category: None`.
+    fn category_breakdown(
+        &self,
+        transaction_type: &TypeTransaction,
+    ) -> Result<Vec<CategoryTotal>, WalletError> {
+        let categories = &self.data.categories;
+        let mut totals: Vec<CategoryTotal> = Vec::new();
+
+        // Dihitung dari posting agar jurnal yang dipecah ke beberapa kategori ikut terhitung.
+        let postings = self
+            .data
+            .items
+            .iter()
+            .flat_map(|item| item.postings().into_iter().map(|p| (item.date, p)));
+        for (date, category, amount, currency) in
+            postings.filter_map(|(date, p)| match (transaction_type, p.account) {
+                (TypeTransaction::Income, LedgerAccount::Income(category)) => {
+                    Some((date, category, -p.amount, p.currency))
+                }
+                (TypeTransaction::Expense, LedgerAccount::Expense(category)) => {
+                    Some((date, category, p.amount, p.currency))
+                }
+                _ => None,
+            })
+        {
+            let amount = self.to_reporting(amount, currency, date)?;
+            // Kategori yang sudah dihapus diperlakukan sebagai tanpa kategori.
+            let category = category.filter(|id| categories.get(*id).is_some());
+            let root = category.map(|id| categories.root_of(id));
+
+            let entry = match totals.iter_mut().position(|t| t.category == root) {
+                Some(i) => &mut totals[i],
+                None => {
+                    totals.push(CategoryTotal {
+                        category: root,
+                        total: Money::ZERO,
+                        children: Vec::new(),
+                    });
+                    totals.last_mut().unwrap()
+                }
+            };
+            entry.total = entry.total.checked_add(amount)?;
+
+            if let Some(id) = category.filter(|id| Some(*id) != root) {
+                match entry.children.iter_mut().find(|(child, _)| *child == id) {
+                    Some((_, total)) => *total = total.checked_add(amount)?,
+                    None => entry.children.push((id, amount)),
+                }
+            }
+        }
+
+        for entry in &mut totals {
+            entry
+                .children
+                .sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+        }
+        totals.sort_by_key(|entry| std::cmp::Reverse(entry.total));
+        Ok(totals)
+    }
+
+    fn add_category(
+        &mut self,
+        name: String,
+        parent: Option<CategoryId>,
+    ) -> Result<CategoryId, WalletError> {
+        self.mutate(AuditAction::Category, |data| {
+            data.categories.add(name, parent)
+        })
+    }
+
+    fn rename_category(&mut self, id: CategoryId, name: String) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Category, |data| {
+            data.categories.rename(id, name)
+        })
+    }
+
+    fn remove_category(&mut self, id: CategoryId) -> Result<(), WalletError> {
+        let used = self
+            .data
+            .items
+            .iter()
+            .filter(|item| item.category == Some(id))
+            .count();
+        if used > 0 {
+            return Err(WalletError::InvalidInput(t!(
+                "error.category_in_use",
+                count = used
+            )));
+        }
+        if let Some(schedule) = self
+            .data
+            .schedules
+            .iter()
+            .find(|s| s.template.category == Some(id))
+        {
+            return Err(WalletError::InvalidInput(t!(
+                "error.category_in_recurring",
+                id = schedule.id
+            )));
+        }
+        self.mutate(AuditAction::Category, |data| {
+            data.categories.remove(id)?;
+            data.budgets.remove(id);
+            Ok(())
+        })
+    }
+
+    /// Menambah akun. Saldo awal selain nol (dalam `currency`) dicatat
+    /// sebagai jurnal terhadap ekuitas; untuk liabilitas, saldo awal adalah
+    /// besar utangnya.
+    fn add_account(
+        &mut self,
+        name: String,
+        kind: AccountKind,
+        currency: Currency,
+        opening: Money,
+        overdraft: Option<OverdraftPolicy>,
+    ) -> Result<AccountId, WalletError> {
+        self.mutate(AuditAction::Account, |data| {
+            let id = data.accounts.add(name, kind, currency)?;
+            if !opening.is_zero() {
+                let account = LedgerAccount::Account(id);
+                let postings = match kind {
+                    AccountKind::Asset => vec![
+                        Posting::debit(account, opening, currency),
+                        Posting::credit(LedgerAccount::Equity, opening, currency),
+                    ],
+                    AccountKind::Liability => vec![
+                        Posting::debit(LedgerAccount::Equity, opening, currency),
+                        Posting::credit(account, opening, currency),
+                    ],
+                };
+                let description = t!(
+                    "account.opening_description",
+                    account = data.accounts.name(id)
+                );
+                data.insert(
+                    WalletItems::journal(description, today(), postings, Vec::new()),
+                    true,
+                )?;
+            }
+            // Saldo awal adalah fakta yang sudah terjadi, jadi kebijakan
+            // cerukan baru dipasang setelahnya.
+            if let Some(policy) = overdraft {
+                data.accounts.set_overdraft(id, policy)?;
+            }
+            Ok(id)
+        })
+    }
+
+    fn set_overdraft(&mut self, id: AccountId, policy: OverdraftPolicy) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Account, |data| {
+            data.accounts.set_overdraft(id, policy)
+        })
+    }
+
+    /// Neraca saldo: saldo setiap akun buku besar di sisi debit atau kredit,
+    /// dipisah per mata uang lalu diurutkan per golongan. Di setiap mata
+    /// uang, total debit selalu sama dengan total kredit.
+    fn trial_balance(&self) -> Result<Vec<TrialBalanceRow>, WalletError> {
+        let mut balances: Vec<(Currency, LedgerAccount, Money)> = Vec::new();
+        for posting in self.data.items.iter().flat_map(|item| item.postings()) {
+            match balances.iter_mut().find(|(currency, account, _)| {
+                *currency == posting.currency && *account == posting.account
+            }) {
+                Some((_, _, balance)) => *balance = balance.checked_add(posting.amount)?,
+                None => balances.push((posting.currency, posting.account, posting.amount)),
+            }
+        }
+
+        let mut rows: Vec<TrialBalanceRow> = balances
+            .into_iter()
+            .map(|(currency, account, balance)| TrialBalanceRow {
+                account,
+                currency,
+                class: match account {
+                    LedgerAccount::Account(id) => self.data.accounts.kind(id).into(),
+                    LedgerAccount::Income(_) => AccountClass::Income,
+                    LedgerAccount::Expense(_) => AccountClass::Expense,
+                    LedgerAccount::Equity | LedgerAccount::Exchange => AccountClass::Equity,
+                },
+                debit: balance.max(Money::ZERO),
+                credit: (-balance).max(Money::ZERO),
+            })
+            .collect();
+        rows.sort_by_key(|row| (row.currency, row.class, row.account));
+        Ok(rows)
+    }
+
+    /// Neraca saldo: saldo setiap akun buku besar di kolom debit atau
+    /// kredit, satu tabel per mata uang. Jika semua transaksi seimbang,
+    /// kedua total di setiap mata uang selalu sama.
+    fn show_trial_balance(&self) -> Result<(), WalletError> {
+        let rows = self.trial_balance()?;
+        let mut currencies: Vec<Currency> = rows.iter().map(|row| row.currency).collect();
+        currencies.dedup();
+        if currencies.is_empty() {
+            currencies.push(self.data.reporting_currency);
+        }
+
+        outln!();
+        outln!("   {}", t!("trial_balance.title"));
+        let mut balanced = true;
+        for currency in currencies {
+            let rows: Vec<&TrialBalanceRow> =
+                rows.iter().filter(|row| row.currency == currency).collect();
+
+            outln!("   {}", "─".repeat(86));
+            outln!(
+                "   {:<36} {:<12} {:>18} {:>18}",
+                t!("trial_balance.column_account", currency = currency),
+                t!("trial_balance.column_class"),
+                t!("trial_balance.column_debit"),
+                t!("trial_balance.column_credit")
+            );
+            outln!("   {}", "─".repeat(86));
+
+            let cell = |amount: Money| {
+                if amount.is_zero() {
+                    String::new()
+                } else {
+                    self.data.format.money(amount, currency)
+                }
+            };
+            for row in &rows {
+                outln!(
+                    "   {:<36} {:<12} {:>18} {:>18}",
+                    truncate(&self.ledger_label(row.account), 36),
+                    row.class,
+                    cell(row.debit),
+                    cell(row.credit)
+                );
+            }
+
+            let debit = Money::sum(rows.iter().map(|row| row.debit))?;
+            let credit = Money::sum(rows.iter().map(|row| row.credit))?;
+            balanced &= debit == credit;
+            outln!("   {}", "─".repeat(86));
+            outln!(
+                "   {:<49} {:>18} {:>18}",
+                format!("TOTAL {}", currency),
+                self.data.format.money(debit, currency),
+                self.data.format.money(credit, currency)
+            );
+        }
+        outln!(
+            "   {}",
+            t!(
+                "balance.status",
+                status = if balanced {
+                    t!("status.balanced")
+                } else {
+                    t!("status.unbalanced")
+                }
+            )
+        );
+        outln!();
+        Ok(())
+    }
+
+    fn rename_account(&mut self, id: AccountId, name: String) -> Result<(), WalletError> {
+        self.mutate(AuditAction::Account, |data| data.accounts.rename(id, name))
+    }
+
+    fn remove_account(&mut self, id: AccountId) -> Result<(), WalletError> {
+        let used = self
+            .data
+            .items
+            .iter()
+            .filter(|item| item.involves(id))
+            .count();
+        if used > 0 {
+            return Err(WalletError::InvalidInput(t!(
+                "error.account_in_use",
+                count = used
+            )));
+        }
+        if let Some(schedule) = self.data.schedules.iter().find(|s| s.template.involves(id)) {
+            return Err(WalletError::InvalidInput(t!(
+                "error.account_in_recurring",
+                id = schedule.id
+            )));
+        }
+        if let Some(goal) = self
+            .data
+            .goals
+            .iter()
+            .find(|g| g.link == GoalLink::Account(id))
+        {
+            return Err(WalletError::InvalidInput(t!(
+                "error.account_in_goal",
+                name = goal.name
+            )));
+        }
+        self.mutate(AuditAction::Account, |data| {
+            data.accounts.remove(id).map(|_| ())
+        })
+    }
+
+    /// Mencari berdasarkan kata kunci di deskripsi, atau ID persis (`T7`).
+    fn search_transaction(&self, description: &str) -> Vec<WalletItems> {
+        let id = description.parse::<TransactionId>().ok();
+        self.data
+            .items
+            .iter()
+            .filter(|item| {
+                Some(item.id) == id
+                    || item
+                        .description
+                        .to_lowercase()
+                        .contains(&description.to_lowercase())
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+struct WalletApp {
+    wallet: WalletList,
+    /// Akun yang sedang dilihat di menu; `None` berarti semua akun.
+    active_account: Option<AccountId>,
+}
+
+impl WalletApp {
+    fn new() -> Result<Self, WalletError> {
+        Ok(Self {
+            wallet: WalletList::with_storage(StorageConfig::from_env()?.open()?)?,
+            active_account: None,
+        })
+    }
+
+    fn clear_screen(&self) {
+        out!("\x1B[2J\x1B[1;1H");
+    }
+
+    fn print_header(&self) {
+        outln!();
+        outln!("   {}", t!("app.title"));
+        outln!("   {}", t!("app.tagline"));
+        outln!();
+    }
+
+    fn print_loading(&self, message: &str) {
+        out!("   {}", t!("app.loading", what = message));
+        thread::sleep(Duration::from_millis(500));
+        outln!(" {}", t!("app.loading_done"));
+    }
+
+    fn pause(&self) {
+        outln!("   {}", t!("app.press_enter"));
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+    }
+
+    fn run(&mut self) {
+        self.clear_screen();
+        self.print_header();
+
+        outln!("   {}", t!("app.welcome"));
+        outln!("   {}", t!("app.welcome_tagline"));
+        outln!();
+
+        thread::sleep(Duration::from_millis(1000));
+
+        match self.review_due_schedules() {
+            Ok(false) => {}
+            Ok(true) => self.pause(),
+            Err(e) => {
+                outln!("\n   [ERROR] {}", e);
+                self.pause();
+            }
+        }
+
+        loop {
+            self.clear_screen();
+            self.print_header();
+            self.show_menu();
+
+            match self.get_user_choice() {
+                Ok(choice) => {
+                    if !self.handle_choice(&choice) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    outln!("\n   [ERROR] {}", e);
+                    self.pause();
+                }
+            }
+        }
+    }
+
+    fn show_menu(&self) {
+        let balance = match self.wallet.calculate_balance(self.active_account) {
+            Ok(balance) => self
+                .wallet
+                .number_format()
+                .brief_signed(balance, self.wallet.balance_currency(self.active_account)),
+            Err(e) => e.to_string(),
+        };
+        let all_accounts = t!("menu.all_accounts");
+        let account = match self.active_account {
+            Some(id) => self.wallet.accounts().name(id),
+            None => &all_accounts,
+        };
+
+        outln!("   {}", t!("menu.title"));
+        outln!("   {}", "─".repeat(50));
+        outln!();
+        outln!("   {}", t!("menu.active_account", account = account));
+        outln!("   {}", t!("menu.current_balance", balance = balance));
+        outln!();
+        outln!("   1. {}", t!("menu.history"));
+        outln!("   2. {}", t!("menu.add"));
+        outln!("   3. {}", t!("menu.balance"));
+        outln!("   4. {}", t!("menu.summary"));
+        outln!("   5. {}", t!("menu.filter"));
+        outln!("   6. {}", t!("menu.delete"));
+        outln!("   7. {}", t!("menu.update"));
+        outln!("   8. {}", t!("menu.search"));
+        outln!("   9. {}", t!("menu.exit"));
+        outln!("   10. {}", t!("menu.categories"));
+        outln!("   11. {}", t!("menu.tags"));
+        outln!("   12. {}", t!("menu.accounts"));
+        outln!("   13. {}", t!("menu.transfer"));
+        outln!("   14. {}", t!("menu.trial_balance"));
+        outln!("   15. {}", t!("menu.rates"));
+        outln!("   16. {}", t!("menu.display"));
+        outln!("   17. {}", t!("menu.period_report"));
+        outln!("   18. {}", t!("menu.budgets"));
+        outln!("   19. {}", t!("menu.recurring"));
+        outln!("   20. {}", t!("menu.goals"));
+        outln!("   21. {}", t!("menu.undo_redo"));
+        outln!("   22. {}", t!("menu.audit"));
+        outln!("   23. {}", t!("menu.events"));
+        outln!();
+        out!("   {}: ", t!("prompt.choose_option", range = "1-23"));
+    }
+
+    fn get_user_choice(&self) -> Result<String, WalletError> {
+        let mut input = String::new();
+        let read = io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        // Input sudah habis (EOF), misalnya stdin ditutup: keluar dari aplikasi.
+        if read == 0 {
+            return Ok("9".to_string());
+        }
+
+        Ok(input.trim().to_string())
+    }
+
+    fn handle_choice(&mut self, choice: &str) -> bool {
+        match choice {
+            "1" => {
+                self.print_loading(&t!("loading.history"));
+                if let Err(e) = self.wallet.show(self.active_account) {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "2" => {
+                self.print_loading(&t!("loading.transaction_form"));
+                if let Err(e) = self.handle_add_transaction() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "3" => {
+                self.print_loading(&t!("loading.balance"));
+                if let Err(e) = self.handle_balance() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "4" => {
+                self.print_loading(&t!("loading.summary"));
+                if let Err(e) = self.show_summary() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "5" => {
+                self.print_loading(&t!("loading.filter"));
+                if let Err(e) = self.handle_filter_transaction() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "6" => {
+                self.print_loading(&t!("loading.transactions"));
+                if let Err(e) = self.handle_delete_transaction() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "7" => {
+                self.print_loading(&t!("loading.update"));
+                if let Err(e) = self.handle_update_transaction() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "8" => {
+                self.print_loading(&t!("loading.search"));
+                if let Err(e) = self.handle_search_transaction() {
+                    outln!("   [ERROR] {}", e);
+                }
+            }
+            "9" => {
+                outln!();
+                outln!("   {}", t!("app.goodbye"));
+                outln!("   {}", "─".repeat(30));
+                outln!();
+                outln!("   {}", t!("app.thanks"));
+                outln!("   {}", t!("app.see_you"));
+                outln!();
+                thread::sleep(Duration::from_millis(1500));
+                return false;
+            }
+            "10" => {
+                self.print_loading(&t!("loading.categories"));
+                if let Err(e) = self.handle_manage_categories() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "11" => {
+                self.print_loading(&t!("loading.tags"));
+                if let Err(e) = self.show_tag_report() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "12" => {
+                self.print_loading(&t!("loading.accounts"));
+                if let Err(e) = self.handle_manage_accounts() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "13" => {
+                self.print_loading(&t!("loading.transfer_form"));
+                if let Err(e) = self.handle_transfer() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "14" => {
+                self.print_loading(&t!("loading.trial_balance"));
+                if let Err(e) = self.wallet.show_trial_balance() {
+                    outln!("   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "15" => {
+                self.print_loading(&t!("loading.rates"));
+                if let Err(e) = self.handle_manage_rates() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "16" => {
+                if let Err(e) = self.handle_display_settings() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "17" => {
+                self.print_loading(&t!("loading.period_report"));
+                if let Err(e) = self.handle_period_report() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "18" => {
+                self.print_loading(&t!("loading.budgets"));
+                if let Err(e) = self.handle_budgets() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "19" => {
+                self.print_loading(&t!("loading.recurring"));
+                if let Err(e) = self.handle_recurring() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "20" => {
+                self.print_loading(&t!("loading.goals"));
+                if let Err(e) = self.handle_goals() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "21" => {
+                if let Err(e) = self.handle_undo_redo() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "22" => {
+                if let Err(e) = self.handle_audit_log() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            "23" => {
+                if let Err(e) = self.handle_events() {
+                    outln!("\n   [ERROR] {}", e);
+                }
+                self.pause();
+            }
+            _ => {
+                outln!();
+                outln!("   [ERROR] {}", t!("menu.invalid"));
+                outln!("   {}", t!("menu.invalid_hint", range = "1-23"));
+                outln!();
+                self.pause();
+            }
+        }
+        true
+    }
+
+    /// Saldo saat ini, lalu bila diminta saldo per akhir suatu tanggal.
+    fn handle_balance(&mut self) -> Result<(), WalletError> {
+        self.wallet.total(None)?;
+        let input = self.read_line(&t!("prompt.balance_as_of"))?;
+        if input.is_empty() {
+            return Ok(());
+        }
+        self.wallet.total(Some(parse_date(&input)?))
+    }
+
+    fn handle_add_transaction(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("add.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        let transaction_type = self.get_type_transaction()?;
+        let account = self.get_account(&t!("prompt.account"), self.default_account())?;
+        let currency = self.wallet.accounts().currency(account);
+        let amount = self.get_amount(currency)?;
+        let description = self.get_description()?;
+        let date = self.get_date()?;
+        let category = self.get_category()?;
+        let tags = self.get_tags()?;
+
+        let transaction = WalletItems::new(
+            description,
+            amount,
+            transaction_type,
+            date,
+            category,
+            tags,
+            account,
+        )
+        .in_currency(currency);
+        let message = transaction.transaction_type.to_string();
+
+        let result = match self.resolve_duplicate(&transaction, DuplicateAction::Ask)? {
+            Resolution::Add => self
+                .confirm_overdraft(|wallet, confirmed| wallet.add(transaction.clone(), confirmed)),
+            Resolution::Skip(_) => {
+                outln!();
+                outln!("   [INFO] {}", t!("add.skipped"));
+                outln!();
+                return Ok(());
+            }
+            Resolution::Merge(id) => {
+                self.wallet.merge(id, transaction)?;
+                outln!();
+                outln!("   [SUCCESS] {}", t!("add.merged", id = id));
+                outln!();
+                return Ok(());
+            }
+        };
+
+        match result {
+            Ok(id) => {
+                outln!();
+                outln!("   [SUCCESS] {}", t!("add.success"));
+                outln!("   {}: {}", t!("column.id"), id);
+                outln!("   {}: {}", t!("column.type"), message);
+                outln!(
+                    "   {}: {}",
+                    t!("column.amount"),
+                    self.wallet.number_format().money(amount, currency)
+                );
+                outln!("   {}: {}", t!("column.date"), date.format(DATE_FORMAT));
+                outln!(
+                    "   {}: {}",
+                    t!("column.category"),
+                    self.wallet.category_label(category)
+                );
+                outln!(
+                    "   {}: {}",
+                    t!("column.account"),
+                    self.wallet.accounts().name(account)
+                );
+                outln!();
+                self.print_budget_alerts(id);
+            }
+            Err(e) => {
+                outln!();
+                outln!("   [ERROR] {}", t!("add.failed"));
+                outln!("   {}", t!("app.reason", reason = e));
+                outln!();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Menjalankan perubahan data. Jika perubahan itu membuat saldo akun
+    /// berkebijakan konfirmasi menjadi minus, pengguna ditanya dulu lalu
+    /// perubahan diulang dengan persetujuannya.
+    fn confirm_overdraft<T>(
+        &mut self,
+        mut change: impl FnMut(&mut WalletList, bool) -> Result<T, WalletError>,
+    ) -> Result<T, WalletError> {
+        match change(&mut self.wallet, false) {
+            Err(WalletError::OverdraftConfirmation(reason)) => {
+                outln!();
+                outln!("   [WARNING] {}", t!("overdraft.warning", reason = reason));
+                match self
+                    .read_line(&t!("overdraft.confirm"))?
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "y" | "ya" | "yes" => change(&mut self.wallet, true),
+                    _ => Err(WalletError::InvalidInput(t!("overdraft.cancelled"))),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Memeriksa kemungkinan duplikat sebelum transaksi ditambahkan. Dengan
+    /// `DuplicateAction::Ask` pengguna diminta memilih; input kosong berarti
+    /// transaksi dilewati. Tanpa terminal (misalnya dari cron) tidak ada yang
+    /// bisa ditanya, sehingga duplikat menjadi galat alih-alih dilewati diam-diam.
+    fn resolve_duplicate(
+        &self,
+        transaction: &WalletItems,
+        action: DuplicateAction,
+    ) -> Result<Resolution, WalletError> {
+        let duplicates = self.wallet.find_duplicates(transaction);
+        let Some(closest) = duplicates.first() else {
+            return Ok(Resolution::Add);
+        };
+        let id = closest.id;
+
+        let action = match action {
+            DuplicateAction::Ask if !io::stdin().is_terminal() => {
+                return Err(WalletError::InvalidInput(t!(
+                    "duplicate.no_terminal",
+                    id = id
+                )));
+            }
+            DuplicateAction::Ask => {
+                outln!();
+                outln!("   [WARNING] {}", t!("duplicate.warning"));
+                for item in &duplicates {
+                    outln!(
+                        "   {:>6} | {} | {} | {}",
+                        item.id,
+                        item.date.format(DATE_FORMAT),
+                        self.wallet
+                            .number_format()
+                            .money(item.amount, item.currency),
+                        truncate(&item.description, 40)
+                    );
+                }
+                outln!();
+                outln!("   1. {}", t!("duplicate.add"));
+                outln!("   2. {}", t!("duplicate.skip"));
+                outln!("   3. {}", t!("duplicate.merge", id = id));
+                outln!();
+
+                match self.read_line(&t!("duplicate.prompt"))?.as_str() {
+                    "1" => DuplicateAction::Add,
+                    "" | "2" => DuplicateAction::Skip,
+                    "3" => DuplicateAction::Merge,
+                    _ => {
+                        return Err(WalletError::InvalidInput(t!("error.choice")));
+                    }
+                }
+            }
+            action => action,
+        };
+
+        Ok(match action {
+            DuplicateAction::Ask | DuplicateAction::Add => Resolution::Add,
+            DuplicateAction::Skip => Resolution::Skip(id),
+            DuplicateAction::Merge => Resolution::Merge(id),
+        })
+    }
+
+    fn handle_update_transaction(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("update.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        self.wallet.show(self.active_account)?;
+
+        let id = self.get_index_transaction()?;
+        let existing =
+            self.wallet.get(id).cloned().ok_or_else(|| {
+                WalletError::InvalidInput(t!("error.transaction_not_found", id = id))
+            })?;
+        let description = self.get_description()?;
+
+        // Transfer tetap transfer dan jurnal tetap jurnal; jenis, kategori,
+        // akun dan posting jurnal tidak diubah di sini.
+        let transaction = if existing.transaction_type == TypeTransaction::Journal {
+            let date = self.get_date()?;
+            let tags = self.get_tags()?;
+            WalletItems::journal(description, date, existing.postings.clone(), tags)
+        } else if let Some(to) = existing.to_account {
+            let from_currency = self.wallet.accounts().currency(existing.account);
+            let to_currency = self.wallet.accounts().currency(to);
+            let amount = self.get_amount(from_currency)?;
+            let date = self.get_date()?;
+            let tags = self.get_tags()?;
+            let transfer =
+                WalletItems::transfer(description, amount, date, existing.account, to, tags)
+                    .in_currency(from_currency);
+            if from_currency == to_currency {
+                transfer
+            } else {
+                let received =
+                    self.get_received_amount(amount, from_currency, to_currency, date)?;
+                transfer.receiving(received, to_currency)
+            }
+        } else {
+            let transaction_type = self.get_type_transaction()?;
+            let account = self.get_account(&t!("prompt.account"), existing.account)?;
+            let currency = self.wallet.accounts().currency(account);
+            let amount = self.get_amount(currency)?;
+            let date = self.get_date()?;
+            let category = self.get_category()?;
+            let tags = self.get_tags()?;
+            WalletItems::new(
+                description,
+                amount,
+                transaction_type,
+                date,
+                category,
+                tags,
+                account,
+            )
+            .in_currency(currency)
+        };
+
+        match self.confirm_overdraft(|wallet, confirmed| {
+            wallet.update(id, transaction.clone(), confirmed)
+        }) {
+            Ok(_) => {
+                outln!();
+                outln!("   [SUCCESS] {}", t!("update.success"));
+                outln!();
+            }
+            Err(e) => {
+                outln!();
+                outln!("   [ERROR] {}", t!("update.failed"));
+                outln!("   {}", t!("app.reason", reason = e));
+                outln!();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_delete_transaction(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("delete.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        self.wallet.show(self.active_account)?;
+
+        let id = self.get_index_transaction()?;
+        let removed =
+            self.confirm_overdraft(|wallet, confirmed| wallet.remove_transaction(id, confirmed))?;
+        outln!(
+            "\n   [SUCCESS] {}",
+            t!(
+                "delete.success",
+                id = removed.id,
+                description = removed.description
+            )
+        );
+        Ok(())
+    }
+
+    fn handle_filter_transaction(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("filter.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        let transaction_type = self.get_filter_type()?;
+        let include_tags = parse_tags(&self.read_line(&t!("filter.include_tags"))?)?;
+        let exclude_tags = parse_tags(&self.read_line(&t!("filter.exclude_tags"))?)?;
+        outln!();
+
+        let filter = TransactionFilter {
+            transaction_type,
+            include_tags,
+            exclude_tags,
+            account: self.active_account,
+        };
+        self.print_filter_result(&filter);
+
+        Ok(())
+    }
+
+    fn print_filter_result(&self, filter: &TransactionFilter) {
+        let transactions = self.wallet.filter(filter);
 
         if transactions.is_empty() {
-            println!(
-                "   Tidak ada transaksi ditemukan untuk jenis: {}",
-                filter_type
+            outln!(
+                "   {}",
+                t!(
+                    "filter.empty",
+                    filter = filter.describe(self.wallet.accounts())
+                )
+            );
+        } else {
+            outln!(
+                "   {}",
+                t!(
+                    "filter.result",
+                    filter = filter.describe(self.wallet.accounts())
+                )
+            );
+            outln!("   {}", "─".repeat(50));
+            outln!();
+
+            for (i, items) in transactions.iter().enumerate() {
+                outln!(
+                    "   {}. [{}] {} | {} | {}",
+                    i + 1,
+                    items.id,
+                    items.description_with_tags(),
+                    self.wallet.account_label(items),
+                    items.amount_label(filter.account, &self.wallet.number_format())
+                );
+            }
+            outln!();
+        }
+    }
+
+    fn handle_search_transaction(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("search.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        let description = self.get_description()?;
+        self.print_search_result(&description);
+
+        self.pause();
+        Ok(())
+    }
+
+    fn print_search_result(&self, description: &str) {
+        let items = self.wallet.search_transaction(description);
+
+        if items.is_empty() {
+            outln!("   {}", t!("search.empty"));
+            outln!("   {}", t!("search.keyword", keyword = description));
+        } else {
+            outln!("   {}", t!("search.result"));
+            outln!("   {}", "─".repeat(50));
+            outln!();
+
+            for (i, item) in items.iter().enumerate() {
+                outln!(
+                    "   {}. [{}] {} | {} | {}",
+                    i + 1,
+                    item.id,
+                    item.description_with_tags(),
+                    self.wallet.account_label(item),
+                    item.amount_label(None, &self.wallet.number_format())
+                );
+            }
+            outln!();
+        }
+    }
+
+    fn get_filter_type(&self) -> Result<Option<TypeTransaction>, WalletError> {
+        outln!("   {}", t!("type.title"));
+        outln!("   {}", "─".repeat(20));
+        outln!("   1. {}", TypeTransaction::Income);
+        outln!("   2. {}", TypeTransaction::Expense);
+        outln!("   3. {}", TypeTransaction::Transfer);
+        outln!("   4. {}", TypeTransaction::Journal);
+        outln!("   5. {}", t!("type.all"));
+        outln!();
+        out!("   {}: ", t!("type.prompt", range = "1-5"));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        let choice = input.trim().to_string();
+
+        match choice.to_lowercase().as_str() {
+            "1" | "pemasukan" | "income" => Ok(Some(TypeTransaction::Income)),
+            "2" | "pengeluaran" | "expense" => Ok(Some(TypeTransaction::Expense)),
+            "3" | "transfer" => Ok(Some(TypeTransaction::Transfer)),
+            "4" | "jurnal" | "journal" => Ok(Some(TypeTransaction::Journal)),
+            "5" | "semua" | "all" | "" => Ok(None),
+            _ => Err(WalletError::InvalidInput(t!("error.choice"))),
+        }
+    }
+
+    fn get_index_transaction(&self) -> Result<TransactionId, WalletError> {
+        out!("   {}: ", t!("prompt.transaction"));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        self.wallet.resolve(&input)
+    }
+
+    fn get_type_transaction(&self) -> Result<TypeTransaction, WalletError> {
+        outln!("   {}", t!("type.title"));
+        outln!("   {}", "─".repeat(20));
+        outln!("   1. {}", TypeTransaction::Income);
+        outln!("   2. {}", TypeTransaction::Expense);
+        outln!();
+        out!("   {}: ", t!("type.prompt", range = "1/2"));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        match input.trim().to_lowercase().as_str() {
+            "1" | "pemasukan" | "income" => Ok(TypeTransaction::Income),
+            "2" | "pengeluaran" | "expense" => Ok(TypeTransaction::Expense),
+            _ => Err(WalletError::InvalidInput(t!("error.type"))),
+        }
+    }
+
+    fn get_amount(&self, currency: Currency) -> Result<Money, WalletError> {
+        out!("   {}: ", t!("prompt.amount", symbol = currency.symbol()));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        parse_amount(&input, currency)
+    }
+
+    /// Jumlah yang diterima akun tujuan pada transfer antar mata uang. Jika
+    /// kurs pada tanggal itu tersedia, hasil konversinya menjadi jawaban bawaan.
+    fn get_received_amount(
+        &self,
+        amount: Money,
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    ) -> Result<Money, WalletError> {
+        let suggested = self.wallet.rates().convert(amount, from, to, date).ok();
+        let prompt = match suggested {
+            Some(suggested) => format!(
+                "{} [{}]",
+                t!("prompt.received_amount", symbol = to.symbol()),
+                self.wallet.number_format().number(suggested, to)
+            ),
+            None => t!("prompt.received_amount", symbol = to.symbol()),
+        };
+        match (self.read_line(&prompt)?.as_str(), suggested) {
+            ("", Some(suggested)) => Ok(suggested),
+            (input, _) => parse_amount(input, to),
+        }
+    }
+
+    fn get_description(&self) -> Result<String, WalletError> {
+        out!("   {}: ", t!("prompt.description"));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        parse_description(&input)
+    }
+
+    fn get_date(&self) -> Result<NaiveDate, WalletError> {
+        let today = today();
+        out!("   {} [{}]: ", t!("prompt.date"), today.format(DATE_FORMAT));
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        if input.trim().is_empty() {
+            return Ok(today);
+        }
+
+        parse_date(&input)
+    }
+
+    /// Menampilkan pohon kategori bernomor; nomor sesuai urutan `CategoryList::tree`.
+    fn print_category_tree(&self) {
+        for (i, category) in self.wallet.categories().tree().iter().enumerate() {
+            let indent = if category.parent.is_some() {
+                "   └ "
+            } else {
+                ""
+            };
+            outln!("   {:>2}. {}{}", i + 1, indent, category.name);
+        }
+    }
+
+    /// Membaca nomor kategori dari pohon kategori. Input kosong atau `0`
+    /// berarti tidak memilih kategori.
+    fn read_category_choice(&self, prompt: &str) -> Result<Option<CategoryId>, WalletError> {
+        out!("   {}: ", prompt);
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        let input = input.trim();
+        if input.is_empty() || input == "0" {
+            return Ok(None);
+        }
+
+        let number = input
+            .parse::<usize>()
+            .map_err(|_| WalletError::InvalidInput(t!("error.category_number")))?;
+
+        self.wallet
+            .categories()
+            .tree()
+            .get(number.wrapping_sub(1))
+            .map(|category| Some(category.id))
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.category_number")))
+    }
+
+    fn get_category(&self) -> Result<Option<CategoryId>, WalletError> {
+        outln!("   {}", t!("category.title"));
+        outln!("   {}", "─".repeat(20));
+        self.print_category_tree();
+        outln!();
+        self.read_category_choice(&t!("prompt.category"))
+    }
+
+    /// Akun untuk transaksi baru: akun aktif, atau akun pertama jika
+    /// menu sedang menampilkan semua akun.
+    fn default_account(&self) -> AccountId {
+        self.active_account
+            .unwrap_or_else(|| self.wallet.accounts().default_id())
+    }
+
+    /// Menampilkan daftar akun bernomor beserta saldonya.
+    fn print_account_list(&self) -> Result<(), WalletError> {
+        for (i, account) in self.wallet.accounts().iter().enumerate() {
+            let balance = self.wallet.calculate_balance(Some(account.id))?;
+            outln!(
+                "   {:>2}. {:<24} {:>18}",
+                i + 1,
+                truncate(&account.name, 24),
+                self.wallet.number_format().money(balance, account.currency)
+            );
+        }
+        Ok(())
+    }
+
+    /// Membaca nomor akun. Jika hanya ada satu akun, akun itu langsung
+    /// dipakai tanpa bertanya; input kosong berarti `default`.
+    fn get_account(&self, label: &str, default: AccountId) -> Result<AccountId, WalletError> {
+        let accounts = self.wallet.accounts();
+        if accounts.len() == 1 {
+            return Ok(accounts.default_id());
+        }
+
+        outln!("   {}", label.to_uppercase());
+        outln!("   {}", "─".repeat(20));
+        self.print_account_list()?;
+        outln!();
+        self.read_account_choice(&t!(
+            "prompt.choose_account",
+            label = label.to_lowercase(),
+            default = accounts.name(default)
+        ))
+        .map(|choice| choice.unwrap_or(default))
+    }
+
+    /// Membaca nomor akun dari `print_account_list`; input kosong berarti
+    /// tidak memilih.
+    fn read_account_choice(&self, prompt: &str) -> Result<Option<AccountId>, WalletError> {
+        let input = self.read_line(prompt)?;
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let number = input
+            .parse::<usize>()
+            .map_err(|_| WalletError::InvalidInput(t!("error.account_number")))?;
+        self.wallet
+            .accounts()
+            .iter()
+            .nth(number.wrapping_sub(1))
+            .map(|account| Some(account.id))
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.account_number")))
+    }
+
+    fn get_tags(&self) -> Result<Vec<String>, WalletError> {
+        parse_tags(&self.read_line(&t!("prompt.tags"))?)
+    }
+
+    fn read_line(&self, prompt: &str) -> Result<String, WalletError> {
+        out!("   {}: ", prompt);
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|_| WalletError::InvalidInput(t!("error.input")))?;
+
+        Ok(input.trim().to_string())
+    }
+
+    fn handle_manage_categories(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("categories.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        self.print_category_tree();
+        outln!();
+        outln!("   1. {}", t!("categories.add"));
+        outln!("   2. {}", t!("categories.add_child"));
+        outln!("   3. {}", t!("categories.rename"));
+        outln!("   4. {}", t!("categories.remove"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-4"))?
+            .as_str()
+        {
+            "1" => {
+                let name = self.read_line(&t!("prompt.category_name"))?;
+                self.wallet.add_category(name, None)?;
+                outln!("\n   [SUCCESS] {}", t!("categories.added"));
+            }
+            "2" => {
+                let parent = self
+                    .read_category_choice(&t!("prompt.parent_category_number"))?
+                    .ok_or_else(|| {
+                        WalletError::InvalidInput(t!("error.parent_category_required"))
+                    })?;
+                let name = self.read_line(&t!("prompt.child_category_name"))?;
+                self.wallet.add_category(name, Some(parent))?;
+                outln!("\n   [SUCCESS] {}", t!("categories.child_added"));
+            }
+            "3" => {
+                let id = self
+                    .read_category_choice(&t!("prompt.category_number"))?
+                    .ok_or_else(|| WalletError::InvalidInput(t!("error.category_required")))?;
+                let name = self.read_line(&t!("prompt.new_name"))?;
+                self.wallet.rename_category(id, name)?;
+                outln!("\n   [SUCCESS] {}", t!("categories.renamed"));
+            }
+            "4" => {
+                let id = self
+                    .read_category_choice(&t!("prompt.category_number"))?
+                    .ok_or_else(|| WalletError::InvalidInput(t!("error.category_required")))?;
+                self.wallet.remove_category(id)?;
+                outln!("\n   [SUCCESS] {}", t!("categories.removed"));
+            }
+            "0" | "" => {}
+            _ => {
+                return Err(WalletError::InvalidInput(t!("error.choice")));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_manage_accounts(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("accounts.title"));
+        outln!("   {}", "─".repeat(48));
+        outln!();
+
+        self.print_account_list()?;
+        outln!("   {}", "─".repeat(48));
+        let net = match self.wallet.calculate_balance(None) {
+            Ok(net) => self
+                .wallet
+                .number_format()
+                .money(net, self.wallet.reporting_currency()),
+            Err(e) => e.to_string(),
+        };
+        outln!("   {:<28} {:>18}", t!("accounts.net_balance"), net);
+        outln!();
+        outln!("   1. {}", t!("accounts.add"));
+        outln!("   2. {}", t!("accounts.rename"));
+        outln!("   3. {}", t!("accounts.remove"));
+        outln!("   4. {}", t!("accounts.select"));
+        outln!("   5. {}", t!("accounts.show_all"));
+        outln!("   6. {}", t!("accounts.overdraft"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        let required = |choice: Option<AccountId>| {
+            choice.ok_or_else(|| WalletError::InvalidInput(t!("error.account_required")))
+        };
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-6"))?
+            .as_str()
+        {
+            "1" => {
+                let name = self.read_line(&t!("prompt.account_name"))?;
+                let kind = match self
+                    .read_line(&t!("prompt.account_kind"))?
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "" | "1" | "aset" | "asset" => AccountKind::Asset,
+                    "2" | "liabilitas" | "liability" => AccountKind::Liability,
+                    _ => {
+                        return Err(WalletError::InvalidInput(t!("error.account_kind")));
+                    }
+                };
+                let currency = match self.read_line(&t!("prompt.account_currency"))?.as_str() {
+                    "" => Currency::IDR,
+                    input => input.parse()?,
+                };
+                let opening = match self
+                    .read_line(&t!("prompt.opening_balance", symbol = currency.symbol()))?
+                    .as_str()
+                {
+                    "" => Money::ZERO,
+                    input => parse_amount(input, currency)?,
+                };
+                self.wallet
+                    .add_account(name, kind, currency, opening, None)?;
+                outln!("\n   [SUCCESS] {}", t!("accounts.added"));
+            }
+            "2" => {
+                let id = required(self.read_account_choice(&t!("prompt.account_number"))?)?;
+                let name = self.read_line(&t!("prompt.new_name"))?;
+                self.wallet.rename_account(id, name)?;
+                outln!("\n   [SUCCESS] {}", t!("accounts.renamed"));
+            }
+            "3" => {
+                let id = required(self.read_account_choice(&t!("prompt.account_number"))?)?;
+                self.wallet.remove_account(id)?;
+                if self.active_account == Some(id) {
+                    self.active_account = None;
+                }
+                outln!("\n   [SUCCESS] {}", t!("accounts.removed"));
+            }
+            "4" => {
+                let id = required(self.read_account_choice(&t!("prompt.account_number"))?)?;
+                self.active_account = Some(id);
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!("accounts.active", account = self.wallet.accounts().name(id))
+                );
+            }
+            "5" => {
+                self.active_account = None;
+                outln!("\n   [SUCCESS] {}", t!("accounts.showing_all"));
+            }
+            "6" => {
+                outln!();
+                for (i, account) in self.wallet.accounts().iter().enumerate() {
+                    outln!(
+                        "   {:>2}. {:<24} {}",
+                        i + 1,
+                        truncate(&account.name, 24),
+                        account.overdraft_label(&self.wallet.number_format())
+                    );
+                }
+                outln!();
+                let id = required(self.read_account_choice(&t!("prompt.account_number"))?)?;
+                let currency = self.wallet.accounts().currency(id);
+                outln!("   1. {}", t!("overdraft.forbid"));
+                outln!("   2. {}", t!("overdraft.confirm_mode"));
+                outln!("   3. {}", t!("overdraft.allow_optional_limit"));
+                let mode = match self.read_line(&t!("prompt.overdraft_policy"))?.as_str() {
+                    "1" => OverdraftMode::Forbid,
+                    "2" => OverdraftMode::Confirm,
+                    "3" => OverdraftMode::Allow,
+                    _ => {
+                        return Err(WalletError::InvalidInput(t!("error.choice")));
+                    }
+                };
+                let limit = match mode {
+                    OverdraftMode::Allow => {
+                        match self
+                            .read_line(&t!("prompt.overdraft_limit", symbol = currency.symbol()))?
+                            .as_str()
+                        {
+                            "" => None,
+                            input => Some(parse_amount(input, currency)?),
+                        }
+                    }
+                    OverdraftMode::Forbid | OverdraftMode::Confirm => None,
+                };
+                let policy = OverdraftPolicy::new(mode, limit)?;
+                self.wallet.set_overdraft(id, policy)?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!(
+                        "overdraft.saved",
+                        policy = policy.describe(currency, &self.wallet.number_format())
+                    )
+                );
+            }
+            "0" | "" => {}
+            _ => {
+                return Err(WalletError::InvalidInput(t!("error.choice")));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Menampilkan tabel kurs beserta mata uang laporan.
+    fn print_rate_list(&self) {
+        if self.wallet.rates().is_empty() {
+            outln!("   {}", t!("rates.empty"));
+            outln!("   {}", t!("rates.empty_hint"));
+        } else {
+            outln!(
+                "   {:<10}  {:<4}  {:<4}  {:>22}",
+                t!("column.date"),
+                t!("rates.column_from"),
+                t!("rates.column_to"),
+                t!("rates.column_rate")
+            );
+            for rate in self.wallet.rates().iter() {
+                outln!(
+                    "   {:<10}  {:<4}  {:<4}  {:>22}",
+                    rate.date.format(DATE_FORMAT).to_string(),
+                    rate.from,
+                    rate.to,
+                    self.wallet.number_format().rate(rate.rate)
+                );
+            }
+        }
+        outln!("   {}", "─".repeat(48));
+        outln!(
+            "   {}",
+            t!(
+                "rates.reporting",
+                currency = self.wallet.reporting_currency()
+            )
+        );
+    }
+
+    fn handle_manage_rates(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("rates.title"));
+        outln!("   {}", "─".repeat(48));
+        outln!();
+
+        let reporting = self.wallet.reporting_currency();
+        self.print_rate_list();
+        outln!();
+        outln!("   1. {}", t!("rates.set"));
+        outln!("   2. {}", t!("rates.remove"));
+        outln!("   3. {}", t!("rates.import"));
+        outln!("   4. {}", t!("rates.set_reporting"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        let read_pair = |app: &Self| -> Result<(Currency, Currency), WalletError> {
+            let from: Currency = app.read_line(&t!("prompt.rate_from"))?.parse()?;
+            let to = match app
+                .read_line(&t!("prompt.rate_to", currency = reporting))?
+                .as_str()
+            {
+                "" => reporting,
+                input => input.parse()?,
+            };
+            Ok((from, to))
+        };
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-4"))?
+            .as_str()
+        {
+            "1" => {
+                let (from, to) = read_pair(self)?;
+                let rate = self
+                    .read_line(&t!("prompt.rate", from = from, to = to))?
+                    .parse()?;
+                let date = self.get_date()?;
+                let replaced = self
+                    .wallet
+                    .set_rate(ExchangeRate::new(date, from, to, rate)?)?;
+                let date = date.format(DATE_FORMAT);
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    if replaced {
+                        t!("rates.updated", from = from, to = to, date = date)
+                    } else {
+                        t!("rates.added", from = from, to = to, date = date)
+                    }
+                );
+            }
+            "2" => {
+                let (from, to) = read_pair(self)?;
+                let date = self.get_date()?;
+                self.wallet.remove_rate(date, from, to)?;
+                outln!("\n   [SUCCESS] {}", t!("rates.removed"));
+            }
+            "3" => {
+                let path = self.read_line(&t!("prompt.rate_file"))?;
+                let import = exchange::read_rates(std::path::Path::new(&path))?;
+                let rates = import.rates.into_iter().map(|(_, rate)| rate).collect();
+                let (added, replaced) = self.wallet.import_rates(rates)?;
+                outln!();
+                outln!(
+                    "   [SUCCESS] {}",
+                    t!("rates.imported", added = added, replaced = replaced)
+                );
+                for row in &import.rejected {
+                    outln!(
+                        "   [WARNING] {}",
+                        t!("import.line", line = row.line, reason = row.reason)
+                    );
+                }
+            }
+            "4" => {
+                let currency = self.read_line(&t!("prompt.reporting_currency"))?.parse()?;
+                self.wallet.set_reporting_currency(currency)?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!("rates.reporting", currency = currency)
+                );
+            }
+            "0" | "" => {}
+            _ => {
+                return Err(WalletError::InvalidInput(t!("error.choice")));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Menampilkan pengaturan tampilan angka beserta contohnya.
+    fn print_number_format(&self) {
+        let format = self.wallet.number_format();
+        let currency = self.wallet.reporting_currency();
+        outln!("   {}", t!("display.language", language = i18n::language()));
+        outln!("   {}", t!("display.locale", locale = format.locale));
+        outln!("   {}", t!("display.symbol", symbol = format.symbol));
+        outln!("   {}", t!("display.negative", style = format.negative));
+        outln!(
+            "   {}",
+            t!(
+                "display.compact",
+                compact = if format.compact {
+                    t!("app.yes")
+                } else {
+                    t!("app.no")
+                }
+            )
+        );
+        outln!("   {}", "─".repeat(48));
+        outln!(
+            "   {}",
+            t!(
+                "display.sample",
+                money = format.money(Money::from_minor(-123_456_789), currency),
+                compact = format.compact(Money::from_minor(123_456_789), currency)
+            )
+        );
+    }
+
+    fn handle_display_settings(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("display.title"));
+        outln!("   {}", "─".repeat(48));
+        outln!();
+
+        self.print_number_format();
+        outln!();
+        outln!("   1. {}", t!("display.change_language"));
+        outln!("   2. {}", t!("display.change_locale"));
+        outln!("   3. {}", t!("display.change_symbol"));
+        outln!("   4. {}", t!("display.change_negative"));
+        outln!("   5. {}", t!("display.toggle_compact"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        let mut format = self.wallet.number_format();
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-5"))?
+            .as_str()
+        {
+            "1" => {
+                let language = self.choose_variant(&t!("prompt.language"))?;
+                self.wallet.set_language(language)?;
+            }
+            "2" => format.locale = self.choose_variant(&t!("prompt.locale"))?,
+            "3" => format.symbol = self.choose_variant(&t!("prompt.symbol"))?,
+            "4" => format.negative = self.choose_variant(&t!("prompt.negative"))?,
+            "5" => format.compact = !format.compact,
+            "0" | "" => return Ok(()),
+            _ => {
+                return Err(WalletError::InvalidInput(t!("error.choice")));
+            }
+        }
+        self.wallet.set_number_format(format)?;
+
+        outln!();
+        outln!("   [SUCCESS] {}", t!("display.saved"));
+        self.print_number_format();
+        Ok(())
+    }
+
+    /// Menampilkan pilihan bernomor dari sebuah enum lalu membaca pilihan pengguna.
+    fn choose_variant<T: ValueEnum + Display>(&self, prompt: &str) -> Result<T, WalletError> {
+        let variants = T::value_variants();
+        outln!();
+        for (i, variant) in variants.iter().enumerate() {
+            outln!("   {}. {}", i + 1, variant);
+        }
+        self.read_line(&format!("{} (1-{})", prompt, variants.len()))?
+            .parse::<usize>()
+            .ok()
+            .and_then(|choice| variants.get(choice.checked_sub(1)?))
+            .cloned()
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.choice")))
+    }
+
+    fn handle_transfer(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("transfer.title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        if self.wallet.accounts().len() < 2 {
+            return Err(WalletError::InvalidInput(t!(
+                "error.transfer_needs_accounts"
+            )));
+        }
+
+        let from = self.get_account(&t!("prompt.source_account"), self.default_account())?;
+        outln!();
+        let to = self
+            .read_account_choice(&t!("prompt.target_account"))?
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.target_account_required")))?;
+        let from_currency = self.wallet.accounts().currency(from);
+        let to_currency = self.wallet.accounts().currency(to);
+        let amount = self.get_amount(from_currency)?;
+        let default_description = t!("type.transfer");
+        let description = match self
+            .read_line(&format!(
+                "{} [{}]",
+                t!("prompt.description"),
+                default_description
+            ))?
+            .as_str()
+        {
+            "" => default_description,
+            input => parse_description(input)?,
+        };
+        let date = self.get_date()?;
+
+        let mut transfer = WalletItems::transfer(description, amount, date, from, to, Vec::new())
+            .in_currency(from_currency);
+        if from_currency != to_currency {
+            let received = self.get_received_amount(amount, from_currency, to_currency, date)?;
+            transfer = transfer.receiving(received, to_currency);
+        }
+        let id =
+            self.confirm_overdraft(|wallet, confirmed| wallet.add(transfer.clone(), confirmed))?;
+
+        let accounts = self.wallet.accounts();
+        outln!();
+        outln!("   [SUCCESS] {}", t!("transfer.success"));
+        outln!("   {}: {}", t!("column.id"), id);
+        outln!(
+            "   {} -> {}: {}",
+            accounts.name(from),
+            accounts.name(to),
+            transfer.amount_label(None, &self.wallet.number_format())
+        );
+        outln!();
+
+        Ok(())
+    }
+
+    fn print_category_breakdown(
+        &self,
+        transaction_type: TypeTransaction,
+        total: Money,
+    ) -> Result<(), WalletError> {
+        let breakdown = self.wallet.category_breakdown(&transaction_type)?;
+        if breakdown.is_empty() {
+            return Ok(());
+        }
+        let currency = self.wallet.reporting_currency();
+        let format = self.wallet.number_format();
+
+        outln!(
+            "   {}",
+            t!(
+                "report.by_category",
+                type = transaction_type.to_string().to_uppercase()
+            )
+        );
+        outln!("   {}", "─".repeat(50));
+
+        let percent = |amount: Money| amount.minor() as f64 / total.minor() as f64 * 100.0;
+        for entry in &breakdown {
+            outln!(
+                "   {:<28} {:>15} ({:>6})",
+                truncate(&self.wallet.category_label(entry.category), 28),
+                format.brief(entry.total, currency),
+                format.percent(percent(entry.total))
+            );
+            for (child, amount) in &entry.children {
+                let name = self
+                    .wallet
+                    .categories()
+                    .get(*child)
+                    .map(|c| c.name.as_str())
+                    .unwrap_or("-");
+                outln!(
+                    "     └ {:<22} {:>15} ({:>6})",
+                    truncate(name, 22),
+                    format.brief(*amount, currency),
+                    format.percent(percent(*amount))
+                );
+            }
+        }
+        outln!();
+        Ok(())
+    }
+
+    fn handle_period_report(&self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("report.menu_title"));
+        outln!("   {}", "─".repeat(30));
+
+        let period = self.choose_variant(&t!("prompt.period"))?;
+        outln!();
+        let read_date = |prompt: String| match self.read_line(&prompt)?.as_str() {
+            "" => Ok(None),
+            input => parse_date(input).map(Some),
+        };
+        let from = read_date(t!("prompt.from_date"))?;
+        let to = read_date(t!("prompt.to_date"))?;
+        self.show_period_report(period, DateRange::new(from, to)?)
+    }
+
+    /// Tabel pemasukan, pengeluaran, saldo bersih, perubahan saldo bersih
+    /// dari periode sebelumnya dan rasio tabungan per periode.
+    fn show_period_report(&self, period: Period, range: DateRange) -> Result<(), WalletError> {
+        let report = self.wallet.period_report(period, range)?;
+        let currency = self.wallet.reporting_currency();
+        let format = self.wallet.number_format();
+
+        outln!();
+        outln!(
+            "   {}",
+            t!("report.title", period = period.to_string().to_uppercase())
+        );
+        outln!("   {}", range.describe());
+        outln!("   {}", "─".repeat(96));
+
+        if report.is_empty() {
+            outln!("   {}", t!("report.empty"));
+            outln!();
+            return Ok(());
+        }
+
+        outln!(
+            "   {:<8} | {:>15} | {:>15} | {:>15} | {:>15} | {:>10}",
+            t!("report.column_period"),
+            t!("type.income"),
+            t!("type.expense"),
+            t!("tags.column_net"),
+            t!("report.column_change"),
+            t!("report.column_savings")
+        );
+        outln!("   {}", "─".repeat(96));
+
+        let savings = |total: &PeriodTotal| -> Result<String, WalletError> {
+            Ok(total
+                .savings_rate()?
+                .map(|rate| format.percent(rate))
+                .unwrap_or_else(|| "-".to_string()))
+        };
+        let mut previous: Option<Money> = None;
+        for total in &report {
+            let net = total.net()?;
+            let change = match previous {
+                Some(previous) => format.brief_signed(net.checked_sub(previous)?, currency),
+                None => "-".to_string(),
+            };
+            outln!(
+                "   {:<8} | {:>15} | {:>15} | {:>15} | {:>15} | {:>10}",
+                period.label(total.start),
+                format.brief_signed(total.income, currency),
+                format.brief(-total.expense, currency),
+                format.brief_signed(net, currency),
+                change,
+                savings(total)?
+            );
+            previous = Some(net);
+        }
+
+        let overall = PeriodTotal {
+            start: report[0].start,
+            income: Money::sum(report.iter().map(|total| total.income))?,
+            expense: Money::sum(report.iter().map(|total| total.expense))?,
+        };
+        outln!("   {}", "─".repeat(96));
+        outln!(
+            "   {:<8} | {:>15} | {:>15} | {:>15} | {:>15} | {:>10}",
+            "TOTAL",
+            format.brief_signed(overall.income, currency),
+            format.brief(-overall.expense, currency),
+            format.brief_signed(overall.net()?, currency),
+            "",
+            savings(&overall)?
+        );
+        outln!("   {}", "─".repeat(96));
+        outln!("   {}", t!("report.note", currency = currency));
+        outln!();
+        Ok(())
+    }
+
+    fn handle_budgets(&mut self) -> Result<(), WalletError> {
+        self.show_budget_status(today())?;
+        outln!("   1. {}", t!("budget.set"));
+        outln!("   2. {}", t!("budget.remove"));
+        outln!("   3. {}", t!("budget.set_threshold"));
+        outln!("   4. {}", t!("budget.other_month"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-4"))?
+            .as_str()
+        {
+            "1" => {
+                outln!();
+                let category = self
+                    .get_category()?
+                    .ok_or_else(|| WalletError::InvalidInput(t!("error.budget_category")))?;
+                let limit = self.get_amount(self.wallet.reporting_currency())?;
+                let replaced = self.wallet.set_budget(category, limit)?;
+                let category = self.wallet.category_label(Some(category));
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    if replaced {
+                        t!("budget.updated", category = category)
+                    } else {
+                        t!("budget.added", category = category)
+                    }
+                );
+            }
+            "2" => {
+                outln!();
+                let category = self
+                    .get_category()?
+                    .ok_or_else(|| WalletError::InvalidInput(t!("error.budget_category")))?;
+                self.wallet.remove_budget(category)?;
+                outln!("\n   [SUCCESS] {}", t!("budget.removed"));
+            }
+            "3" => {
+                let percent = self.read_line(&t!("prompt.budget_threshold"))?;
+                let percent = percent.trim_end_matches('%').parse().map_err(|_| {
+                    WalletError::InvalidInput(t!("error.budget_threshold", percent = percent))
+                })?;
+                self.wallet.set_budget_threshold(percent)?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!("budget.threshold_saved", percent = percent)
+                );
+            }
+            "4" => {
+                let month = period::parse_month(&self.read_line(&t!("prompt.month"))?)?;
+                self.show_budget_status(month)?;
+            }
+            "0" => {}
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
+        }
+        Ok(())
+    }
+
+    fn handle_recurring(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("recurring.title"));
+        outln!("   {}", "─".repeat(104));
+        self.print_schedule_list();
+        outln!();
+        outln!("   1. {}", t!("recurring.add"));
+        outln!("   2. {}", t!("recurring.remove"));
+        outln!("   3. {}", t!("recurring.upcoming"));
+        outln!("   4. {}", t!("recurring.post_due"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-4"))?
+            .as_str()
+        {
+            "1" => self.handle_add_schedule()?,
+            "2" => {
+                let id = self.read_line(&t!("prompt.recurring_id"))?.parse()?;
+                let removed = self.wallet.remove_schedule(id)?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!(
+                        "recurring.removed",
+                        id = id,
+                        description = removed.template.description
+                    )
+                );
+            }
+            "3" => self.show_upcoming(UPCOMING_DAYS),
+            "4" => {
+                if !self.review_due_schedules()? {
+                    outln!("\n   [INFO] {}", t!("recurring.nothing_due"));
+                }
+            }
+            "0" => {}
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
+        }
+        Ok(())
+    }
+
+    fn handle_add_schedule(&mut self) -> Result<(), WalletError> {
+        outln!();
+        let transaction_type = self.get_type_transaction()?;
+        let account = self.get_account(&t!("prompt.account"), self.default_account())?;
+        let currency = self.wallet.accounts().currency(account);
+        let amount = self.get_amount(currency)?;
+        let description = self.get_description()?;
+        let category = self.get_category()?;
+        let tags = self.get_tags()?;
+
+        let frequency: Frequency = self.choose_variant(&t!("prompt.frequency"))?;
+        let interval = match self.read_line(&t!("prompt.recurring_interval"))?.as_str() {
+            "" => 1,
+            input => input
+                .parse()
+                .map_err(|_| WalletError::InvalidInput(t!("error.recurring_interval")))?,
+        };
+        let start = match self
+            .read_line(&t!(
+                "prompt.recurring_start",
+                date = today().format(DATE_FORMAT)
+            ))?
+            .as_str()
+        {
+            "" => today(),
+            input => parse_date(input)?,
+        };
+        let end = match self.read_line(&t!("prompt.recurring_end"))?.as_str() {
+            "" => None,
+            input => Some(parse_date(input)?),
+        };
+        let count = match self.read_line(&t!("prompt.recurring_count"))?.as_str() {
+            "" => None,
+            input => Some(
+                input
+                    .parse()
+                    .map_err(|_| WalletError::InvalidInput(t!("error.recurring_count")))?,
+            ),
+        };
+        let last_business_day = matches!(frequency, Frequency::Monthly | Frequency::Yearly)
+            && matches!(
+                self.read_line(&t!("prompt.last_business_day"))?
+                    .to_lowercase()
+                    .as_str(),
+                "y" | "ya" | "yes"
+            );
+
+        let rule = Recurrence::new(frequency, interval, start, end, count, last_business_day)?;
+        let template = WalletItems::new(
+            description,
+            amount,
+            transaction_type,
+            start,
+            category,
+            tags,
+            account,
+        )
+        .in_currency(currency);
+        let id = self.wallet.add_schedule(template, rule)?;
+
+        outln!();
+        outln!("   [SUCCESS] {}", t!("recurring.added", id = id));
+        let schedule = self.wallet.schedules().get(id)?;
+        outln!(
+            "   {}: {}",
+            t!("recurring.column_rule"),
+            schedule.rule.describe()
+        );
+        if let Some(next) = schedule.next_date() {
+            outln!(
+                "   {}: {}",
+                t!("recurring.column_next"),
+                next.format(DATE_FORMAT)
+            );
+        }
+        self.review_due_schedules()?;
+        Ok(())
+    }
+
+    /// Menawarkan pencatatan kejadian transaksi berulang yang sudah jatuh
+    /// tempo. Mengembalikan `false` jika tidak ada yang jatuh tempo.
+    fn review_due_schedules(&mut self) -> Result<bool, WalletError> {
+        let due = self.wallet.scheduled_until(today());
+        if due.is_empty() {
+            return Ok(false);
+        }
+
+        outln!();
+        outln!("   {}", t!("recurring.due_title", count = due.len()));
+        outln!("   {}", "─".repeat(90));
+        self.print_occurrences(&due);
+        outln!("   {}", "─".repeat(90));
+        outln!("   1. {}", t!("recurring.post_all"));
+        outln!("   2. {}", t!("recurring.review_each"));
+        outln!("   0. {}", t!("recurring.later"));
+        outln!();
+        let review = match self
+            .read_line(&t!("prompt.choose_option", range = "0-2"))?
+            .as_str()
+        {
+            "1" => false,
+            "2" => true,
+            _ => return Ok(true),
+        };
+        outln!();
+
+        // Kejadian sebuah jadwal harus dicatat berurutan; setelah satu
+        // ditunda atau gagal, kejadian berikutnya dari jadwal itu ikut ditunda.
+        let mut held: Vec<ScheduleId> = Vec::new();
+        for (id, date) in due {
+            if held.contains(&id) {
+                continue;
+            }
+            let date_label = date.format(DATE_FORMAT);
+            if review {
+                let description = self
+                    .wallet
+                    .schedules()
+                    .get(id)
+                    .map(|s| s.template.description.clone())
+                    .unwrap_or_default();
+                match self
+                    .read_line(&t!(
+                        "prompt.recurring_post",
+                        id = id,
+                        date = date_label,
+                        description = description
+                    ))?
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "y" | "ya" | "yes" => {}
+                    "s" | "skip" | "lewati" => {
+                        self.wallet.skip_occurrence(id)?;
+                        outln!(
+                            "   [INFO] {}",
+                            t!("recurring.skipped", id = id, date = date_label)
+                        );
+                        continue;
+                    }
+                    _ => {
+                        held.push(id);
+                        continue;
+                    }
+                }
+            }
+
+            match self.confirm_overdraft(|wallet, confirmed| wallet.post_occurrence(id, confirmed))
+            {
+                Ok(transaction) => {
+                    outln!(
+                        "   [SUCCESS] {}",
+                        t!(
+                            "recurring.posted",
+                            id = id,
+                            date = date_label,
+                            transaction = transaction
+                        )
+                    );
+                    self.print_budget_alerts(transaction);
+                }
+                Err(e) => {
+                    outln!(
+                        "   [ERROR] {}",
+                        t!(
+                            "recurring.post_failed",
+                            id = id,
+                            date = date_label,
+                            reason = e
+                        )
+                    );
+                    held.push(id);
+                }
+            }
+        }
+        outln!();
+        Ok(true)
+    }
+
+    /// Tabel transaksi berulang beserta aturan dan kejadian berikutnya.
+    fn print_schedule_list(&self) {
+        let schedules = self.wallet.schedules();
+        if schedules.is_empty() {
+            outln!("   {}", t!("recurring.empty"));
+            return;
+        }
+
+        let format = self.wallet.number_format();
+        outln!(
+            "   {:<4} | {:<11} | {:>15} | {:<18} | {:<30} | {:>10}",
+            t!("column.id"),
+            t!("column.type"),
+            t!("column.amount"),
+            t!("column.description"),
+            t!("recurring.column_rule"),
+            t!("recurring.column_next")
+        );
+        outln!("   {}", "─".repeat(104));
+        for schedule in schedules.iter() {
+            let next = match schedule.next_date() {
+                Some(date) => date.format(DATE_FORMAT).to_string(),
+                None => t!("recurring.finished"),
+            };
+            outln!(
+                "   {:<4} | {:<11} | {:>15} | {:<18} | {:<30} | {:>10}",
+                schedule.id,
+                schedule.template.transaction_type,
+                schedule.template.amount_label(None, &format),
+                truncate(&schedule.template.description, 18),
+                truncate(&schedule.rule.describe(), 30),
+                next
+            );
+        }
+    }
+
+    /// Kejadian transaksi berulang sampai `days` hari ke depan, termasuk
+    /// yang sudah jatuh tempo tetapi belum dicatat.
+    fn show_upcoming(&self, days: u32) {
+        let until = today()
+            .checked_add_days(chrono::Days::new(days.into()))
+            .unwrap_or(NaiveDate::MAX);
+        let occurrences = self.wallet.scheduled_until(until);
+
+        outln!();
+        outln!("   {}", t!("recurring.upcoming_title", days = days));
+        outln!("   {}", "─".repeat(90));
+        if occurrences.is_empty() {
+            outln!("   {}", t!("recurring.upcoming_empty"));
+        } else {
+            self.print_occurrences(&occurrences);
+        }
+        outln!();
+    }
+
+    fn print_occurrences(&self, occurrences: &[(ScheduleId, NaiveDate)]) {
+        let format = self.wallet.number_format();
+        outln!(
+            "   {:<10} | {:<4} | {:<11} | {:>15} | {:<22} | {}",
+            t!("column.date"),
+            t!("column.id"),
+            t!("column.type"),
+            t!("column.amount"),
+            t!("column.description"),
+            t!("column.account")
+        );
+        for (id, date) in occurrences {
+            let Ok(schedule) = self.wallet.schedules().get(*id) else {
+                continue;
+            };
+            let item = &schedule.template;
+            outln!(
+                "   {:<10} | {:<4} | {:<11} | {:>15} | {:<22} | {}",
+                date.format(DATE_FORMAT).to_string(),
+                id,
+                item.transaction_type,
+                item.amount_label(None, &format),
+                truncate(&item.description, 22),
+                self.wallet.accounts().name(item.account)
+            );
+        }
+    }
+
+    /// Tabel anggaran, pengeluaran, sisa dan bilah kemajuan setiap anggaran
+    /// pada bulan yang memuat `month`.
+    fn show_budget_status(&self, month: NaiveDate) -> Result<(), WalletError> {
+        let statuses = self.wallet.budget_status(month)?;
+        let threshold = self.wallet.budgets().threshold();
+        let format = self.wallet.number_format();
+
+        outln!();
+        outln!(
+            "   {}",
+            t!("budget.title", month = Period::Month.label(month))
+        );
+        outln!("   {}", "─".repeat(100));
+
+        if statuses.is_empty() {
+            outln!("   {}", t!("budget.empty"));
+            outln!();
+            return Ok(());
+        }
+
+        outln!(
+            "   {:<18} | {:>13} | {:>13} | {:>13} | {:<17} | {}",
+            t!("column.category"),
+            t!("budget.column_limit"),
+            t!("budget.column_spent"),
+            t!("budget.column_remaining"),
+            t!("budget.column_progress"),
+            t!("budget.column_status")
+        );
+        outln!("   {}", "─".repeat(100));
+        for status in &statuses {
+            outln!(
+                "   {:<18} | {:>13} | {:>13} | {:>13} | {} {:>6} | {}",
+                truncate(&self.wallet.category_label(Some(status.category)), 18),
+                format.brief(status.limit, status.currency),
+                format.brief(status.spent, status.currency),
+                format.brief(status.remaining()?, status.currency),
+                progress_bar(status.percent(), 10),
+                format.percent(status.percent()),
+                status.level(threshold)
+            );
+        }
+        outln!("   {}", "─".repeat(100));
+        outln!("   {}", t!("budget.note", percent = threshold));
+        outln!();
+        Ok(())
+    }
+
+    /// Mencetak peringatan untuk anggaran yang melewati batas peringatan
+    /// atau terlampaui karena transaksi `id`.
+    fn print_budget_alerts(&self, id: TransactionId) {
+        let Some(item) = self.wallet.get(id) else {
+            return;
+        };
+        let alerts = match self.wallet.budget_alerts(item) {
+            Ok(alerts) => alerts,
+            Err(e) => {
+                outln!("   [WARNING] {}", t!("budget.check_failed", reason = e));
+                return;
+            }
+        };
+
+        let threshold = self.wallet.budgets().threshold();
+        let format = self.wallet.number_format();
+        for status in &alerts {
+            let category = self.wallet.category_label(Some(status.category));
+            let spent = format.money(status.spent, status.currency);
+            let limit = format.money(status.limit, status.currency);
+            let percent = format.percent(status.percent());
+            outln!(
+                "   [WARNING] {}",
+                if status.level(threshold) == BudgetLevel::Over {
+                    t!(
+                        "budget.alert_over",
+                        category = category,
+                        spent = spent,
+                        limit = limit,
+                        percent = percent
+                    )
+                } else {
+                    t!(
+                        "budget.alert_threshold",
+                        category = category,
+                        spent = spent,
+                        limit = limit,
+                        percent = percent
+                    )
+                }
+            );
+        }
+        if !alerts.is_empty() {
+            outln!();
+        }
+    }
+
+    fn handle_goals(&mut self) -> Result<(), WalletError> {
+        self.show_goals()?;
+        outln!("   1. {}", t!("goal.add"));
+        outln!("   2. {}", t!("goal.detail"));
+        outln!("   3. {}", t!("goal.remove"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
+
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-3"))?
+            .as_str()
+        {
+            "1" => self.handle_add_goal()?,
+            "2" => {
+                let name = self.read_line(&t!("prompt.goal_name"))?;
+                let goal = self.wallet.goals().find(&name)?.clone();
+                self.show_goal_detail(&goal)?;
+            }
+            "3" => {
+                let name = self.read_line(&t!("prompt.goal_name"))?;
+                let removed = self.wallet.remove_goal(&name)?;
+                outln!("\n   [SUCCESS] {}", t!("goal.removed", name = removed.name));
+            }
+            "0" => {}
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
+        }
+        Ok(())
+    }
+
+    fn handle_add_goal(&mut self) -> Result<(), WalletError> {
+        outln!();
+        let name = self.read_line(&t!("prompt.goal_name"))?;
+        outln!("   1. {}", t!("goal.link_by_account"));
+        outln!("   2. {}", t!("goal.link_by_tag"));
+        let (link, currency) = match self
+            .read_line(&t!("prompt.choose_option", range = "1-2"))?
+            .as_str()
+        {
+            "1" => {
+                outln!();
+                let account =
+                    self.get_account(&t!("prompt.goal_account"), self.default_account())?;
+                (
+                    GoalLink::Account(account),
+                    self.wallet.accounts().currency(account),
+                )
+            }
+            "2" => (
+                GoalLink::tag(&self.read_line(&t!("prompt.goal_tag"))?)?,
+                self.wallet.reporting_currency(),
+            ),
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
+        };
+        let target = self.get_amount(currency)?;
+        let deadline = match self.read_line(&t!("prompt.goal_deadline"))?.as_str() {
+            "" => None,
+            input => Some(parse_date(input)?),
+        };
+
+        let goal = Goal {
+            name,
+            target,
+            currency,
+            deadline,
+            link,
+        };
+        let name = goal.name.trim().to_string();
+        self.wallet.add_goal(goal)?;
+        outln!("\n   [SUCCESS] {}", t!("goal.added", name = name));
+        Ok(())
+    }
+
+    /// Uraian sumber setoran, misalnya "Akun Tabungan" atau "#liburan".
+    fn goal_link_label(&self, goal: &Goal) -> String {
+        match &goal.link {
+            GoalLink::Account(id) => {
+                t!("goal.link_account", name = self.wallet.accounts().name(*id))
+            }
+            GoalLink::Tag(tag) => format_tags(std::slice::from_ref(tag)),
+        }
+    }
+
+    /// Tabel kemajuan semua tujuan tabungan.
+    fn show_goals(&self) -> Result<(), WalletError> {
+        let format = self.wallet.number_format();
+
+        outln!();
+        outln!("   {}", t!("goal.title"));
+        outln!("   {}", "─".repeat(132));
+
+        let goals = self.wallet.goals();
+        if goals.is_empty() {
+            outln!("   {}", t!("goal.empty"));
+            outln!();
+            return Ok(());
+        }
+
+        outln!(
+            "   {:<18} | {:>13} | {:>13} | {:<17} | {:<10} | {:>15} | {:<10} | {}",
+            t!("goal.column_name"),
+            t!("goal.column_saved"),
+            t!("goal.column_target"),
+            t!("goal.column_progress"),
+            t!("goal.column_deadline"),
+            t!("goal.column_monthly"),
+            t!("goal.column_projected"),
+            t!("goal.column_status")
+        );
+        outln!("   {}", "─".repeat(132));
+        for goal in goals.iter() {
+            let progress = self.wallet.goal_progress(goal)?;
+            let date = |date: Option<NaiveDate>| {
+                date.map(|date| date.format(DATE_FORMAT).to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            outln!(
+                "   {:<18} | {:>13} | {:>13} | {} {:>6} | {:<10} | {:>15} | {:<10} | {}",
+                truncate(&goal.name, 18),
+                format.brief(progress.saved, goal.currency),
+                format.brief(goal.target, goal.currency),
+                progress_bar(progress.percent(), 10),
+                format.percent(progress.percent()),
+                date(goal.deadline),
+                progress
+                    .required_monthly()?
+                    .map(|amount| format.brief(amount, goal.currency))
+                    .unwrap_or_else(|| "-".to_string()),
+                date(progress.projected()?),
+                progress
+                    .status()?
+                    .map(|status| status.to_string())
+                    .unwrap_or_default()
             );
-        } else {
-            println!("   HASIL FILTER: {}", filter_type);
-            println!("   {}", "─".repeat(50));
-            println!();
+        }
+        outln!("   {}", "─".repeat(132));
+        outln!("   {}", t!("goal.note", months = RECENT_MONTHS));
+        outln!();
+        Ok(())
+    }
 
-            for (i, items) in transactions.iter().enumerate() {
-                let symbol = match items.transaction_type {
-                    TypeTransaction::Income => "+",
-                    TypeTransaction::Expense => "-",
-                };
-                println!(
-                    "   {}. {} | {}Rp{}",
-                    i + 1,
-                    items.description,
-                    symbol,
-                    WalletList::format_currency(items.amount)
+    /// Rincian satu tujuan beserta riwayat setorannya.
+    fn show_goal_detail(&self, goal: &Goal) -> Result<(), WalletError> {
+        let progress = self.wallet.goal_progress(goal)?;
+        let format = self.wallet.number_format();
+        let money = |amount: Money| format.money(amount, goal.currency);
+        let none = || "-".to_string();
+
+        outln!();
+        outln!(
+            "   {}",
+            t!("goal.detail_title", name = goal.name.to_uppercase())
+        );
+        outln!("   {}", "─".repeat(80));
+        let row = |label: String, value: String| outln!("   {:<22}: {}", label, value);
+        row(t!("goal.label_source"), self.goal_link_label(goal));
+        row(t!("goal.column_target"), money(goal.target));
+        row(
+            t!("goal.column_saved"),
+            format!(
+                "{} {} {}",
+                money(progress.saved),
+                progress_bar(progress.percent(), 20),
+                format.percent(progress.percent())
+            ),
+        );
+        row(t!("goal.label_remaining"), money(progress.remaining()?));
+        row(
+            t!("goal.column_deadline"),
+            goal.deadline
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_else(none),
+        );
+        row(
+            t!("goal.label_required"),
+            progress
+                .required_monthly()?
+                .map(|amount| t!("goal.per_month", amount = money(amount)))
+                .unwrap_or_else(none),
+        );
+        row(
+            t!("goal.label_pace", months = RECENT_MONTHS),
+            t!("goal.per_month", amount = money(progress.pace())),
+        );
+        row(
+            t!("goal.column_projected"),
+            progress
+                .projected()?
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_else(none),
+        );
+        if let Some(status) = progress.status()? {
+            row(t!("goal.column_status"), status.to_string());
+        }
+
+        outln!();
+        outln!("   {}", t!("goal.contributions_title"));
+        outln!("   {}", "─".repeat(80));
+        if progress.contributions.is_empty() {
+            outln!("   {}", t!("goal.no_contributions"));
+        } else {
+            outln!(
+                "   {:<10} | {:<6} | {:>18} | {}",
+                t!("column.date"),
+                t!("column.id"),
+                t!("column.amount"),
+                t!("column.description")
+            );
+            for contribution in &progress.contributions {
+                outln!(
+                    "   {:<10} | {:<6} | {:>18} | {}",
+                    contribution.date.format(DATE_FORMAT).to_string(),
+                    contribution.transaction,
+                    format.signed(contribution.amount, goal.currency),
+                    truncate(&contribution.description, 36)
                 );
             }
-            println!();
+        }
+        outln!();
+        Ok(())
+    }
+
+    /// Satu baris kemajuan per tujuan untuk ringkasan keuangan.
+    fn print_goal_summary(&self) -> Result<(), WalletError> {
+        let goals = self.wallet.goals();
+        if goals.is_empty() {
+            return Ok(());
         }
 
+        let format = self.wallet.number_format();
+        for goal in goals.iter() {
+            let progress = self.wallet.goal_progress(goal)?;
+            outln!(
+                "   {}",
+                t!(
+                    "summary.goal",
+                    name = goal.name,
+                    percent = format.percent(progress.percent()),
+                    saved = format.money(progress.saved, goal.currency),
+                    target = format.money(goal.target, goal.currency)
+                )
+            );
+        }
+        outln!();
         Ok(())
     }
 
-    fn handle_search_transaction(&mut self) -> Result<(), WalletError> {
-        println!();
-        println!("   CARI TRANSAKSI");
-        println!("   {}", "─".repeat(30));
-        println!();
+    fn handle_undo_redo(&mut self) -> Result<(), WalletError> {
+        outln!();
+        outln!("   {}", t!("undo.title"));
+        outln!("   {}", "─".repeat(80));
 
-        let description = self.get_description()?;
-        let items = self.wallet.search_transaction(&description);
+        let (undo, redo) = self.wallet.history();
+        match undo {
+            Some(change) => {
+                outln!("   {}", t!("undo.next_undo", change = change.describe()));
+                self.print_change(change, true);
+            }
+            None => outln!("   {}", t!("undo.nothing_to_undo")),
+        }
+        outln!();
+        match redo {
+            Some(change) => {
+                outln!("   {}", t!("undo.next_redo", change = change.describe()));
+                self.print_change(change, false);
+            }
+            None => outln!("   {}", t!("undo.nothing_to_redo")),
+        }
+        outln!();
+        outln!("   1. {}", t!("undo.undo"));
+        outln!("   2. {}", t!("undo.redo"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
 
-        if items.is_empty() {
-            println!("   Tidak ada transaksi ditemukan");
-            println!("   Kata kunci: '{}'", description);
-        } else {
-            println!("   HASIL PENCARIAN");
-            println!("   {}", "─".repeat(50));
-            println!();
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-2"))?
+            .as_str()
+        {
+            "1" => {
+                let change = self.confirm_overdraft(|wallet, confirmed| wallet.undo(confirmed))?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!("undo.undone", change = change.describe())
+                );
+            }
+            "2" => {
+                let change = self.confirm_overdraft(|wallet, confirmed| wallet.redo(confirmed))?;
+                outln!(
+                    "\n   [SUCCESS] {}",
+                    t!("undo.redone", change = change.describe())
+                );
+            }
+            "0" => {}
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
+        }
+        Ok(())
+    }
 
-            for (i, item) in items.iter().enumerate() {
-                let symbol = match item.transaction_type {
-                    TypeTransaction::Income => "+",
-                    TypeTransaction::Expense => "-",
-                };
-                println!(
-                    "   {}. {} | {}Rp{}",
-                    i + 1,
-                    item.description,
-                    symbol,
-                    WalletList::format_currency(item.amount)
+    /// Mencetak apa yang akan terjadi pada setiap transaksi jika `change`
+    /// dibatalkan (`undo`) atau diulang.
+    fn print_change(&self, change: &Change, undo: bool) {
+        const SHOWN: usize = 10;
+
+        outln!(
+            "   {}",
+            t!(
+                "undo.recorded_at",
+                time = change.at.format("%d/%m/%Y %H:%M")
+            )
+        );
+        for revision in change.revisions.iter().take(SHOWN) {
+            let (from, to) = if undo {
+                (&revision.after, &revision.before)
+            } else {
+                (&revision.before, &revision.after)
+            };
+            self.print_revision(from.as_ref(), to.as_ref(), true);
+        }
+        if change.revisions.len() > SHOWN {
+            outln!(
+                "   {}",
+                t!("undo.more", count = change.revisions.len() - SHOWN)
+            );
+        }
+    }
+
+    /// Mencetak perubahan satu transaksi dari `from` ke `to`. Transaksi yang
+    /// muncul kembali disebut "dikembalikan" jika `restore`, selain itu
+    /// "ditambahkan".
+    fn print_revision(&self, from: Option<&WalletItems>, to: Option<&WalletItems>, restore: bool) {
+        match (from, to) {
+            (Some(from), None) => outln!(
+                "   - {}",
+                t!(
+                    "undo.will_remove",
+                    transaction = self.transaction_line(from)
+                )
+            ),
+            (None, Some(to)) => outln!(
+                "   + {}",
+                if restore {
+                    t!("undo.will_restore", transaction = self.transaction_line(to))
+                } else {
+                    t!("audit.added", transaction = self.transaction_line(to))
+                }
+            ),
+            (Some(from), Some(to)) => {
+                outln!(
+                    "   ~ {}",
+                    t!(
+                        "undo.will_change",
+                        transaction = self.transaction_line(from)
+                    )
                 );
+                let differences = self.differences(from, to);
+                if differences.is_empty() {
+                    outln!("       {}", t!("undo.fingerprints_only"));
+                }
+                for (label, old, new) in differences {
+                    outln!("       {}: {} → {}", label, old, new);
+                }
             }
-            println!();
+            (None, None) => {}
         }
+    }
 
-        self.pause();
+    fn handle_audit_log(&mut self) -> Result<(), WalletError> {
+        const SHOWN: usize = 20;
+
+        outln!();
+        outln!("   {}", t!("audit.filter_title"));
+        outln!("   {}", "─".repeat(30));
+        outln!();
+
+        let optional = |input: String| Some(input).filter(|input| !input.is_empty());
+        let actor = optional(self.read_line(&t!("audit.prompt_actor"))?);
+        let actions = AuditAction::value_variants();
+        outln!();
+        for (i, action) in actions.iter().enumerate() {
+            outln!("   {}. {}", i + 1, action);
+        }
+        let action = optional(self.read_line(&t!(
+            "audit.prompt_action",
+            range = format!("1-{}", actions.len())
+        ))?)
+        .map(|input| {
+            input
+                .parse::<usize>()
+                .ok()
+                .and_then(|choice| actions.get(choice.checked_sub(1)?))
+                .copied()
+                .ok_or_else(|| WalletError::InvalidInput(t!("error.choice")))
+        })
+        .transpose()?;
+        outln!();
+        let transaction = optional(self.read_line(&t!("audit.prompt_transaction"))?)
+            .map(|input| input.parse())
+            .transpose()?;
+        let from = optional(self.read_line(&t!("audit.prompt_from"))?)
+            .map(|input| parse_date(&input))
+            .transpose()?;
+        let to = optional(self.read_line(&t!("audit.prompt_to"))?)
+            .map(|input| parse_date(&input))
+            .transpose()?;
+
+        let entries = self.wallet.audit_log(&AuditFilter {
+            actor,
+            action,
+            transaction,
+            from,
+            to,
+        })?;
+        let older = entries.len().saturating_sub(SHOWN);
+        self.print_audit_log(&entries[older..]);
+        if older > 0 {
+            outln!("   {}", t!("audit.older", count = older));
+            outln!();
+        }
         Ok(())
     }
 
-    fn get_filter_type(&self) -> Result<TypeTransaction, WalletError> {
-        println!("   JENIS TRANSAKSI");
-        println!("   {}", "─".repeat(20));
-        println!("   1. Pemasukan");
-        println!("   2. Pengeluaran");
-        println!();
-        print!("   Pilih jenis (1/2): ");
-        io::stdout().flush().unwrap();
-
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+    fn handle_events(&mut self) -> Result<(), WalletError> {
+        const SHOWN: usize = 10;
 
-        let choice = input.trim().to_string();
+        let events = self.wallet.events()?;
+        self.print_events(&events[events.len().saturating_sub(SHOWN)..])?;
+        outln!("   1. {}", t!("events.balance_at"));
+        outln!("   2. {}", t!("events.verify"));
+        outln!("   3. {}", t!("events.rebuild"));
+        outln!("   0. {}", t!("menu.back"));
+        outln!();
 
-        match choice.to_lowercase().as_str() {
-            "1" | "pemasukan" => Ok(TypeTransaction::Income),
-            "2" | "pengeluaran" => Ok(TypeTransaction::Expense),
-            _ => Err(WalletError::InvalidInput("Pilihan tidak valid".to_string())),
+        match self
+            .read_line(&t!("prompt.choose_option", range = "0-3"))?
+            .as_str()
+        {
+            "1" => {
+                let date = parse_date(&self.read_line(&t!("prompt.date"))?)?;
+                let recorded = self.wallet.as_recorded(date)?;
+                outln!();
+                outln!(
+                    "   {}",
+                    t!("events.as_recorded", date = date.format(DATE_FORMAT))
+                );
+                recorded.total(None)?;
+            }
+            "2" => {
+                let revisions = self.wallet.rebuild(false)?;
+                self.print_rebuild(&revisions, false);
+            }
+            "3" => {
+                let revisions = self.wallet.rebuild(true)?;
+                self.print_rebuild(&revisions, true);
+            }
+            "0" => {}
+            _ => return Err(WalletError::InvalidInput(t!("error.choice"))),
         }
+        Ok(())
     }
 
-    fn get_index_transaction(&self) -> Result<usize, WalletError> {
-        print!("   Masukkan nomor transaksi: ");
-        io::stdout().flush().unwrap();
+    /// Mencetak `events` beserta posisi log dan snapshot terbarunya.
+    fn print_events(&mut self, events: &[WalletEvent]) -> Result<(), WalletError> {
+        let (seq, snapshot) = self.wallet.event_position()?;
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+        outln!();
+        outln!("   {}", t!("events.title"));
+        outln!("   {}", "─".repeat(90));
+        outln!(
+            "   {}",
+            match snapshot {
+                Some(snapshot) => t!("events.position", seq = seq, snapshot = snapshot),
+                None => t!("events.position_no_snapshot", seq = seq),
+            }
+        );
+        outln!();
 
-        let index = input
-            .trim()
-            .parse::<usize>()
-            .map_err(|_| WalletError::InvalidInput("Nomor transaksi tidak valid".to_string()))?;
+        if events.is_empty() {
+            outln!("   {}", t!("events.empty"));
+            outln!();
+            return Ok(());
+        }
 
-        Ok(index)
+        outln!(
+            "   {:>6}  {:<19}  {:<20}  {:<6}  {}",
+            t!("column.no"),
+            t!("column.time"),
+            t!("column.event"),
+            t!("column.id"),
+            t!("column.description")
+        );
+        outln!("   {}", "─".repeat(90));
+        for event in events {
+            let description = match &event.kind {
+                EventKind::TransactionAdded { item } | EventKind::TransactionAmended { item } => {
+                    truncate(&item.description, 30)
+                }
+                EventKind::TransactionDeleted { .. } => "-".to_string(),
+            };
+            outln!(
+                "   {:>6}  {:<19}  {:<20}  {:<6}  {}",
+                event.seq,
+                event.at.format("%d/%m/%Y %H:%M:%S").to_string(),
+                event.kind.label(),
+                event.kind.id(),
+                description
+            );
+        }
+        outln!();
+        Ok(())
     }
 
-    fn get_type_transaction(&self) -> Result<TypeTransaction, WalletError> {
-        println!("   JENIS TRANSAKSI");
-        println!("   {}", "─".repeat(20));
-        println!("   1. Pemasukan");
-        println!("   2. Pengeluaran");
-        println!();
-        print!("   Pilih jenis (1/2): ");
-        io::stdout().flush().unwrap();
-
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+    /// Mencetak beda transaksi tersimpan dengan hasil replay log peristiwa.
+    fn print_rebuild(&self, revisions: &[Revision], applied: bool) {
+        const SHOWN: usize = 10;
 
-        match input.trim().to_lowercase().as_str() {
-            "1" | "pemasukan" => Ok(TypeTransaction::Income),
-            "2" | "pengeluaran" => Ok(TypeTransaction::Expense),
-            _ => Err(WalletError::InvalidInput(
-                "Jenis transaksi tidak valid".to_string(),
-            )),
+        outln!();
+        if revisions.is_empty() {
+            outln!("   [SUCCESS] {}", t!("events.consistent"));
+            outln!();
+            return;
+        }
+        if applied {
+            outln!(
+                "   [SUCCESS] {}",
+                t!("events.rebuilt", count = revisions.len())
+            );
+        } else {
+            outln!(
+                "   [WARNING] {}",
+                t!("events.differences", count = revisions.len())
+            );
+        }
+        for revision in revisions.iter().take(SHOWN) {
+            self.print_revision(revision.before.as_ref(), revision.after.as_ref(), true);
+        }
+        if revisions.len() > SHOWN {
+            outln!("   {}", t!("undo.more", count = revisions.len() - SHOWN));
         }
+        if !applied {
+            outln!("   {}", t!("events.rebuild_hint"));
+        }
+        outln!();
     }
 
-    fn get_amount(&self) -> Result<i64, WalletError> {
-        print!("   Masukkan jumlah (Rp): ");
-        io::stdout().flush().unwrap();
+    /// Mencetak entri log audit beserta nilai sebelum dan sesudah setiap
+    /// transaksi yang berubah.
+    fn print_audit_log(&self, entries: &[(usize, AuditEntry)]) {
+        const SHOWN: usize = 10;
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+        outln!();
+        outln!("   {}", t!("audit.title"));
+        outln!("   {}", "─".repeat(80));
 
-        let amount = input
-            .trim()
-            .replace(".", "")
-            .replace(",", "")
-            .replace("Rp", "")
-            .trim()
-            .parse()
-            .map_err(|_| WalletError::ParseError)?;
+        if entries.is_empty() {
+            outln!("   {}", t!("audit.empty"));
+            outln!();
+            return;
+        }
 
-        if amount <= 0 {
-            return Err(WalletError::InvalidInput(
-                "Jumlah harus lebih besar dari 0".to_string(),
-            ));
+        for (seq, entry) in entries {
+            outln!(
+                "   {}",
+                t!(
+                    "audit.entry",
+                    seq = seq,
+                    time = entry.at.format("%d/%m/%Y %H:%M:%S"),
+                    actor = entry.actor,
+                    action = entry.action
+                )
+            );
+            for revision in entry.revisions.iter().take(SHOWN) {
+                self.print_revision(revision.before.as_ref(), revision.after.as_ref(), false);
+            }
+            if entry.revisions.len() > SHOWN {
+                outln!(
+                    "   {}",
+                    t!("undo.more", count = entry.revisions.len() - SHOWN)
+                );
+            }
+            if !entry.sections.is_empty() {
+                outln!(
+                    "   * {}",
+                    t!("audit.sections", sections = entry.sections.join(", "))
+                );
+            }
+            outln!();
         }
+    }
 
-        Ok(amount)
+    /// Satu baris ringkas, misalnya "T7 17/10/2026 -Rp25.000 Makan siang".
+    fn transaction_line(&self, item: &WalletItems) -> String {
+        format!(
+            "{} {} {} {}",
+            item.id,
+            item.date.format(DATE_FORMAT),
+            item.amount_label(None, &self.wallet.number_format()),
+            truncate(&item.description, 30)
+        )
     }
 
-    fn get_description(&self) -> Result<String, WalletError> {
-        print!("   Masukkan deskripsi: ");
-        io::stdout().flush().unwrap();
+    /// Field yang berbeda antara dua keadaan transaksi: label, nilai lama
+    /// dan nilai baru.
+    fn differences(&self, old: &WalletItems, new: &WalletItems) -> Vec<(String, String, String)> {
+        let format = self.wallet.number_format();
+        let values = |item: &WalletItems| {
+            [
+                item.description.clone(),
+                item.amount_label(None, &format),
+                item.date.format(DATE_FORMAT).to_string(),
+                item.transaction_type.to_string(),
+                self.wallet.category_label(item.category),
+                self.wallet.account_label(item),
+                match item.tags.as_slice() {
+                    [] => "-".to_string(),
+                    tags => format_tags(tags),
+                },
+            ]
+        };
+        let labels = [
+            t!("column.description"),
+            t!("column.amount"),
+            t!("column.date"),
+            t!("column.type"),
+            t!("column.category"),
+            t!("column.account"),
+            t!("column.tags"),
+        ];
+        labels
+            .into_iter()
+            .zip(values(old).into_iter().zip(values(new)))
+            .filter(|(_, (old, new))| old != new)
+            .map(|(label, (old, new))| (label, old, new))
+            .collect()
+    }
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|_| WalletError::InvalidInput("Input tidak valid".to_string()))?;
+    fn show_tag_report(&self) -> Result<(), WalletError> {
+        let report = self.wallet.tag_report()?;
+        let currency = self.wallet.reporting_currency();
+        let format = self.wallet.number_format();
 
-        let description = input.trim().to_string();
+        outln!();
+        outln!("   {}", t!("tags.title"));
+        outln!("   {}", "─".repeat(78));
 
-        if description.is_empty() {
-            return Err(WalletError::InvalidInput(
-                "Deskripsi tidak boleh kosong".to_string(),
-            ));
+        if report.is_empty() {
+            outln!("   {}", t!("tags.empty"));
+            outln!();
+            return Ok(());
         }
 
-        Ok(description)
+        outln!(
+            "   {:<22} | {:>6} | {:>15} | {:>15} | {:>15}",
+            t!("tags.column_tag"),
+            t!("tags.column_count"),
+            t!("type.income"),
+            t!("type.expense"),
+            t!("tags.column_net")
+        );
+        outln!("   {}", "─".repeat(78));
+
+        for entry in &report {
+            let net = entry.income.checked_sub(entry.expense)?;
+            outln!(
+                "   {:<22} | {:>6} | {:>15} | {:>15} | {:>15}",
+                truncate(&format!("#{}", entry.tag), 22),
+                entry.count,
+                format.brief_signed(entry.income, currency),
+                format.brief(-entry.expense, currency),
+                format.brief_signed(net, currency)
+            );
+        }
+        outln!("   {}", "─".repeat(78));
+        outln!("   {}", t!("tags.note"));
+        outln!();
+        Ok(())
     }
 
-    fn show_summary(&self) {
-        let (total_income, total_expense, balance) = self.wallet.get_summary();
+    fn show_summary(&self) -> Result<(), WalletError> {
+        let (total_income, total_expense, balance) = self.wallet.get_summary()?;
+        let currency = self.wallet.reporting_currency();
+        let format = self.wallet.number_format();
 
-        println!();
-        println!("   RINGKASAN KEUANGAN");
-        println!("   {}", "─".repeat(50));
-        println!();
-        println!(
-            "   Total Pemasukan  : +Rp{}",
-            WalletList::format_currency(total_income)
+        outln!();
+        outln!("   {}", t!("summary.title"));
+        outln!("   {}", "─".repeat(50));
+        outln!();
+        outln!(
+            "   {}",
+            t!(
+                "summary.income",
+                amount = format.signed(total_income, currency)
+            )
         );
-        println!(
-            "   Total Pengeluaran: -Rp{}",
-            WalletList::format_currency(total_expense)
+        outln!(
+            "   {}",
+            t!(
+                "summary.expense",
+                amount = format.money(-total_expense, currency)
+            )
         );
-        println!("   {}", "─".repeat(50));
+        outln!("   {}", "─".repeat(50));
 
-        let (status_text, symbol) = if balance > 0 {
-            ("SURPLUS", "+")
-        } else if balance == 0 {
-            ("SEIMBANG", "")
+        let status_text = if balance.is_positive() {
+            t!("status.surplus")
+        } else if balance.is_zero() {
+            t!("status.balanced")
         } else {
-            ("DEFISIT", "-")
+            t!("status.deficit")
         };
 
-        println!(
-            "   Saldo Akhir      : {}Rp{}",
-            symbol,
-            WalletList::format_currency(balance.abs())
+        outln!(
+            "   {}",
+            t!("summary.balance", amount = format.signed(balance, currency))
         );
-        println!("   Status Keuangan  : {}", status_text);
-        println!();
+        outln!("   {}", t!("summary.status", status = status_text));
+        outln!();
+
+        // Dengan beberapa mata uang, total di atas adalah hasil konversi;
+        // jumlah aslinya ditampilkan per mata uang.
+        let by_currency = self.wallet.summary_by_currency()?;
+        if by_currency.len() > 1 || by_currency.iter().any(|t| t.currency != currency) {
+            outln!("   {}", t!("summary.by_currency"));
+            outln!("   {}", "─".repeat(50));
+            for total in &by_currency {
+                outln!(
+                    "   {:<5} {:<21} {}",
+                    total.currency,
+                    format.signed(total.income, total.currency),
+                    format.money(-total.expense, total.currency)
+                );
+            }
+            outln!("   {}", t!("summary.converted", currency = currency));
+            outln!();
+        }
 
         // Saran keuangan
-        if balance > 0 {
-            let savings_rate = (balance as f64 / total_income as f64) * 100.0;
-            println!(
-                "   Rasio Tabungan: {:.1}% - Kondisi keuangan bagus!",
-                savings_rate
+        if balance.is_positive() {
+            let savings_rate = (balance.minor() as f64 / total_income.minor() as f64) * 100.0;
+            outln!(
+                "   {}",
+                t!("summary.savings_rate", rate = format.percent(savings_rate))
             );
-        } else if balance == 0 {
-            println!("   Pengeluaran sama dengan pemasukan - pertahankan keseimbangan ini!");
+        } else if balance.is_zero() {
+            outln!("   {}", t!("summary.advice_balanced"));
         } else {
-            println!("   Pertimbangkan untuk mengurangi pengeluaran atau menambah pemasukan.");
+            outln!("   {}", t!("summary.advice_deficit"));
         }
-        println!();
+        outln!();
+        self.print_goal_summary()?;
+
+        self.print_category_breakdown(TypeTransaction::Expense, total_expense)?;
+        self.print_category_breakdown(TypeTransaction::Income, total_income)
     }
 
     fn show_welcome_animation(&self) {
         let messages = vec![
-            "Memuat sistem",
-            "Menginisialisasi dompet",
-            "Mempersiapkan antarmuka",
-            "Siap digunakan",
+            t!("welcome.loading"),
+            t!("welcome.initializing"),
+            t!("welcome.preparing"),
+            t!("welcome.ready"),
         ];
 
         for message in &messages {
-            print!("\r   {}...", message);
-            io::stdout().flush().unwrap();
+            out!("\r   {}...", message);
             thread::sleep(Duration::from_millis(400));
         }
-        println!("\r   Sistem Manajemen Dompet Siap!");
+        outln!("\r   {}", t!("welcome.done"));
+    }
+}
+
+pub fn wallet_app() -> ExitCode {
+    // Urutan pilihan bahasa: opsi --lang, WALLET_LANG, lalu pengaturan yang
+    // disimpan; tanpa ketiganya dipakai bahasa Indonesia. Bahasa dipilih
+    // sebelum baris perintah diurai agar teks bantuan ikut diterjemahkan;
+    // galat saat membuka data baru dilaporkan setelah penguraian.
+    let requested = cli::Cli::requested_language().or_else(Language::from_env);
+    if let Some(language) = requested {
+        i18n::set_language(language);
+    }
+    let app = WalletApp::new();
+    if requested.is_none()
+        && let Ok(app) = &app
+        && let Some(language) = app.wallet.language()
+    {
+        i18n::set_language(language);
+    }
+
+    let cli = cli::Cli::parse_localized();
+    if let Some(language) = cli.lang {
+        i18n::set_language(language);
+    }
+
+    let mut app = match app {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("\n   [ERROR] {}", e);
+            return e.exit_code();
+        }
+    };
+    match cli.command {
+        // Mode perintah: jalankan satu perintah tanpa animasi lalu keluar.
+        Some(command) => match app.run_command(command, cli.format, cli.allow_overdraft) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("   [ERROR] {}", e);
+                e.exit_code()
+            }
+        },
+        None => {
+            // Tampilkan animasi selamat datang
+            app.show_welcome_animation();
+            thread::sleep(Duration::from_millis(800));
+
+            app.run();
+            ExitCode::SUCCESS
+        }
     }
 }
 
-pub fn wallet_app() {
-    let mut app = WalletApp::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rupiah(amount: i64) -> Money {
+        Money::from_major(amount, Currency::IDR).unwrap()
+    }
+
+    fn transaction(transaction_type: TypeTransaction, amount: i64) -> WalletItems {
+        WalletItems::new(
+            format!("{} {}", transaction_type, amount),
+            rupiah(amount),
+            transaction_type,
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            None,
+            Vec::new(),
+            1,
+        )
+    }
+
+    /// Dompet dengan pemasukan 100.000 dan pengeluaran 60.000 di akun Tunai
+    /// berkebijakan `policy`. Mengembalikan ID pemasukan dan pengeluarannya.
+    fn funded_wallet(policy: OverdraftPolicy) -> (WalletList, TransactionId, TransactionId) {
+        let mut wallet = WalletList::new();
+        let income = wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        let expense = wallet
+            .add(transaction(TypeTransaction::Expense, 60_000), false)
+            .unwrap();
+        wallet.set_overdraft(1, policy).unwrap();
+        (wallet, income, expense)
+    }
+
+    #[test]
+    fn forbid_rejects_update_and_delete_that_overdraw() {
+        let (mut wallet, income, expense) = funded_wallet(OverdraftPolicy::Forbid);
+
+        let raised = transaction(TypeTransaction::Expense, 150_000);
+        let error = wallet.update(expense, raised, false).unwrap_err();
+        assert!(matches!(error, WalletError::InsufficientFunds(_)));
+        assert_eq!(error.exit_code(), ExitCode::from(4));
+
+        let error = wallet.remove_transaction(income, false).unwrap_err();
+        assert!(matches!(error, WalletError::InsufficientFunds(_)));
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(40_000));
+        assert_eq!(wallet.data.items.len(), 2);
+
+        // Perubahan yang saldonya tetap positif selalu boleh.
+        let lowered = transaction(TypeTransaction::Expense, 90_000);
+        wallet.update(expense, lowered, false).unwrap();
+    }
+
+    #[test]
+    fn allow_accepts_overdraft_within_the_limit() {
+        let limit = Some(rupiah(100_000));
+        let (mut wallet, income, expense) = funded_wallet(OverdraftPolicy::Allow { limit });
+
+        let raised = transaction(TypeTransaction::Expense, 150_000);
+        wallet.update(expense, raised, false).unwrap();
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(-50_000));
+
+        // Tanpa pemasukan saldonya -150.000, melewati batas 100.000.
+        let error = wallet.remove_transaction(income, false).unwrap_err();
+        assert!(matches!(error, WalletError::InsufficientFunds(_)));
+
+        let (mut wallet, income, _) = funded_wallet(OverdraftPolicy::Allow { limit });
+        wallet.remove_transaction(income, false).unwrap();
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(-60_000));
+    }
+
+    #[test]
+    fn confirm_requires_confirmation_before_overdrawing() {
+        let (mut wallet, income, expense) = funded_wallet(OverdraftPolicy::Confirm);
+
+        let raised = transaction(TypeTransaction::Expense, 150_000);
+        let error = wallet.update(expense, raised.clone(), false).unwrap_err();
+        assert!(matches!(error, WalletError::OverdraftConfirmation(_)));
+        assert_eq!(error.exit_code(), ExitCode::from(6));
+        let error = wallet.remove_transaction(income, false).unwrap_err();
+        assert_eq!(error.exit_code(), ExitCode::from(6));
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(40_000));
+
+        wallet.update(expense, raised, true).unwrap();
+        wallet.remove_transaction(income, true).unwrap();
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(-150_000));
+    }
+
+    #[test]
+    fn undo_and_redo_add_update_and_delete() {
+        let mut wallet = WalletList::new();
+        let id = wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        let added = wallet.data.items.clone();
+
+        wallet.undo(false).unwrap();
+        assert!(wallet.data.items.is_empty());
+        wallet.redo(false).unwrap();
+        assert_eq!(wallet.data.items, added);
+
+        let raised = transaction(TypeTransaction::Income, 120_000);
+        wallet.update(id, raised, false).unwrap();
+        let updated = wallet.data.items.clone();
+        assert_eq!(wallet.undo(false).unwrap().kind, ChangeKind::Update);
+        assert_eq!(wallet.data.items, added);
+        wallet.redo(false).unwrap();
+        assert_eq!(wallet.data.items, updated);
+
+        wallet.remove_transaction(id, false).unwrap();
+        assert_eq!(wallet.undo(false).unwrap().kind, ChangeKind::Delete);
+        assert_eq!(wallet.data.items, updated);
+        wallet.redo(false).unwrap();
+        assert!(wallet.data.items.is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_an_import_as_one_step() {
+        let mut wallet = WalletList::new();
+        let existing = wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        let before = wallet.data.items.clone();
+
+        let mut merged = transaction(TypeTransaction::Income, 100_000);
+        merged.tags = vec!["gaji".to_string()];
+        let items = vec![
+            transaction(TypeTransaction::Expense, 10_000),
+            transaction(TypeTransaction::Expense, 20_000),
+        ];
+        wallet
+            .import(items, vec![(existing, merged)], false)
+            .unwrap();
+        let imported = wallet.data.items.clone();
+        assert_eq!(imported.len(), 3);
+
+        let change = wallet.undo(false).unwrap();
+        assert_eq!(change.kind, ChangeKind::Import);
+        assert_eq!(change.revisions.len(), 3);
+        assert_eq!(wallet.data.items, before);
+        wallet.redo(false).unwrap();
+        assert_eq!(wallet.data.items, imported);
+    }
+
+    #[test]
+    fn new_change_clears_the_redo_stack() {
+        let mut wallet = WalletList::new();
+        wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        wallet.undo(false).unwrap();
+        assert!(wallet.history().1.is_some());
+
+        wallet
+            .add(transaction(TypeTransaction::Income, 50_000), false)
+            .unwrap();
+        assert!(wallet.history().1.is_none());
+        assert!(matches!(
+            wallet.redo(false),
+            Err(WalletError::InvalidInput(_))
+        ));
+        assert_eq!(wallet.data.items.len(), 1);
+    }
+
+    #[test]
+    fn history_survives_a_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("wallet-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let open = || {
+            let storage = storage::FileStorage::new(dir.join("wallet.json"));
+            WalletList::with_storage(Box::new(storage)).unwrap()
+        };
+
+        let mut wallet = open();
+        wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        wallet
+            .add(transaction(TypeTransaction::Expense, 25_000), false)
+            .unwrap();
+        wallet.undo(false).unwrap();
+        let saved = wallet.data.items.clone();
+        drop(wallet);
+
+        let mut wallet = open();
+        assert_eq!(wallet.data.items, saved);
+        let (undo, redo) = wallet.history();
+        assert_eq!(undo.map(|change| change.kind), Some(ChangeKind::Add));
+        assert_eq!(redo.map(|change| change.kind), Some(ChangeKind::Add));
+
+        wallet.redo(false).unwrap();
+        assert_eq!(wallet.data.items.len(), 2);
+        drop(wallet);
 
-    // Tampilkan animasi selamat datang
-    app.show_welcome_animation();
-    thread::sleep(Duration::from_millis(800));
+        let mut wallet = open();
+        assert_eq!(wallet.data.items.len(), 2);
+        wallet.undo(false).unwrap();
+        wallet.undo(false).unwrap();
+        assert!(wallet.data.items.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Jumlah posting per mata uang; debit positif, kredit negatif.
+    fn posting_totals(postings: &[Posting]) -> Vec<(Currency, Money)> {
+        let mut totals: Vec<(Currency, Money)> = Vec::new();
+        for posting in postings {
+            match totals.iter_mut().find(|(c, _)| *c == posting.currency) {
+                Some((_, total)) => *total = total.checked_add(posting.amount).unwrap(),
+                None => totals.push((posting.currency, posting.amount)),
+            }
+        }
+        totals
+    }
+
+    #[test]
+    fn postings_sum_to_zero_per_currency() {
+        let sgd: Currency = "SGD".parse().unwrap();
+        let cross = WalletItems::transfer(
+            "Kirim ke Singapura".to_string(),
+            rupiah(1_000_000),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            1,
+            2,
+            Vec::new(),
+        )
+        .receiving(Money::from_major(85, sgd).unwrap(), sgd);
+        let transfer = WalletItems::transfer(
+            "Tarik tunai".to_string(),
+            rupiah(50_000),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            2,
+            1,
+            Vec::new(),
+        );
+
+        for item in [
+            transaction(TypeTransaction::Income, 100_000),
+            transaction(TypeTransaction::Expense, 25_000),
+            transfer,
+            cross.clone(),
+        ] {
+            let postings = item.postings();
+            let totals = posting_totals(&postings);
+            assert!(
+                totals.iter().all(|(_, total)| total.is_zero()),
+                "{}: {:?}",
+                item.description,
+                totals
+            );
+            check_balanced(&postings, &NumberFormat::default()).unwrap();
+        }
+
+        let cross_currencies: Vec<Currency> = posting_totals(&cross.postings())
+            .into_iter()
+            .map(|(currency, _)| currency)
+            .collect();
+        assert_eq!(cross_currencies, [Currency::IDR, sgd]);
+    }
+
+    #[test]
+    fn trial_balance_debits_equal_credits() {
+        let sgd: Currency = "SGD".parse().unwrap();
+        let mut wallet = WalletList::new();
+        let bank = wallet
+            .add_account(
+                "Bank".to_string(),
+                AccountKind::Asset,
+                Currency::IDR,
+                rupiah(500_000),
+                None,
+            )
+            .unwrap();
+        let overseas = wallet
+            .add_account(
+                "DBS".to_string(),
+                AccountKind::Asset,
+                sgd,
+                Money::ZERO,
+                None,
+            )
+            .unwrap();
+        wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        wallet
+            .add(transaction(TypeTransaction::Expense, 25_000), false)
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let transfer = WalletItems::transfer(
+            "Setor".to_string(),
+            rupiah(50_000),
+            date,
+            1,
+            bank,
+            Vec::new(),
+        );
+        wallet.add(transfer, false).unwrap();
+        let cross = WalletItems::transfer(
+            "Kirim".to_string(),
+            rupiah(200_000),
+            date,
+            bank,
+            overseas,
+            Vec::new(),
+        )
+        .receiving(Money::from_major(17, sgd).unwrap(), sgd);
+        wallet.add(cross, false).unwrap();
+
+        let rows = wallet.trial_balance().unwrap();
+        for currency in [Currency::IDR, sgd] {
+            let rows: Vec<_> = rows.iter().filter(|row| row.currency == currency).collect();
+            let debit = Money::sum(rows.iter().map(|row| row.debit)).unwrap();
+            let credit = Money::sum(rows.iter().map(|row| row.credit)).unwrap();
+            assert!(debit.is_positive());
+            assert_eq!(debit, credit, "{}", currency);
+        }
+
+        let balance = |account| {
+            rows.iter()
+                .find(|row| row.account == LedgerAccount::Account(account))
+                .map(|row| row.debit.checked_sub(row.credit).unwrap())
+        };
+        assert_eq!(balance(1), Some(rupiah(25_000)));
+        assert_eq!(balance(bank), Some(rupiah(350_000)));
+        assert_eq!(balance(overseas), Some(Money::from_major(17, sgd).unwrap()));
+    }
+
+    #[test]
+    fn balance_as_of_counts_transactions_up_to_the_date() {
+        let mut wallet = WalletList::new();
+        let day = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        for (amount, date) in [(100_000, day(5)), (-30_000, day(10)), (-20_000, day(15))] {
+            let mut item = if amount > 0 {
+                transaction(TypeTransaction::Income, amount)
+            } else {
+                transaction(TypeTransaction::Expense, -amount)
+            };
+            item.date = date;
+            wallet.add(item, false).unwrap();
+        }
+
+        assert_eq!(wallet.balance_as_of(None, day(4)).unwrap(), Money::ZERO);
+        assert_eq!(wallet.balance_as_of(Some(1), day(4)).unwrap(), Money::ZERO);
+        assert_eq!(wallet.balance_as_of(None, day(5)).unwrap(), rupiah(100_000));
+        assert_eq!(
+            wallet.balance_as_of(Some(1), day(10)).unwrap(),
+            rupiah(70_000)
+        );
+        assert_eq!(wallet.balance_as_of(None, day(14)).unwrap(), rupiah(70_000));
+        assert_eq!(
+            wallet.balance_as_of(None, day(31)).unwrap(),
+            wallet.calculate_balance(None).unwrap()
+        );
+    }
+
+    #[test]
+    fn running_balances_follow_each_account_through_transfers() {
+        let mut wallet = WalletList::new();
+        let bank = wallet
+            .add_account(
+                "Bank".to_string(),
+                AccountKind::Asset,
+                Currency::IDR,
+                Money::ZERO,
+                None,
+            )
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        let transfer = WalletItems::transfer(
+            "Setor".to_string(),
+            rupiah(40_000),
+            date,
+            1,
+            bank,
+            Vec::new(),
+        );
+        wallet.add(transfer, false).unwrap();
+        wallet
+            .add(transaction(TypeTransaction::Expense, 10_000), false)
+            .unwrap();
+
+        let running = |account| wallet.running_balances(account).unwrap();
+        assert_eq!(
+            running(Some(1)),
+            [rupiah(100_000), rupiah(60_000), rupiah(50_000)]
+        );
+        assert_eq!(
+            running(Some(bank)),
+            [Money::ZERO, rupiah(40_000), rupiah(40_000)]
+        );
+        // Transfer tidak mengubah saldo gabungan.
+        assert_eq!(
+            running(None),
+            [rupiah(100_000), rupiah(100_000), rupiah(90_000)]
+        );
+        assert_eq!(
+            running(Some(bank)).last(),
+            Some(&wallet.calculate_balance(Some(bank)).unwrap())
+        );
+    }
+
+    #[test]
+    fn dates_are_parsed_as_day_month_year() {
+        let date = parse_date(" 05/03/2026 ").unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2026, 3, 5).unwrap());
+        assert!(parse_date("2026-03-05").is_err());
+        assert!(parse_date("31/02/2026").is_err());
+    }
+
+    #[test]
+    fn transactions_stay_in_date_order_and_keep_created_at() {
+        let mut wallet = WalletList::new();
+        let mut later = transaction(TypeTransaction::Income, 100_000);
+        later.date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let later = wallet.add(later, false).unwrap();
+        let earlier = wallet
+            .add(transaction(TypeTransaction::Income, 50_000), false)
+            .unwrap();
+        let ids: Vec<_> = wallet.data.items.iter().map(|item| item.id).collect();
+        assert_eq!(ids, [earlier, later]);
+
+        let created_at = wallet.data.items[0].created_at;
+        let mut moved = transaction(TypeTransaction::Income, 50_000);
+        moved.date = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        wallet.update(earlier, moved, false).unwrap();
+        let ids: Vec<_> = wallet.data.items.iter().map(|item| item.id).collect();
+        assert_eq!(ids, [later, earlier]);
+        let item = &wallet.data.items[1];
+        assert_eq!(item.created_at, created_at);
+        assert!(item.modified_at >= created_at);
+    }
+
+    #[test]
+    fn transaction_ids_are_parsed_with_prefix() {
+        assert_eq!(" t7 ".parse::<TransactionId>().unwrap(), TransactionId(7));
+        assert_eq!(TransactionId(7).to_string(), "T7");
+        for invalid in ["7", "T0", "Tx", "T-1"] {
+            assert!(invalid.parse::<TransactionId>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn transactions_resolve_by_id_or_row_and_ids_are_not_reused() {
+        let mut wallet = WalletList::new();
+        let first = wallet
+            .add(transaction(TypeTransaction::Income, 100_000), false)
+            .unwrap();
+        let second = wallet
+            .add(transaction(TypeTransaction::Income, 50_000), false)
+            .unwrap();
+        assert_eq!(wallet.resolve("2").unwrap(), second);
+        assert_eq!(wallet.resolve(&first.to_string()).unwrap(), first);
+        assert!(wallet.resolve("3").is_err());
+        assert!(wallet.resolve("T9").is_err());
+
+        wallet.remove_transaction(second, false).unwrap();
+        let third = wallet
+            .add(transaction(TypeTransaction::Income, 25_000), false)
+            .unwrap();
+        assert!(third > second);
+        assert!(wallet.resolve(&second.to_string()).is_err());
+    }
+
+    #[test]
+    fn transfer_moves_money_between_accounts() {
+        let mut wallet = WalletList::new();
+        let bank = wallet
+            .add_account(
+                "BCA".to_string(),
+                AccountKind::Asset,
+                Currency::IDR,
+                rupiah(500_000),
+                None,
+            )
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let transfer = |amount| {
+            WalletItems::transfer(
+                "Tarik tunai".to_string(),
+                rupiah(amount),
+                date,
+                bank,
+                1,
+                Vec::new(),
+            )
+        };
+        wallet.add(transfer(200_000), false).unwrap();
+        assert_eq!(
+            wallet.calculate_balance(Some(bank)).unwrap(),
+            rupiah(300_000)
+        );
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(200_000));
+        assert_eq!(wallet.calculate_balance(None).unwrap(), rupiah(500_000));
+
+        // Transfer yang membuat akun asal minus ditolak seluruhnya.
+        let error = wallet.add(transfer(400_000), false).unwrap_err();
+        assert!(matches!(error, WalletError::InsufficientFunds(_)));
+        assert_eq!(wallet.calculate_balance(Some(1)).unwrap(), rupiah(200_000));
+
+        assert!(wallet.remove_account(bank).is_err());
+    }
+
+    #[test]
+    fn budget_alerts_when_spending_crosses_the_threshold() {
+        let mut wallet = WalletList::new();
+        wallet
+            .set_overdraft(1, OverdraftPolicy::Allow { limit: None })
+            .unwrap();
+        let food = wallet.data.categories.find_path("Makanan").unwrap();
+        let lunch = wallet.data.categories.find_path("Makan Siang").unwrap();
+        wallet.set_budget(food, rupiah(100_000)).unwrap();
+
+        let mut spend = |amount| {
+            let mut item = transaction(TypeTransaction::Expense, amount);
+            item.category = Some(lunch);
+            let id = wallet.add(item, false).unwrap();
+            let threshold = wallet.data.budgets.threshold();
+            let alerts = wallet.budget_alerts(wallet.get(id).unwrap()).unwrap();
+            alerts
+                .iter()
+                .map(|status| status.level(threshold))
+                .collect::<Vec<_>>()
+        };
+        assert!(spend(50_000).is_empty());
+        assert_eq!(spend(30_000), [BudgetLevel::Warning]);
+        assert!(spend(10_000).is_empty());
+        assert_eq!(spend(20_000), [BudgetLevel::Over]);
+
+        let status = wallet.budget_status(NaiveDate::from_ymd_opt(2026, 3, 15).unwrap());
+        assert_eq!(status.unwrap()[0].spent, rupiah(110_000));
+    }
+
+    #[test]
+    fn every_change_is_written_to_the_audit_log() {
+        let (mut wallet, income, expense) = funded_wallet(OverdraftPolicy::Forbid);
+        wallet
+            .update(
+                expense,
+                transaction(TypeTransaction::Expense, 70_000),
+                false,
+            )
+            .unwrap();
+        wallet.remove_transaction(expense, false).unwrap();
+        wallet.remove_transaction(income, false).unwrap();
+        // Perubahan yang ditolak tidak dicatat.
+        assert!(
+            wallet
+                .add(transaction(TypeTransaction::Expense, 10_000), false)
+                .is_err()
+        );
+
+        let log = wallet.audit_log(&AuditFilter::default()).unwrap();
+        let actions: Vec<_> = log.iter().map(|(_, entry)| entry.action).collect();
+        assert_eq!(
+            actions,
+            [
+                AuditAction::Add,
+                AuditAction::Add,
+                AuditAction::Account,
+                AuditAction::Update,
+                AuditAction::Delete,
+                AuditAction::Delete,
+            ]
+        );
 
-    app.run();
+        let filter = AuditFilter {
+            transaction: Some(expense),
+            action: Some(AuditAction::Update),
+            ..AuditFilter::default()
+        };
+        let log = wallet.audit_log(&filter).unwrap();
+        let [(number, entry)] = log.as_slice() else {
+            panic!("harus tepat satu entri: {:?}", log);
+        };
+        assert_eq!(*number, 4);
+        let revision = &entry.revisions[0];
+        assert_eq!(revision.before.as_ref().unwrap().amount, rupiah(60_000));
+        assert_eq!(revision.after.as_ref().unwrap().amount, rupiah(70_000));
+    }
 }
@@ -0,0 +1,351 @@
+use std::{fmt::Display, ops::Neg, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::{WalletError, i18n::t};
+
+/// Kode mata uang ISO 4217, disimpan sebagai teks seperti `"IDR"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub(super) struct Currency([u8; 3]);
+
+impl Currency {
+    pub(super) const IDR: Currency = Currency(*b"IDR");
+
+    pub(super) fn code(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    /// Simbol yang ditulis menempel di depan angka, misalnya "Rp" atau
+    /// "S$". Mata uang tanpa simbol yang dikenal memakai kodenya.
+    pub(super) fn symbol(&self) -> &str {
+        match &self.0 {
+            b"IDR" => "Rp",
+            b"USD" => "US$",
+            b"SGD" => "S$",
+            b"AUD" => "A$",
+            b"MYR" => "RM",
+            b"EUR" => "€",
+            b"GBP" => "£",
+            b"JPY" => "¥",
+            b"KRW" => "₩",
+            b"THB" => "฿",
+            b"INR" => "₹",
+            _ => self.code(),
+        }
+    }
+
+    /// Jumlah digit satuan terkecil menurut ISO 4217, misalnya 2 untuk
+    /// rupiah (sen) dan dolar (sen), 0 untuk yen.
+    pub(super) fn minor_units(self) -> u32 {
+        match &self.0 {
+            b"JPY" | b"KRW" | b"VND" | b"CLP" | b"ISK" | b"PYG" | b"UGX" | b"XAF" | b"XOF" => 0,
+            b"BHD" | b"IQD" | b"JOD" | b"KWD" | b"LYD" | b"OMR" | b"TND" => 3,
+            _ => 2,
+        }
+    }
+
+    fn scale(self) -> i64 {
+        10i64.pow(self.minor_units())
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::IDR
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = WalletError;
+
+    /// Menerima kode tiga huruf tanpa memedulikan huruf besar/kecil, misalnya "usd".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim().to_ascii_uppercase();
+        match <[u8; 3]>::try_from(code.as_bytes()) {
+            Ok(bytes) if bytes.iter().all(u8::is_ascii_uppercase) => Ok(Currency(bytes)),
+            _ => Err(WalletError::InvalidInput(t!(
+                "error.currency_code",
+                code = s.trim()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = WalletError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.code().to_string()
+    }
+}
+
+/// Jumlah uang dalam satuan terkecil mata uangnya, misalnya sen untuk
+/// rupiah: Rp12.500,50 disimpan sebagai 1250050.
+///
+/// Semua penjumlahan memakai aritmetika yang diperiksa; hasil di luar
+/// jangkauan `i64` menjadi `WalletError::Overflow`, bukan nilai yang
+/// berputar (wrap) diam-diam.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Money(i64);
+
+impl Money {
+    pub(super) const ZERO: Money = Money(0);
+
+    pub(super) fn minor(self) -> i64 {
+        self.0
+    }
+
+    pub(super) fn from_minor(minor: i64) -> Self {
+        Money(minor)
+    }
+
+    /// Mengubah jumlah satuan utuh (misalnya rupiah tanpa sen) ke satuan terkecil.
+    pub(super) fn from_major(units: i64, currency: Currency) -> Result<Self, WalletError> {
+        units
+            .checked_mul(currency.scale())
+            .map(Money)
+            .ok_or(WalletError::Overflow)
+    }
+
+    pub(super) fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(super) fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub(super) fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    pub(super) fn abs(self) -> Self {
+        Money(self.0.saturating_abs())
+    }
+
+    pub(super) fn checked_add(self, other: Money) -> Result<Self, WalletError> {
+        self.0
+            .checked_add(other.0)
+            .map(Money)
+            .ok_or(WalletError::Overflow)
+    }
+
+    pub(super) fn checked_sub(self, other: Money) -> Result<Self, WalletError> {
+        self.0
+            .checked_sub(other.0)
+            .map(Money)
+            .ok_or(WalletError::Overflow)
+    }
+
+    pub(super) fn sum(amounts: impl IntoIterator<Item = Money>) -> Result<Self, WalletError> {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, |total, amount| total.checked_add(amount))
+    }
+
+    /// Membaca jumlah seperti "25.000", "Rp12.500,50", "12,50" atau
+    /// "1,250.75". Pemisah desimal ditentukan otomatis:
+    ///
+    /// - jika titik dan koma sama-sama dipakai, yang terakhir adalah desimal;
+    /// - jika satu jenis pemisah muncul lebih dari sekali, itu pemisah ribuan;
+    /// - jika muncul sekali dan diikuti tepat tiga digit ("25.000", "1,000"),
+    ///   itu pemisah ribuan; selain itu desimal ("12,50", "7.5").
+    pub(super) fn parse(input: &str, currency: Currency) -> Result<Self, WalletError> {
+        let mut text = input.trim();
+        let negative = match text.strip_prefix('-') {
+            Some(rest) => {
+                text = rest.trim_start();
+                true
+            }
+            None => false,
+        };
+        let text = strip_currency(text, currency);
+
+        let amount = Self::parse_number(text, detect_decimal(text), currency)?;
+        Ok(if negative { -amount } else { amount })
+    }
+
+    /// Membaca angka tanpa tanda dengan pemisah desimal yang sudah diketahui;
+    /// pemisah lain dan spasi dianggap pemisah ribuan. Pecahan tidak boleh
+    /// lebih panjang dari satuan terkecil mata uangnya.
+    pub(super) fn parse_number(
+        text: &str,
+        decimal: Option<char>,
+        currency: Currency,
+    ) -> Result<Self, WalletError> {
+        let (whole, fraction) = match decimal {
+            Some(decimal) => text.rsplit_once(decimal).unwrap_or((text, "")),
+            None => (text, ""),
+        };
+
+        let digits: String = whole
+            .chars()
+            .filter(|c| !matches!(c, '.' | ',' | ' ' | '\''))
+            .collect();
+        if digits.is_empty()
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(WalletError::ParseError);
+        }
+
+        let minor_units = currency.minor_units() as usize;
+        if fraction.len() > minor_units {
+            return Err(WalletError::InvalidInput(match minor_units {
+                0 => t!("error.no_fraction", currency = currency),
+                n => t!("error.fraction_digits", currency = currency, digits = n),
+            }));
+        }
+        let fraction = format!("{:0<width$}", fraction, width = minor_units);
+
+        let whole: i64 = digits.parse().map_err(|_| WalletError::Overflow)?;
+        let fraction: i64 = if fraction.is_empty() {
+            0
+        } else {
+            fraction.parse().map_err(|_| WalletError::ParseError)?
+        };
+        Money::from_major(whole, currency)?.checked_add(Money(fraction))
+    }
+
+    /// Angka desimal bertanda untuk keluaran mesin (JSON/CSV), misalnya
+    /// "-12500.50". Jumlah digit pecahan selalu sesuai mata uangnya.
+    pub(super) fn to_decimal_string(self, currency: Currency) -> String {
+        let scale = currency.scale().unsigned_abs();
+        let value = self.0.unsigned_abs();
+        let sign = if self.is_negative() { "-" } else { "" };
+        match currency.minor_units() {
+            0 => format!("{}{}", sign, value),
+            width => format!(
+                "{}{}.{:0width$}",
+                sign,
+                value / scale,
+                value % scale,
+                width = width as usize
+            ),
+        }
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    /// Jumlah yang tersimpan selalu lolos validasi, jadi satu-satunya nilai
+    /// yang tidak bisa dinegasikan (`i64::MIN`) tidak pernah muncul di sini.
+    fn neg(self) -> Money {
+        Money(self.0.saturating_neg())
+    }
+}
+
+/// Membuang simbol atau kode mata uang di depan ("Rp", "S$", "USD") atau
+/// kode di belakang ("12,50 USD"), tanpa memedulikan huruf besar/kecil.
+fn strip_currency(text: &str, currency: Currency) -> &str {
+    let mut text = text;
+    let code = currency.code();
+    if text.len() >= code.len()
+        && text
+            .get(text.len() - code.len()..)
+            .is_some_and(|suffix| suffix.eq_ignore_ascii_case(code))
+    {
+        text = text[..text.len() - code.len()].trim_end();
+    }
+    for prefix in [currency.symbol(), code] {
+        if let Some(head) = text.get(..prefix.len())
+            && head.eq_ignore_ascii_case(prefix)
+        {
+            text = &text[prefix.len()..];
+            break;
+        }
+    }
+    text.trim()
+}
+
+fn detect_decimal(text: &str) -> Option<char> {
+    let last_dot = text.rfind('.');
+    let last_comma = text.rfind(',');
+    match (last_dot, last_comma) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(position), None) | (None, Some(position)) => {
+            let separator = text[position..].chars().next()?;
+            let repeated = text.matches(separator).count() > 1;
+            let digits_after = text.len() - position - 1;
+            if repeated || digits_after == 3 {
+                None
+            } else {
+                Some(separator)
+            }
+        }
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rupiah(input: &str) -> Result<i64, WalletError> {
+        Money::parse(input, Currency::IDR).map(Money::minor)
+    }
+
+    #[test]
+    fn single_separator_with_three_digits_is_thousands() {
+        assert_eq!(rupiah("1,250").unwrap(), 125_000);
+        assert_eq!(rupiah("25.000").unwrap(), 2_500_000);
+        assert_eq!(detect_decimal("1,250"), None);
+    }
+
+    #[test]
+    fn single_separator_with_other_digits_is_decimal() {
+        assert_eq!(rupiah("12,50").unwrap(), 1_250);
+        assert_eq!(rupiah("7.5").unwrap(), 750);
+        assert_eq!(detect_decimal("12,50"), Some(','));
+    }
+
+    #[test]
+    fn last_of_mixed_separators_is_decimal() {
+        assert_eq!(rupiah("1.250.000,00").unwrap(), 125_000_000);
+        assert_eq!(rupiah("1,250.75").unwrap(), 125_075);
+        assert_eq!(detect_decimal("1.250.000"), None);
+    }
+
+    #[test]
+    fn currency_symbol_and_sign_are_accepted() {
+        assert_eq!(rupiah("Rp12.500,50").unwrap(), 1_250_050);
+        assert_eq!(rupiah("- Rp5.000").unwrap(), -500_000);
+        assert_eq!(rupiah("IDR 5.000").unwrap(), 500_000);
+    }
+
+    #[test]
+    fn fraction_longer_than_minor_units_is_rejected() {
+        let yen = Currency(*b"JPY");
+        assert!(matches!(
+            Money::parse("1,5", yen),
+            Err(WalletError::InvalidInput(_))
+        ));
+        assert!(matches!(rupiah("12,505"), Ok(1_250_500)));
+        assert!(matches!(rupiah("abc"), Err(WalletError::ParseError)));
+    }
+
+    #[test]
+    fn decimal_string_follows_minor_units() {
+        let yen = Currency(*b"JPY");
+        assert_eq!(
+            Money(-1_250_050).to_decimal_string(Currency::IDR),
+            "-12500.50"
+        );
+        assert_eq!(Money(1_500).to_decimal_string(yen), "1500");
+    }
+}
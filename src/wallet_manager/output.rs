@@ -0,0 +1,1643 @@
+use std::{
+    io::{self, StdoutLock, Write},
+    process,
+};
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use super::{
+    CategoryTotal, TrialBalanceRow, TypeTransaction, WalletError, WalletItems, WalletList,
+    audit::AuditEntry,
+    budget::BudgetStatus,
+    category::CategoryId,
+    events::{EventKind, WalletEvent},
+    exchange::ExchangeRate,
+    goal::{Goal, GoalLink},
+    i18n::t,
+    locale::NumberFormat,
+    money::{Currency, Money},
+    period::{DateRange, Period},
+    schedule::{Schedule, ScheduleId},
+    tag::TagTotal,
+};
+
+/// Format keluaran untuk mode perintah.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub(super) enum OutputFormat {
+    #[default]
+    #[value(help = t!("help.value.output_format.text"))]
+    Text,
+    #[value(help = t!("help.value.output_format.json"))]
+    Json,
+    #[value(help = t!("help.value.output_format.csv"))]
+    Csv,
+}
+
+/// Setiap jumlah untuk mesin ditulis dua kali: `*_minor` berupa bilangan bulat
+/// dalam satuan terkecil mata uangnya (misalnya sen), dan `*_decimal` berupa
+/// angka desimal dengan titik, misalnya "12500.50". Yang desimal ditulis
+/// sebagai teks agar pecahan sen tidak berubah karena pembulatan.
+fn decimal(amount: Money, currency: Currency) -> String {
+    amount.to_decimal_string(currency)
+}
+
+fn percent(amount: Money, total: Money) -> f64 {
+    amount.minor() as f64 / total.minor() as f64 * 100.0
+}
+
+fn type_code(transaction_type: &TypeTransaction) -> &'static str {
+    match transaction_type {
+        TypeTransaction::Income => "income",
+        TypeTransaction::Expense => "expense",
+        TypeTransaction::Transfer => "transfer",
+        TypeTransaction::Journal => "journal",
+    }
+}
+
+fn balance_status(balance: Money) -> &'static str {
+    if balance.is_positive() {
+        "surplus"
+    } else if balance.is_zero() {
+        "balanced"
+    } else {
+        "deficit"
+    }
+}
+
+/// Baris yang bisa ditulis sebagai CSV.
+pub(super) trait CsvRecord {
+    const HEADER: &'static [&'static str];
+    fn fields(&self) -> Vec<String>;
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Keluaran mode perintah sering dipipe ke program lain, misalnya
+/// `wallet list | head`. Jika pembacanya sudah menutup pipe, sisa keluaran
+/// tidak dibutuhkan lagi dan proses selesai dengan tenang.
+fn exit_if_broken_pipe(error: &io::Error) {
+    if error.kind() == io::ErrorKind::BrokenPipe {
+        process::exit(0);
+    }
+}
+
+/// Menulis ke stdout yang dikunci selama `write` berjalan, lalu mem-flush.
+/// Semua keluaran aplikasi lewat sini (langsung, atau lewat [`out!`] dan
+/// [`outln!`]) agar pipe yang tertutup tidak membuat proses panik.
+pub(super) fn write_stdout(write: impl FnOnce(&mut StdoutLock) -> io::Result<()>) {
+    let mut out = io::stdout().lock();
+    if let Err(e) = write(&mut out).and_then(|()| out.flush()) {
+        exit_if_broken_pipe(&e);
+        panic!("failed printing to stdout: {}", e);
+    }
+}
+
+/// Pengganti `print!` yang menulis lewat [`write_stdout`]. Teksnya disusun
+/// lebih dulu sehingga `?` di dalam argumen tetap berlaku untuk pemanggil.
+macro_rules! out {
+    ($($arg:tt)*) => {{
+        let text = format!($($arg)*);
+        $crate::wallet_manager::output::write_stdout(|out| {
+            std::io::Write::write_all(out, text.as_bytes())
+        })
+    }};
+}
+
+/// Pengganti `println!` yang menulis lewat [`write_stdout`].
+macro_rules! outln {
+    () => {
+        $crate::wallet_manager::output::out!("\n")
+    };
+    ($($arg:tt)*) => {{
+        let mut text = format!($($arg)*);
+        text.push('\n');
+        $crate::wallet_manager::output::write_stdout(|out| {
+            std::io::Write::write_all(out, text.as_bytes())
+        })
+    }};
+}
+
+pub(super) use {out, outln};
+
+pub(super) fn write_csv<T: CsvRecord>(records: &[T]) {
+    let row = |fields: Vec<String>| {
+        fields
+            .iter()
+            .map(|f| csv_escape(f))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    write_stdout(|out| {
+        writeln!(
+            out,
+            "{}",
+            row(T::HEADER.iter().map(|h| h.to_string()).collect())
+        )?;
+        for record in records {
+            writeln!(out, "{}", row(record.fields()))?;
+        }
+        Ok(())
+    });
+}
+
+pub(super) fn write_json<T: Serialize>(value: &T) {
+    write_stdout(|out| {
+        serde_json::to_writer_pretty(&mut *out, value).map_err(io::Error::from)?;
+        writeln!(out)
+    });
+}
+
+#[derive(Serialize)]
+pub(super) struct TransactionRecord {
+    id: String,
+    /// Tanggal pembukuan dalam format ISO 8601 (YYYY-MM-DD).
+    date: String,
+    #[serde(rename = "type")]
+    transaction_type: &'static str,
+    /// Jumlah mentah dalam satuan terkecil `currency`, selalu positif; arah
+    /// ditentukan `type`.
+    amount_minor: i64,
+    amount_decimal: String,
+    /// Kode ISO 4217 mata uang `amount_minor`.
+    currency: Currency,
+    amount_formatted: String,
+    account: String,
+    /// Akun tujuan; hanya terisi untuk transfer.
+    to_account: Option<String>,
+    /// Jumlah yang diterima akun tujuan; hanya untuk transfer antar mata uang.
+    to_amount_minor: Option<i64>,
+    to_amount_decimal: Option<String>,
+    to_currency: Option<Currency>,
+    category: Option<String>,
+    tags: Vec<String>,
+    description: String,
+    /// Posting buku besar transaksi; tidak ikut keluaran CSV.
+    postings: Vec<PostingRecord>,
+    created_at: String,
+    modified_at: String,
+    /// Saldo setelah transaksi ini, dalam `running_balance_currency`; hanya
+    /// pada `list` dan `search`, dan kosong jika kurs belum ada.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    running_balance_minor: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    running_balance_decimal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    running_balance_currency: Option<Currency>,
+}
+
+#[derive(Serialize)]
+struct PostingRecord {
+    account: String,
+    currency: Currency,
+    debit_minor: i64,
+    debit_decimal: String,
+    credit_minor: i64,
+    credit_decimal: String,
+}
+
+impl TransactionRecord {
+    pub(super) fn new(wallet: &WalletList, item: &WalletItems) -> Self {
+        let accounts = wallet.accounts();
+        Self {
+            id: item.id.to_string(),
+            date: item.date.to_string(),
+            transaction_type: type_code(&item.transaction_type),
+            amount_minor: item.amount.minor(),
+            amount_decimal: decimal(item.amount, item.currency),
+            currency: item.currency,
+            amount_formatted: item.amount_label(None, &wallet.number_format().exact()),
+            account: accounts.name(item.account).to_string(),
+            to_account: item.to_account.map(|id| accounts.name(id).to_string()),
+            to_amount_minor: item.to_amount.map(Money::minor),
+            to_amount_decimal: item
+                .to_amount
+                .zip(item.to_currency)
+                .map(|(amount, currency)| decimal(amount, currency)),
+            to_currency: item.to_currency,
+            category: item.category.and_then(|id| wallet.categories().path(id)),
+            tags: item.tags.clone(),
+            description: item.description.clone(),
+            postings: item
+                .postings()
+                .iter()
+                .map(|p| {
+                    let (debit, credit) = (p.amount.max(Money::ZERO), (-p.amount).max(Money::ZERO));
+                    PostingRecord {
+                        account: wallet.ledger_label(p.account),
+                        currency: p.currency,
+                        debit_minor: debit.minor(),
+                        debit_decimal: decimal(debit, p.currency),
+                        credit_minor: credit.minor(),
+                        credit_decimal: decimal(credit, p.currency),
+                    }
+                })
+                .collect(),
+            created_at: item.created_at.to_rfc3339(),
+            modified_at: item.modified_at.to_rfc3339(),
+            running_balance_minor: None,
+            running_balance_decimal: None,
+            running_balance_currency: None,
+        }
+    }
+
+    pub(super) fn with_running_balance(self, balance: Money, currency: Currency) -> Self {
+        Self {
+            running_balance_minor: Some(balance.minor()),
+            running_balance_decimal: Some(decimal(balance, currency)),
+            running_balance_currency: Some(currency),
+            ..self
+        }
+    }
+}
+
+impl CsvRecord for TransactionRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "date",
+        "type",
+        "amount_minor",
+        "amount_decimal",
+        "currency",
+        "amount_formatted",
+        "account",
+        "to_account",
+        "to_amount_minor",
+        "to_amount_decimal",
+        "to_currency",
+        "category",
+        "tags",
+        "description",
+        "created_at",
+        "modified_at",
+        "running_balance_minor",
+        "running_balance_decimal",
+        "running_balance_currency",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.date.clone(),
+            self.transaction_type.to_string(),
+            self.amount_minor.to_string(),
+            self.amount_decimal.clone(),
+            self.currency.to_string(),
+            self.amount_formatted.clone(),
+            self.account.clone(),
+            self.to_account.clone().unwrap_or_default(),
+            self.to_amount_minor
+                .map(|minor| minor.to_string())
+                .unwrap_or_default(),
+            self.to_amount_decimal.clone().unwrap_or_default(),
+            self.to_currency.map(|c| c.to_string()).unwrap_or_default(),
+            self.category.clone().unwrap_or_default(),
+            self.tags.join(" "),
+            self.description.clone(),
+            self.created_at.clone(),
+            self.modified_at.clone(),
+            self.running_balance_minor
+                .map(|minor| minor.to_string())
+                .unwrap_or_default(),
+            self.running_balance_decimal.clone().unwrap_or_default(),
+            self.running_balance_currency
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct BalanceRecord {
+    /// Tanggal `--as-of` dalam format ISO 8601 (YYYY-MM-DD); kosong untuk
+    /// saldo saat ini.
+    as_of: Option<String>,
+    balance_minor: i64,
+    balance_decimal: String,
+    currency: Currency,
+    balance_formatted: String,
+    status: &'static str,
+}
+
+impl BalanceRecord {
+    pub(super) fn new(
+        balance: Money,
+        currency: Currency,
+        as_of: Option<NaiveDate>,
+        format: &NumberFormat,
+    ) -> Self {
+        Self {
+            as_of: as_of.map(|date| date.to_string()),
+            balance_minor: balance.minor(),
+            balance_decimal: decimal(balance, currency),
+            currency,
+            balance_formatted: format.signed(balance, currency),
+            status: balance_status(balance),
+        }
+    }
+}
+
+impl CsvRecord for BalanceRecord {
+    const HEADER: &'static [&'static str] = &[
+        "as_of",
+        "balance_minor",
+        "balance_decimal",
+        "currency",
+        "balance_formatted",
+        "status",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.as_of.clone().unwrap_or_default(),
+            self.balance_minor.to_string(),
+            self.balance_decimal.clone(),
+            self.currency.to_string(),
+            self.balance_formatted.clone(),
+            self.status.to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct AccountRecord {
+    account: String,
+    balance_minor: i64,
+    balance_decimal: String,
+    currency: Currency,
+    balance_formatted: String,
+    /// Kebijakan saldo minus; kosong pada baris total.
+    overdraft: Option<String>,
+}
+
+impl AccountRecord {
+    /// Saldo per akun dalam mata uangnya, diakhiri satu baris saldo bersih
+    /// semua akun dalam mata uang laporan dengan `account` bernilai "total".
+    pub(super) fn all(wallet: &WalletList) -> Result<Vec<Self>, WalletError> {
+        let format = wallet.number_format();
+        let record =
+            |account: &str, balance: Money, currency: Currency, overdraft: Option<String>| Self {
+                account: account.to_string(),
+                balance_minor: balance.minor(),
+                balance_decimal: decimal(balance, currency),
+                currency,
+                balance_formatted: format.signed(balance, currency),
+                overdraft,
+            };
+
+        let mut records = Vec::new();
+        for account in wallet.accounts().iter() {
+            records.push(record(
+                &account.name,
+                wallet.calculate_balance(Some(account.id))?,
+                account.currency,
+                Some(account.overdraft_label(&format)),
+            ));
+        }
+        records.push(record(
+            "total",
+            wallet.calculate_balance(None)?,
+            wallet.reporting_currency(),
+            None,
+        ));
+        Ok(records)
+    }
+}
+
+impl CsvRecord for AccountRecord {
+    const HEADER: &'static [&'static str] = &[
+        "account",
+        "balance_minor",
+        "balance_decimal",
+        "currency",
+        "balance_formatted",
+        "overdraft",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.account.clone(),
+            self.balance_minor.to_string(),
+            self.balance_decimal.clone(),
+            self.currency.to_string(),
+            self.balance_formatted.clone(),
+            self.overdraft.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct TrialBalanceRecord {
+    account: String,
+    class: String,
+    currency: Currency,
+    debit_minor: i64,
+    debit_decimal: String,
+    credit_minor: i64,
+    credit_decimal: String,
+}
+
+impl TrialBalanceRecord {
+    pub(super) fn new(wallet: &WalletList, row: &TrialBalanceRow) -> Self {
+        Self {
+            account: wallet.ledger_label(row.account),
+            class: row.class.to_string(),
+            currency: row.currency,
+            debit_minor: row.debit.minor(),
+            debit_decimal: decimal(row.debit, row.currency),
+            credit_minor: row.credit.minor(),
+            credit_decimal: decimal(row.credit, row.currency),
+        }
+    }
+}
+
+impl CsvRecord for TrialBalanceRecord {
+    const HEADER: &'static [&'static str] = &[
+        "account",
+        "class",
+        "currency",
+        "debit_minor",
+        "debit_decimal",
+        "credit_minor",
+        "credit_decimal",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.account.clone(),
+            self.class.clone(),
+            self.currency.to_string(),
+            self.debit_minor.to_string(),
+            self.debit_decimal.clone(),
+            self.credit_minor.to_string(),
+            self.credit_decimal.clone(),
+        ]
+    }
+}
+
+/// Total pemasukan dan pengeluaran dalam satu mata uang asli, tanpa konversi.
+#[derive(Serialize)]
+struct CurrencyRecord {
+    currency: Currency,
+    income_minor: i64,
+    income_decimal: String,
+    income_formatted: String,
+    expense_minor: i64,
+    expense_decimal: String,
+    expense_formatted: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct CategoryRecord {
+    #[serde(rename = "type")]
+    transaction_type: &'static str,
+    /// Nama lengkap kategori; `None` untuk transaksi tanpa kategori.
+    category: Option<String>,
+    amount_minor: i64,
+    amount_decimal: String,
+    amount_formatted: String,
+    /// Persentase terhadap total jenis transaksi yang sama.
+    percent: f64,
+    children: Vec<CategoryRecord>,
+}
+
+impl CategoryRecord {
+    fn new(
+        transaction_type: &TypeTransaction,
+        category: Option<String>,
+        amount: Money,
+        total: Money,
+        currency: Currency,
+        format: &NumberFormat,
+        children: Vec<CategoryRecord>,
+    ) -> Self {
+        Self {
+            transaction_type: type_code(transaction_type),
+            category,
+            amount_minor: amount.minor(),
+            amount_decimal: decimal(amount, currency),
+            amount_formatted: format.money(amount, currency),
+            percent: if total.is_zero() {
+                0.0
+            } else {
+                percent(amount, total)
+            },
+            children,
+        }
+    }
+
+    pub(super) fn from_breakdown(
+        wallet: &WalletList,
+        transaction_type: &TypeTransaction,
+        breakdown: &[CategoryTotal],
+        total: Money,
+    ) -> Vec<Self> {
+        let currency = wallet.reporting_currency();
+        let format = wallet.number_format();
+        breakdown
+            .iter()
+            .map(|entry| {
+                let children = entry
+                    .children
+                    .iter()
+                    .map(|(id, amount)| {
+                        let path = wallet.categories().path(*id);
+                        Self::new(
+                            transaction_type,
+                            path,
+                            *amount,
+                            total,
+                            currency,
+                            &format,
+                            Vec::new(),
+                        )
+                    })
+                    .collect();
+                let path = entry.category.and_then(|id| wallet.categories().path(id));
+                Self::new(
+                    transaction_type,
+                    path,
+                    entry.total,
+                    total,
+                    currency,
+                    &format,
+                    children,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Ringkasan dalam mata uang laporan; setiap transaksi dikonversi dengan
+/// kurs pada tanggalnya.
+#[derive(Serialize)]
+pub(super) struct SummaryRecord {
+    currency: Currency,
+    total_income_minor: i64,
+    total_income_decimal: String,
+    total_income_formatted: String,
+    total_expense_minor: i64,
+    total_expense_decimal: String,
+    total_expense_formatted: String,
+    balance_minor: i64,
+    balance_decimal: String,
+    balance_formatted: String,
+    status: &'static str,
+    /// Persentase saldo terhadap pemasukan; `None` jika belum ada pemasukan.
+    savings_rate: Option<f64>,
+    categories: Vec<CategoryRecord>,
+    /// Total asli per mata uang sebelum dikonversi.
+    by_currency: Vec<CurrencyRecord>,
+}
+
+impl SummaryRecord {
+    pub(super) fn new(wallet: &WalletList) -> Result<Self, WalletError> {
+        let (total_income, total_expense, balance) = wallet.get_summary()?;
+
+        let mut categories = CategoryRecord::from_breakdown(
+            wallet,
+            &TypeTransaction::Expense,
+            &wallet.category_breakdown(&TypeTransaction::Expense)?,
+            total_expense,
+        );
+        categories.extend(CategoryRecord::from_breakdown(
+            wallet,
+            &TypeTransaction::Income,
+            &wallet.category_breakdown(&TypeTransaction::Income)?,
+            total_income,
+        ));
+
+        let format = wallet.number_format();
+        let by_currency = wallet
+            .summary_by_currency()?
+            .into_iter()
+            .map(|total| CurrencyRecord {
+                currency: total.currency,
+                income_minor: total.income.minor(),
+                income_decimal: decimal(total.income, total.currency),
+                income_formatted: format.signed(total.income, total.currency),
+                expense_minor: total.expense.minor(),
+                expense_decimal: decimal(total.expense, total.currency),
+                expense_formatted: format.money(-total.expense, total.currency),
+            })
+            .collect();
+
+        let currency = wallet.reporting_currency();
+        Ok(Self {
+            currency,
+            total_income_minor: total_income.minor(),
+            total_income_decimal: decimal(total_income, currency),
+            total_income_formatted: format.signed(total_income, currency),
+            total_expense_minor: total_expense.minor(),
+            total_expense_decimal: decimal(total_expense, currency),
+            total_expense_formatted: format.money(-total_expense, currency),
+            balance_minor: balance.minor(),
+            balance_decimal: decimal(balance, currency),
+            balance_formatted: format.signed(balance, currency),
+            status: balance_status(balance),
+            savings_rate: total_income
+                .is_positive()
+                .then(|| percent(balance, total_income)),
+            categories,
+            by_currency,
+        })
+    }
+
+    /// Ringkasan dalam bentuk baris CSV: satu baris per metrik, lalu satu
+    /// baris per kategori dan sub-kategori, lalu total asli per mata uang
+    /// dengan section "currency:KODE".
+    pub(super) fn csv_rows(&self) -> Vec<SummaryCsvRow> {
+        let metric = |name: &str, minor: i64, decimal: &str, formatted: &str| SummaryCsvRow {
+            section: "total".to_string(),
+            name: name.to_string(),
+            amount_minor: Some(minor),
+            amount_decimal: decimal.to_string(),
+            amount_formatted: formatted.to_string(),
+            percent: String::new(),
+        };
+
+        let mut rows = vec![
+            metric(
+                "income",
+                self.total_income_minor,
+                &self.total_income_decimal,
+                &self.total_income_formatted,
+            ),
+            metric(
+                "expense",
+                self.total_expense_minor,
+                &self.total_expense_decimal,
+                &self.total_expense_formatted,
+            ),
+            metric(
+                "balance",
+                self.balance_minor,
+                &self.balance_decimal,
+                &self.balance_formatted,
+            ),
+        ];
+        if let Some(rate) = self.savings_rate {
+            rows.push(SummaryCsvRow {
+                section: "total".to_string(),
+                name: "savings_rate".to_string(),
+                amount_minor: None,
+                amount_decimal: String::new(),
+                amount_formatted: String::new(),
+                percent: format!("{:.1}", rate),
+            });
+        }
+
+        fn push_category(rows: &mut Vec<SummaryCsvRow>, record: &CategoryRecord) {
+            rows.push(SummaryCsvRow {
+                section: format!("category_{}", record.transaction_type),
+                name: record.category.clone().unwrap_or_default(),
+                amount_minor: Some(record.amount_minor),
+                amount_decimal: record.amount_decimal.clone(),
+                amount_formatted: record.amount_formatted.clone(),
+                percent: format!("{:.1}", record.percent),
+            });
+            for child in &record.children {
+                push_category(rows, child);
+            }
+        }
+        for record in &self.categories {
+            push_category(&mut rows, record);
+        }
+
+        for record in &self.by_currency {
+            let section = format!("currency:{}", record.currency);
+            for (name, minor, decimal, formatted) in [
+                (
+                    "income",
+                    record.income_minor,
+                    &record.income_decimal,
+                    &record.income_formatted,
+                ),
+                (
+                    "expense",
+                    record.expense_minor,
+                    &record.expense_decimal,
+                    &record.expense_formatted,
+                ),
+            ] {
+                rows.push(SummaryCsvRow {
+                    section: section.clone(),
+                    name: name.to_string(),
+                    amount_minor: Some(minor),
+                    amount_decimal: decimal.clone(),
+                    amount_formatted: formatted.clone(),
+                    percent: String::new(),
+                });
+            }
+        }
+
+        rows
+    }
+}
+
+pub(super) struct SummaryCsvRow {
+    section: String,
+    name: String,
+    /// Kosong pada baris `savings_rate`.
+    amount_minor: Option<i64>,
+    amount_decimal: String,
+    amount_formatted: String,
+    percent: String,
+}
+
+impl CsvRecord for SummaryCsvRow {
+    const HEADER: &'static [&'static str] = &[
+        "section",
+        "name",
+        "amount_minor",
+        "amount_decimal",
+        "amount_formatted",
+        "percent",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.section.clone(),
+            self.name.clone(),
+            self.amount_minor
+                .map(|minor| minor.to_string())
+                .unwrap_or_default(),
+            self.amount_decimal.clone(),
+            self.amount_formatted.clone(),
+            self.percent.clone(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct PeriodRecord {
+    /// Nama periode, misalnya "2026-10", "2026-Q4" atau "2026".
+    period: String,
+    /// Tanggal pertama dan terakhir periode dalam format ISO 8601.
+    from: String,
+    to: String,
+    /// Mata uang laporan; semua jumlah sudah dikonversi.
+    currency: Currency,
+    income_minor: i64,
+    income_decimal: String,
+    income_formatted: String,
+    expense_minor: i64,
+    expense_decimal: String,
+    expense_formatted: String,
+    net_minor: i64,
+    net_decimal: String,
+    net_formatted: String,
+    /// Selisih saldo bersih dengan periode sebelumnya; kosong untuk
+    /// periode pertama.
+    net_change_minor: Option<i64>,
+    net_change_decimal: Option<String>,
+    net_change_formatted: Option<String>,
+    /// Persen pemasukan yang tidak dibelanjakan; kosong tanpa pemasukan.
+    savings_rate: Option<f64>,
+}
+
+impl PeriodRecord {
+    pub(super) fn all(
+        wallet: &WalletList,
+        period: Period,
+        range: DateRange,
+    ) -> Result<Vec<Self>, WalletError> {
+        let currency = wallet.reporting_currency();
+        let format = wallet.number_format();
+        let mut previous: Option<Money> = None;
+        let mut records = Vec::new();
+        for total in wallet.period_report(period, range)? {
+            let net = total.net()?;
+            let change = previous
+                .map(|previous| net.checked_sub(previous))
+                .transpose()?;
+            records.push(Self {
+                period: period.label(total.start),
+                from: total.start.to_string(),
+                to: period.end_of(total.start).to_string(),
+                currency,
+                income_minor: total.income.minor(),
+                income_decimal: decimal(total.income, currency),
+                income_formatted: format.signed(total.income, currency),
+                expense_minor: total.expense.minor(),
+                expense_decimal: decimal(total.expense, currency),
+                expense_formatted: format.money(-total.expense, currency),
+                net_minor: net.minor(),
+                net_decimal: decimal(net, currency),
+                net_formatted: format.signed(net, currency),
+                net_change_minor: change.map(Money::minor),
+                net_change_decimal: change.map(|change| decimal(change, currency)),
+                net_change_formatted: change.map(|change| format.signed(change, currency)),
+                savings_rate: total.savings_rate()?,
+            });
+            previous = Some(net);
+        }
+        Ok(records)
+    }
+}
+
+impl CsvRecord for PeriodRecord {
+    const HEADER: &'static [&'static str] = &[
+        "period",
+        "from",
+        "to",
+        "currency",
+        "income_minor",
+        "income_decimal",
+        "income_formatted",
+        "expense_minor",
+        "expense_decimal",
+        "expense_formatted",
+        "net_minor",
+        "net_decimal",
+        "net_formatted",
+        "net_change_minor",
+        "net_change_decimal",
+        "net_change_formatted",
+        "savings_rate",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.period.clone(),
+            self.from.clone(),
+            self.to.clone(),
+            self.currency.to_string(),
+            self.income_minor.to_string(),
+            self.income_decimal.clone(),
+            self.income_formatted.clone(),
+            self.expense_minor.to_string(),
+            self.expense_decimal.clone(),
+            self.expense_formatted.clone(),
+            self.net_minor.to_string(),
+            self.net_decimal.clone(),
+            self.net_formatted.clone(),
+            self.net_change_minor
+                .map(|minor| minor.to_string())
+                .unwrap_or_default(),
+            self.net_change_decimal.clone().unwrap_or_default(),
+            self.net_change_formatted.clone().unwrap_or_default(),
+            self.savings_rate
+                .map(|rate| format!("{:.1}", rate))
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct BudgetRecord {
+    /// Bulan anggaran, misalnya "2026-10".
+    month: String,
+    category_id: CategoryId,
+    category: String,
+    currency: Currency,
+    limit_minor: i64,
+    limit_decimal: String,
+    limit_formatted: String,
+    spent_minor: i64,
+    spent_decimal: String,
+    spent_formatted: String,
+    /// Negatif jika anggaran terlampaui.
+    remaining_minor: i64,
+    remaining_decimal: String,
+    remaining_formatted: String,
+    /// Persen anggaran yang terpakai; bisa lebih dari 100.
+    percent: f64,
+    /// "ok", "warning" (batas peringatan tercapai) atau "over".
+    status: &'static str,
+    /// Batas peringatan dalam persen.
+    threshold: u8,
+}
+
+impl BudgetRecord {
+    pub(super) fn all(wallet: &WalletList, month: NaiveDate) -> Result<Vec<Self>, WalletError> {
+        let format = wallet.number_format();
+        let threshold = wallet.budgets().threshold();
+        wallet
+            .budget_status(month)?
+            .iter()
+            .map(|status| Self::new(wallet, status, month, threshold, &format))
+            .collect()
+    }
+
+    fn new(
+        wallet: &WalletList,
+        status: &BudgetStatus,
+        month: NaiveDate,
+        threshold: u8,
+        format: &NumberFormat,
+    ) -> Result<Self, WalletError> {
+        let currency = status.currency;
+        let remaining = status.remaining()?;
+        Ok(Self {
+            month: Period::Month.label(month),
+            category_id: status.category,
+            category: wallet.category_label(Some(status.category)),
+            currency,
+            limit_minor: status.limit.minor(),
+            limit_decimal: decimal(status.limit, currency),
+            limit_formatted: format.money(status.limit, currency),
+            spent_minor: status.spent.minor(),
+            spent_decimal: decimal(status.spent, currency),
+            spent_formatted: format.money(status.spent, currency),
+            remaining_minor: remaining.minor(),
+            remaining_decimal: decimal(remaining, currency),
+            remaining_formatted: format.money(remaining, currency),
+            percent: status.percent(),
+            status: status.level(threshold).code(),
+            threshold,
+        })
+    }
+}
+
+impl CsvRecord for BudgetRecord {
+    const HEADER: &'static [&'static str] = &[
+        "month",
+        "category_id",
+        "category",
+        "currency",
+        "limit_minor",
+        "limit_decimal",
+        "limit_formatted",
+        "spent_minor",
+        "spent_decimal",
+        "spent_formatted",
+        "remaining_minor",
+        "remaining_decimal",
+        "remaining_formatted",
+        "percent",
+        "status",
+        "threshold",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.month.clone(),
+            self.category_id.to_string(),
+            self.category.clone(),
+            self.currency.to_string(),
+            self.limit_minor.to_string(),
+            self.limit_decimal.clone(),
+            self.limit_formatted.clone(),
+            self.spent_minor.to_string(),
+            self.spent_decimal.clone(),
+            self.spent_formatted.clone(),
+            self.remaining_minor.to_string(),
+            self.remaining_decimal.clone(),
+            self.remaining_formatted.clone(),
+            format!("{:.1}", self.percent),
+            self.status.to_string(),
+            self.threshold.to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct ScheduleRecord {
+    id: String,
+    #[serde(rename = "type")]
+    transaction_type: &'static str,
+    amount_minor: i64,
+    amount_decimal: String,
+    currency: Currency,
+    amount_formatted: String,
+    account: String,
+    category: Option<String>,
+    tags: Vec<String>,
+    description: String,
+    /// "daily", "weekly", "monthly" atau "yearly".
+    frequency: &'static str,
+    interval: u32,
+    /// Tanggal kejadian pertama dan terakhir dalam format ISO 8601.
+    start: String,
+    end: Option<String>,
+    count: Option<u32>,
+    last_business_day: bool,
+    /// Jumlah kejadian yang sudah dicatat atau dilewati.
+    done: u32,
+    /// Kejadian berikutnya; kosong jika jadwal sudah selesai.
+    next_date: Option<String>,
+}
+
+impl ScheduleRecord {
+    pub(super) fn all(wallet: &WalletList) -> Vec<Self> {
+        wallet
+            .schedules()
+            .iter()
+            .map(|schedule| Self::new(wallet, schedule))
+            .collect()
+    }
+
+    fn new(wallet: &WalletList, schedule: &Schedule) -> Self {
+        let item = &schedule.template;
+        let rule = &schedule.rule;
+        Self {
+            id: schedule.id.to_string(),
+            transaction_type: type_code(&item.transaction_type),
+            amount_minor: item.amount.minor(),
+            amount_decimal: decimal(item.amount, item.currency),
+            currency: item.currency,
+            amount_formatted: item.amount_label(None, &wallet.number_format().exact()),
+            account: wallet.accounts().name(item.account).to_string(),
+            category: item.category.and_then(|id| wallet.categories().path(id)),
+            tags: item.tags.clone(),
+            description: item.description.clone(),
+            frequency: rule.frequency.code(),
+            interval: rule.interval,
+            start: rule.start.to_string(),
+            end: rule.end.map(|date| date.to_string()),
+            count: rule.count,
+            last_business_day: rule.last_business_day,
+            done: schedule.done,
+            next_date: schedule.next_date().map(|date| date.to_string()),
+        }
+    }
+}
+
+impl CsvRecord for ScheduleRecord {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "type",
+        "amount_minor",
+        "amount_decimal",
+        "currency",
+        "amount_formatted",
+        "account",
+        "category",
+        "tags",
+        "description",
+        "frequency",
+        "interval",
+        "start",
+        "end",
+        "count",
+        "last_business_day",
+        "done",
+        "next_date",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.transaction_type.to_string(),
+            self.amount_minor.to_string(),
+            self.amount_decimal.clone(),
+            self.currency.to_string(),
+            self.amount_formatted.clone(),
+            self.account.clone(),
+            self.category.clone().unwrap_or_default(),
+            self.tags.join(" "),
+            self.description.clone(),
+            self.frequency.to_string(),
+            self.interval.to_string(),
+            self.start.clone(),
+            self.end.clone().unwrap_or_default(),
+            self.count
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            self.last_business_day.to_string(),
+            self.done.to_string(),
+            self.next_date.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// Satu kejadian transaksi berulang yang belum dicatat.
+#[derive(Serialize)]
+pub(super) struct OccurrenceRecord {
+    date: String,
+    schedule: String,
+    #[serde(rename = "type")]
+    transaction_type: &'static str,
+    amount_minor: i64,
+    amount_decimal: String,
+    currency: Currency,
+    amount_formatted: String,
+    account: String,
+    description: String,
+    /// Tanggalnya sudah lewat atau hari ini.
+    due: bool,
+}
+
+impl OccurrenceRecord {
+    pub(super) fn all(wallet: &WalletList, occurrences: &[(ScheduleId, NaiveDate)]) -> Vec<Self> {
+        let today = super::today();
+        occurrences
+            .iter()
+            .filter_map(|(id, date)| {
+                let item = &wallet.schedules().get(*id).ok()?.template;
+                Some(Self {
+                    date: date.to_string(),
+                    schedule: id.to_string(),
+                    transaction_type: type_code(&item.transaction_type),
+                    amount_minor: item.amount.minor(),
+                    amount_decimal: decimal(item.amount, item.currency),
+                    currency: item.currency,
+                    amount_formatted: item.amount_label(None, &wallet.number_format().exact()),
+                    account: wallet.accounts().name(item.account).to_string(),
+                    description: item.description.clone(),
+                    due: *date <= today,
+                })
+            })
+            .collect()
+    }
+}
+
+impl CsvRecord for OccurrenceRecord {
+    const HEADER: &'static [&'static str] = &[
+        "date",
+        "schedule",
+        "type",
+        "amount_minor",
+        "amount_decimal",
+        "currency",
+        "amount_formatted",
+        "account",
+        "description",
+        "due",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.date.clone(),
+            self.schedule.clone(),
+            self.transaction_type.to_string(),
+            self.amount_minor.to_string(),
+            self.amount_decimal.clone(),
+            self.currency.to_string(),
+            self.amount_formatted.clone(),
+            self.account.clone(),
+            self.description.clone(),
+            self.due.to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct GoalRecord {
+    name: String,
+    /// Akun tabungan, atau kosong jika tujuan terhubung ke tag.
+    account: Option<String>,
+    tag: Option<String>,
+    currency: Currency,
+    target_minor: i64,
+    target_decimal: String,
+    target_formatted: String,
+    saved_minor: i64,
+    saved_decimal: String,
+    saved_formatted: String,
+    remaining_minor: i64,
+    remaining_decimal: String,
+    remaining_formatted: String,
+    percent: f64,
+    /// Tanggal dalam format ISO 8601.
+    deadline: Option<String>,
+    /// Setoran per bulan agar target tercapai pada tenggat.
+    required_monthly_minor: Option<i64>,
+    required_monthly_decimal: Option<String>,
+    required_monthly_formatted: Option<String>,
+    /// Rata-rata setoran per bulan selama beberapa bulan terakhir.
+    pace_minor: i64,
+    pace_decimal: String,
+    pace_formatted: String,
+    projected: Option<String>,
+    /// "achieved", "on_track", "behind", atau kosong untuk tujuan tanpa
+    /// tenggat yang belum tercapai.
+    status: Option<&'static str>,
+    /// Tidak ikut keluaran CSV.
+    contributions: Vec<ContributionRecord>,
+}
+
+#[derive(Serialize)]
+struct ContributionRecord {
+    date: String,
+    transaction: String,
+    description: String,
+    /// Dalam mata uang tujuan; negatif untuk penarikan.
+    amount_minor: i64,
+    amount_decimal: String,
+}
+
+impl GoalRecord {
+    pub(super) fn all(wallet: &WalletList) -> Result<Vec<Self>, WalletError> {
+        wallet
+            .goals()
+            .iter()
+            .map(|goal| Self::new(wallet, goal))
+            .collect()
+    }
+
+    pub(super) fn new(wallet: &WalletList, goal: &Goal) -> Result<Self, WalletError> {
+        let progress = wallet.goal_progress(goal)?;
+        let format = wallet.number_format().exact();
+        let currency = goal.currency;
+        let remaining = progress.remaining()?;
+        let required = progress.required_monthly()?;
+        let (account, tag) = match &goal.link {
+            GoalLink::Account(id) => (Some(wallet.accounts().name(*id).to_string()), None),
+            GoalLink::Tag(tag) => (None, Some(tag.clone())),
+        };
+        Ok(Self {
+            name: goal.name.clone(),
+            account,
+            tag,
+            currency,
+            target_minor: goal.target.minor(),
+            target_decimal: decimal(goal.target, currency),
+            target_formatted: format.money(goal.target, currency),
+            saved_minor: progress.saved.minor(),
+            saved_decimal: decimal(progress.saved, currency),
+            saved_formatted: format.money(progress.saved, currency),
+            remaining_minor: remaining.minor(),
+            remaining_decimal: decimal(remaining, currency),
+            remaining_formatted: format.money(remaining, currency),
+            percent: progress.percent(),
+            deadline: goal.deadline.map(|date| date.to_string()),
+            required_monthly_minor: required.map(Money::minor),
+            required_monthly_decimal: required.map(|amount| decimal(amount, currency)),
+            required_monthly_formatted: required.map(|amount| format.money(amount, currency)),
+            pace_minor: (progress.pace()).minor(),
+            pace_decimal: decimal(progress.pace(), currency),
+            pace_formatted: format.money(progress.pace(), currency),
+            projected: progress.projected()?.map(|date| date.to_string()),
+            status: progress.status()?.map(|status| status.code()),
+            contributions: progress
+                .contributions
+                .iter()
+                .map(|contribution| ContributionRecord {
+                    date: contribution.date.to_string(),
+                    transaction: contribution.transaction.to_string(),
+                    description: contribution.description.clone(),
+                    amount_minor: contribution.amount.minor(),
+                    amount_decimal: decimal(contribution.amount, currency),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl CsvRecord for GoalRecord {
+    const HEADER: &'static [&'static str] = &[
+        "name",
+        "account",
+        "tag",
+        "currency",
+        "target_minor",
+        "target_decimal",
+        "target_formatted",
+        "saved_minor",
+        "saved_decimal",
+        "saved_formatted",
+        "remaining_minor",
+        "remaining_decimal",
+        "remaining_formatted",
+        "percent",
+        "deadline",
+        "required_monthly_minor",
+        "required_monthly_decimal",
+        "required_monthly_formatted",
+        "pace_minor",
+        "pace_decimal",
+        "pace_formatted",
+        "projected",
+        "status",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.account.clone().unwrap_or_default(),
+            self.tag.clone().unwrap_or_default(),
+            self.currency.to_string(),
+            self.target_minor.to_string(),
+            self.target_decimal.clone(),
+            self.target_formatted.clone(),
+            self.saved_minor.to_string(),
+            self.saved_decimal.clone(),
+            self.saved_formatted.clone(),
+            self.remaining_minor.to_string(),
+            self.remaining_decimal.clone(),
+            self.remaining_formatted.clone(),
+            format!("{:.1}", self.percent),
+            self.deadline.clone().unwrap_or_default(),
+            self.required_monthly_minor
+                .map(|minor| minor.to_string())
+                .unwrap_or_default(),
+            self.required_monthly_decimal.clone().unwrap_or_default(),
+            self.required_monthly_formatted.clone().unwrap_or_default(),
+            self.pace_minor.to_string(),
+            self.pace_decimal.clone(),
+            self.pace_formatted.clone(),
+            self.projected.clone().unwrap_or_default(),
+            self.status.unwrap_or_default().to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct TagRecord {
+    tag: String,
+    count: usize,
+    /// Mata uang laporan; semua jumlah sudah dikonversi.
+    currency: Currency,
+    income_minor: i64,
+    income_decimal: String,
+    income_formatted: String,
+    expense_minor: i64,
+    expense_decimal: String,
+    expense_formatted: String,
+    net_minor: i64,
+    net_decimal: String,
+    net_formatted: String,
+}
+
+impl TagRecord {
+    pub(super) fn new(
+        total: &TagTotal,
+        currency: Currency,
+        format: &NumberFormat,
+    ) -> Result<Self, WalletError> {
+        let net = total.income.checked_sub(total.expense)?;
+        Ok(Self {
+            tag: total.tag.clone(),
+            count: total.count,
+            currency,
+            income_minor: total.income.minor(),
+            income_decimal: decimal(total.income, currency),
+            income_formatted: format.signed(total.income, currency),
+            expense_minor: total.expense.minor(),
+            expense_decimal: decimal(total.expense, currency),
+            expense_formatted: format.money(-total.expense, currency),
+            net_minor: net.minor(),
+            net_decimal: decimal(net, currency),
+            net_formatted: format.signed(net, currency),
+        })
+    }
+}
+
+impl CsvRecord for TagRecord {
+    const HEADER: &'static [&'static str] = &[
+        "tag",
+        "count",
+        "currency",
+        "income_minor",
+        "income_decimal",
+        "income_formatted",
+        "expense_minor",
+        "expense_decimal",
+        "expense_formatted",
+        "net_minor",
+        "net_decimal",
+        "net_formatted",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.tag.clone(),
+            self.count.to_string(),
+            self.currency.to_string(),
+            self.income_minor.to_string(),
+            self.income_decimal.clone(),
+            self.income_formatted.clone(),
+            self.expense_minor.to_string(),
+            self.expense_decimal.clone(),
+            self.expense_formatted.clone(),
+            self.net_minor.to_string(),
+            self.net_decimal.clone(),
+            self.net_formatted.clone(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct RateRecord {
+    /// Tanggal berlaku dalam format ISO 8601 (YYYY-MM-DD).
+    date: String,
+    from: Currency,
+    to: Currency,
+    /// 1 `from` = `rate` `to`, sebagai angka desimal dengan titik.
+    rate: String,
+}
+
+impl RateRecord {
+    pub(super) fn new(rate: &ExchangeRate) -> Self {
+        Self {
+            date: rate.date.to_string(),
+            from: rate.from,
+            to: rate.to,
+            rate: rate.rate.to_decimal_string(),
+        }
+    }
+}
+
+impl CsvRecord for RateRecord {
+    const HEADER: &'static [&'static str] = &["date", "from", "to", "rate"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.date.clone(),
+            self.from.to_string(),
+            self.to.to_string(),
+            self.rate.clone(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct AuditRecord {
+    /// Nomor urut entri di log, mulai dari 1.
+    seq: usize,
+    /// Waktu perubahan dalam format RFC 3339.
+    at: String,
+    actor: String,
+    action: &'static str,
+    /// ID transaksi yang berubah.
+    transactions: Vec<String>,
+    /// Bagian data lain yang berubah, misalnya "accounts".
+    sections: Vec<String>,
+    /// Nilai sebelum dan sesudah; tidak ikut keluaran CSV.
+    revisions: Vec<RevisionRecord>,
+}
+
+#[derive(Serialize)]
+struct RevisionRecord {
+    id: String,
+    /// Kosong untuk transaksi yang baru ditambahkan.
+    before: Option<TransactionRecord>,
+    /// Kosong untuk transaksi yang dihapus.
+    after: Option<TransactionRecord>,
+}
+
+impl AuditRecord {
+    pub(super) fn new(wallet: &WalletList, seq: usize, entry: &AuditEntry) -> Self {
+        let record = |item: &Option<WalletItems>| {
+            item.as_ref()
+                .map(|item| TransactionRecord::new(wallet, item))
+        };
+        Self {
+            seq,
+            at: entry.at.to_rfc3339(),
+            actor: entry.actor.clone(),
+            action: entry.action.code(),
+            transactions: entry
+                .revisions
+                .iter()
+                .map(|revision| revision.id.to_string())
+                .collect(),
+            sections: entry.sections.clone(),
+            revisions: entry
+                .revisions
+                .iter()
+                .map(|revision| RevisionRecord {
+                    id: revision.id.to_string(),
+                    before: record(&revision.before),
+                    after: record(&revision.after),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CsvRecord for AuditRecord {
+    const HEADER: &'static [&'static str] =
+        &["seq", "at", "actor", "action", "transactions", "sections"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.seq.to_string(),
+            self.at.clone(),
+            self.actor.clone(),
+            self.action.to_string(),
+            self.transactions.join(" "),
+            self.sections.join(" "),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct EventRecord {
+    seq: u64,
+    /// Waktu peristiwa dalam format RFC 3339.
+    at: String,
+    /// "transaction_added", "transaction_amended" atau "transaction_deleted".
+    event: &'static str,
+    id: String,
+    /// Keadaan transaksi setelah peristiwa; kosong untuk penghapusan. Tidak
+    /// ikut keluaran CSV.
+    transaction: Option<TransactionRecord>,
+}
+
+impl EventRecord {
+    pub(super) fn new(wallet: &WalletList, event: &WalletEvent) -> Self {
+        let transaction = match &event.kind {
+            EventKind::TransactionAdded { item } | EventKind::TransactionAmended { item } => {
+                Some(TransactionRecord::new(wallet, item))
+            }
+            EventKind::TransactionDeleted { .. } => None,
+        };
+        Self {
+            seq: event.seq,
+            at: event.at.to_rfc3339(),
+            event: event.kind.code(),
+            id: event.kind.id().to_string(),
+            transaction,
+        }
+    }
+}
+
+impl CsvRecord for EventRecord {
+    const HEADER: &'static [&'static str] = &["seq", "at", "event", "id", "description"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.seq.to_string(),
+            self.at.clone(),
+            self.event.to_string(),
+            self.id.clone(),
+            self.transaction
+                .as_ref()
+                .map(|t| t.description.clone())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+impl CsvRecord for NumberFormat {
+    const HEADER: &'static [&'static str] = &["locale", "symbol", "negative", "compact"];
+
+    fn fields(&self) -> Vec<String> {
+        let name = |value: Option<clap::builder::PossibleValue>| {
+            value.map(|v| v.get_name().to_string()).unwrap_or_default()
+        };
+        vec![
+            name(self.locale.to_possible_value()),
+            name(self.symbol.to_possible_value()),
+            name(self.negative.to_possible_value()),
+            self.compact.to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_escape("Makan siang"), "Makan siang");
+        assert_eq!(csv_escape("Kopi, roti"), "\"Kopi, roti\"");
+        assert_eq!(csv_escape("Toko \"Maju\""), "\"Toko \"\"Maju\"\"\"");
+    }
+
+    #[test]
+    fn transaction_record_has_raw_amounts() {
+        let mut wallet = WalletList::new();
+        let item = WalletItems::new(
+            "Kopi, roti".to_string(),
+            Money::from_major(25_000, Currency::IDR).unwrap(),
+            TypeTransaction::Income,
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            None,
+            vec!["kantor".to_string()],
+            1,
+        );
+        let id = wallet.add(item, false).unwrap();
+        let record = TransactionRecord::new(&wallet, wallet.get(id).unwrap());
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["id"], "T1");
+        assert_eq!(json["date"], "2026-03-01");
+        assert_eq!(json["type"], "income");
+        assert_eq!(json["amount_minor"], 2_500_000);
+        assert_eq!(json["amount_decimal"], "25000.00");
+        assert_eq!(json["currency"], "IDR");
+        assert!(json.get("running_balance_minor").is_none());
+
+        let fields = record.fields();
+        assert_eq!(fields.len(), TransactionRecord::HEADER.len());
+        assert_eq!(fields[3], "2500000");
+        assert_eq!(fields[14], "Kopi, roti");
+    }
+
+    #[test]
+    fn balance_record_reports_status() {
+        let format = NumberFormat::default();
+        let deficit = Money::from_minor(-150);
+        let record = BalanceRecord::new(deficit, Currency::IDR, None, &format);
+        let fields = record.fields();
+        assert_eq!(fields.len(), BalanceRecord::HEADER.len());
+        assert_eq!(fields[..3], ["", "-150", "-1.50"]);
+        assert_eq!(fields[5], "deficit");
+
+        let zero = BalanceRecord::new(Money::ZERO, Currency::IDR, None, &format);
+        assert_eq!(zero.fields()[5], "balanced");
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Months, NaiveDate};
+use clap::ValueEnum;
+
+use super::{DATE_FORMAT, WalletError, i18n::t, money::Money};
+
+/// Panjang satu periode pada laporan periode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub(super) enum Period {
+    #[default]
+    #[value(alias = "bulan", help = t!("help.value.period.month"))]
+    Month,
+    #[value(alias = "kuartal", help = t!("help.value.period.quarter"))]
+    Quarter,
+    #[value(alias = "tahun", help = t!("help.value.period.year"))]
+    Year,
+}
+
+impl Period {
+    /// Tanggal pertama periode yang memuat `date`.
+    pub(super) fn start_of(self, date: NaiveDate) -> NaiveDate {
+        let month = match self {
+            Period::Month => date.month(),
+            Period::Quarter => date.month0() / 3 * 3 + 1,
+            Period::Year => 1,
+        };
+        date.with_day(1)
+            .and_then(|date| date.with_month(month))
+            .unwrap_or(date)
+    }
+
+    /// Tanggal pertama periode sesudah periode yang dimulai pada `start`.
+    pub(super) fn next(self, start: NaiveDate) -> Option<NaiveDate> {
+        let months = match self {
+            Period::Month => 1,
+            Period::Quarter => 3,
+            Period::Year => 12,
+        };
+        start.checked_add_months(Months::new(months))
+    }
+
+    /// Tanggal terakhir periode yang dimulai pada `start`.
+    pub(super) fn end_of(self, start: NaiveDate) -> NaiveDate {
+        self.next(start)
+            .and_then(|next| next.pred_opt())
+            .unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Nama periode yang sama di semua bahasa, misalnya "2026-10",
+    /// "2026-Q4" atau "2026".
+    pub(super) fn label(self, start: NaiveDate) -> String {
+        match self {
+            Period::Month => format!("{}-{:02}", start.year(), start.month()),
+            Period::Quarter => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+            Period::Year => start.year().to_string(),
+        }
+    }
+}
+
+/// Membaca bulan MM/YYYY, misalnya "10/2026", menjadi tanggal pertamanya.
+pub(super) fn parse_month(input: &str) -> Result<NaiveDate, WalletError> {
+    let input = input.trim();
+    NaiveDate::parse_from_str(&format!("01/{}", input), DATE_FORMAT)
+        .map_err(|_| WalletError::InvalidInput(t!("error.month", month = input)))
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Month => f.pad(&t!("period.month")),
+            Period::Quarter => f.pad(&t!("period.quarter")),
+            Period::Year => f.pad(&t!("period.year")),
+        }
+    }
+}
+
+/// Rentang tanggal laporan, kedua ujungnya ikut dihitung. Ujung yang
+/// kosong berarti tanpa batas.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct DateRange {
+    pub(super) from: Option<NaiveDate>,
+    pub(super) to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub(super) fn new(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Self, WalletError> {
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return Err(WalletError::InvalidInput(t!(
+                "error.date_range",
+                from = from.format(DATE_FORMAT),
+                to = to.format(DATE_FORMAT)
+            )));
+        }
+        Ok(Self { from, to })
+    }
+
+    pub(super) fn contains(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+    }
+
+    pub(super) fn describe(&self) -> String {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => t!(
+                "range.between",
+                from = from.format(DATE_FORMAT),
+                to = to.format(DATE_FORMAT)
+            ),
+            (Some(from), None) => t!("range.since", from = from.format(DATE_FORMAT)),
+            (None, Some(to)) => t!("range.until", to = to.format(DATE_FORMAT)),
+            (None, None) => t!("range.all"),
+        }
+    }
+}
+
+/// Total pemasukan dan pengeluaran satu periode dalam mata uang laporan.
+pub(super) struct PeriodTotal {
+    pub(super) start: NaiveDate,
+    pub(super) income: Money,
+    pub(super) expense: Money,
+}
+
+impl PeriodTotal {
+    pub(super) fn net(&self) -> Result<Money, WalletError> {
+        self.income.checked_sub(self.expense)
+    }
+
+    /// Bagian pemasukan yang tidak dibelanjakan, dalam persen. `None` jika
+    /// periode ini tidak memiliki pemasukan.
+    pub(super) fn savings_rate(&self) -> Result<Option<f64>, WalletError> {
+        let net = self.net()?;
+        Ok(self
+            .income
+            .is_positive()
+            .then(|| net.minor() as f64 / self.income.minor() as f64 * 100.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn periods_start_end_and_label() {
+        let day = date(2026, 8, 17);
+        let cases = [
+            (
+                Period::Month,
+                date(2026, 8, 1),
+                date(2026, 8, 31),
+                "2026-08",
+            ),
+            (
+                Period::Quarter,
+                date(2026, 7, 1),
+                date(2026, 9, 30),
+                "2026-Q3",
+            ),
+            (Period::Year, date(2026, 1, 1), date(2026, 12, 31), "2026"),
+        ];
+        for (period, start, end, label) in cases {
+            assert_eq!(period.start_of(day), start);
+            assert_eq!(period.end_of(start), end);
+            assert_eq!(period.label(start), label);
+        }
+        assert_eq!(Period::Month.end_of(date(2028, 2, 1)), date(2028, 2, 29));
+        assert_eq!(
+            Period::Quarter.next(date(2026, 10, 1)),
+            Some(date(2027, 1, 1))
+        );
+    }
+
+    #[test]
+    fn months_and_ranges_are_validated() {
+        assert_eq!(parse_month("10/2026").unwrap(), date(2026, 10, 1));
+        assert!(parse_month("13/2026").is_err());
+
+        let range = DateRange::new(Some(date(2026, 3, 1)), Some(date(2026, 3, 31))).unwrap();
+        assert!(range.contains(date(2026, 3, 1)));
+        assert!(range.contains(date(2026, 3, 31)));
+        assert!(!range.contains(date(2026, 4, 1)));
+        assert!(DateRange::default().contains(date(1999, 1, 1)));
+        assert!(DateRange::new(Some(date(2026, 4, 1)), Some(date(2026, 3, 1))).is_err());
+    }
+
+    #[test]
+    fn savings_rate_needs_income() {
+        let total = |income, expense| PeriodTotal {
+            start: date(2026, 3, 1),
+            income: Money::from_minor(income),
+            expense: Money::from_minor(expense),
+        };
+        assert_eq!(total(1_000, 750).savings_rate().unwrap(), Some(25.0));
+        assert_eq!(total(1_000, 1_500).net().unwrap(), Money::from_minor(-500));
+        assert_eq!(total(0, 500).savings_rate().unwrap(), None);
+    }
+}
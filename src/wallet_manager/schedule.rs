@@ -0,0 +1,420 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{Datelike, Days, Local, Months, NaiveDate, Weekday};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{DATE_FORMAT, WalletError, WalletItems, i18n::t};
+
+/// Seberapa sering transaksi berulang terjadi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Frequency {
+    #[value(alias = "harian", help = t!("help.value.frequency.daily"))]
+    Daily,
+    #[value(alias = "mingguan", help = t!("help.value.frequency.weekly"))]
+    Weekly,
+    #[value(alias = "bulanan", help = t!("help.value.frequency.monthly"))]
+    Monthly,
+    #[value(alias = "tahunan", help = t!("help.value.frequency.yearly"))]
+    Yearly,
+}
+
+impl Frequency {
+    /// Kode stabil untuk keluaran mesin.
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+            Frequency::Yearly => "yearly",
+        }
+    }
+}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Frequency::Daily => f.pad(&t!("frequency.daily")),
+            Frequency::Weekly => f.pad(&t!("frequency.weekly")),
+            Frequency::Monthly => f.pad(&t!("frequency.monthly")),
+            Frequency::Yearly => f.pad(&t!("frequency.yearly")),
+        }
+    }
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// Aturan pengulangan. Kejadian ke-n dihitung dari `start`, bukan dari
+/// kejadian sebelumnya, sehingga tanggal 31 yang digeser ke 28 Februari
+/// kembali ke 31 Maret.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Recurrence {
+    pub(super) frequency: Frequency,
+    /// Setiap berapa hari, minggu, bulan atau tahun.
+    #[serde(default = "one")]
+    pub(super) interval: u32,
+    pub(super) start: NaiveDate,
+    /// Tanggal terakhir yang masih boleh terjadi, ikut dihitung.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) end: Option<NaiveDate>,
+    /// Jumlah kejadian paling banyak.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) count: Option<u32>,
+    /// Hanya untuk bulanan dan tahunan: kejadian jatuh pada hari kerja
+    /// (Senin-Jumat) terakhir di bulan itu.
+    #[serde(default)]
+    pub(super) last_business_day: bool,
+}
+
+impl Recurrence {
+    pub(super) fn new(
+        frequency: Frequency,
+        interval: u32,
+        start: NaiveDate,
+        end: Option<NaiveDate>,
+        count: Option<u32>,
+        last_business_day: bool,
+    ) -> Result<Self, WalletError> {
+        if interval == 0 {
+            return Err(WalletError::InvalidInput(t!("error.recurring_interval")));
+        }
+        if count == Some(0) {
+            return Err(WalletError::InvalidInput(t!("error.recurring_count")));
+        }
+        if end.is_some_and(|end| end < start) {
+            return Err(WalletError::InvalidInput(t!("error.recurring_end")));
+        }
+        if last_business_day && matches!(frequency, Frequency::Daily | Frequency::Weekly) {
+            return Err(WalletError::InvalidInput(t!(
+                "error.recurring_business_day"
+            )));
+        }
+        Ok(Self {
+            frequency,
+            interval,
+            start,
+            end,
+            count,
+            last_business_day,
+        })
+    }
+
+    /// Tanggal kejadian ke-`n` (mulai dari 0), atau `None` jika aturan sudah
+    /// selesai sebelum kejadian itu.
+    pub(super) fn occurrence(&self, n: u32) -> Option<NaiveDate> {
+        if self.count.is_some_and(|count| n >= count) {
+            return None;
+        }
+        // Hari kerja terakhir bulan pertama bisa jatuh sebelum `start`
+        // (mulai Sabtu 31 Januari, hari kerjanya Jumat 30 Januari); kejadian
+        // itu dilewati sehingga yang pertama jatuh di bulan berikutnya.
+        let n = if self.scheduled(0)? < self.start {
+            n.checked_add(1)?
+        } else {
+            n
+        };
+        let date = self.scheduled(n)?;
+        if self.end.is_some_and(|end| date > end) {
+            return None;
+        }
+        Some(date)
+    }
+
+    /// Tanggal ke-`n` menurut frekuensi dan intervalnya, tanpa batas
+    /// `count` dan `end`.
+    fn scheduled(&self, n: u32) -> Option<NaiveDate> {
+        let step = n.checked_mul(self.interval)?;
+        let date = match self.frequency {
+            Frequency::Daily => self.start.checked_add_days(Days::new(step.into()))?,
+            Frequency::Weekly => self
+                .start
+                .checked_add_days(Days::new(u64::from(step) * 7))?,
+            Frequency::Monthly => self.start.checked_add_months(Months::new(step))?,
+            Frequency::Yearly => self
+                .start
+                .checked_add_months(Months::new(step.checked_mul(12)?))?,
+        };
+        Some(if self.last_business_day {
+            last_business_day(date)
+        } else {
+            date
+        })
+    }
+
+    /// Uraian singkat, misalnya "Bulanan, hari kerja terakhir, 12 kali".
+    pub(super) fn describe(&self) -> String {
+        let interval = self.interval;
+        let mut parts = vec![match self.frequency {
+            frequency if interval == 1 => frequency.to_string(),
+            Frequency::Daily => t!("recurring.every_days", interval = interval),
+            Frequency::Weekly => t!("recurring.every_weeks", interval = interval),
+            Frequency::Monthly => t!("recurring.every_months", interval = interval),
+            Frequency::Yearly => t!("recurring.every_years", interval = interval),
+        }];
+        if self.last_business_day {
+            parts.push(t!("recurring.last_business_day"));
+        }
+        if let Some(end) = self.end {
+            parts.push(t!("recurring.until", date = end.format(DATE_FORMAT)));
+        }
+        if let Some(count) = self.count {
+            parts.push(t!("recurring.times", count = count));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Hari Senin-Jumat terakhir pada bulan yang memuat `date`. Hari libur
+/// nasional tidak diperhitungkan.
+fn last_business_day(date: NaiveDate) -> NaiveDate {
+    let mut day = date
+        .with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date);
+    while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+        day = day.pred_opt().unwrap_or(day);
+    }
+    day
+}
+
+/// ID transaksi berulang, ditampilkan dengan awalan `R`, misalnya `R3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct ScheduleId(u32);
+
+impl Display for ScheduleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("R{}", self.0))
+    }
+}
+
+impl FromStr for ScheduleId {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || WalletError::InvalidInput(t!("error.recurring_id", id = s));
+        let number = s
+            .trim()
+            .strip_prefix(['R', 'r'])
+            .ok_or_else(invalid)?
+            .parse::<u32>()
+            .map_err(|_| invalid())?;
+        if number == 0 {
+            return Err(invalid());
+        }
+        Ok(ScheduleId(number))
+    }
+}
+
+/// Transaksi berulang: contoh transaksi yang disalin pada setiap kejadian.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Schedule {
+    pub(super) id: ScheduleId,
+    /// Contoh transaksi; tanggal dan ID-nya diabaikan.
+    pub(super) template: WalletItems,
+    pub(super) rule: Recurrence,
+    /// Jumlah kejadian yang sudah dicatat atau dilewati.
+    #[serde(default)]
+    pub(super) done: u32,
+}
+
+impl Schedule {
+    /// Tanggal kejadian berikutnya yang belum diproses.
+    pub(super) fn next_date(&self) -> Option<NaiveDate> {
+        self.rule.occurrence(self.done)
+    }
+
+    /// Tanggal kejadian yang belum diproses sampai `until`, ikut dihitung.
+    pub(super) fn dates_until(&self, until: NaiveDate) -> impl Iterator<Item = NaiveDate> + '_ {
+        (self.done..)
+            .map_while(|n| self.rule.occurrence(n))
+            .take_while(move |date| *date <= until)
+    }
+
+    /// Transaksi baru untuk kejadian pada `date`.
+    pub(super) fn transaction(&self, date: NaiveDate) -> WalletItems {
+        let now = Local::now();
+        WalletItems {
+            date,
+            created_at: now,
+            modified_at: now,
+            fingerprints: Vec::new(),
+            ..self.template.clone()
+        }
+    }
+}
+
+/// Daftar transaksi berulang.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct ScheduleList {
+    schedules: Vec<Schedule>,
+}
+
+impl ScheduleList {
+    pub(super) fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Schedule> {
+        self.schedules.iter()
+    }
+
+    pub(super) fn get(&self, id: ScheduleId) -> Result<&Schedule, WalletError> {
+        self.schedules
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.recurring_not_found", id = id)))
+    }
+
+    pub(super) fn get_mut(&mut self, id: ScheduleId) -> Result<&mut Schedule, WalletError> {
+        self.schedules
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.recurring_not_found", id = id)))
+    }
+
+    /// Menambah transaksi berulang dengan ID dari penghitung `next_id`, agar
+    /// ID yang sudah dihapus tidak pernah dipakai ulang.
+    pub(super) fn add(
+        &mut self,
+        next_id: &mut u32,
+        template: WalletItems,
+        rule: Recurrence,
+    ) -> ScheduleId {
+        let max_id = self.schedules.iter().map(|s| s.id.0).max().unwrap_or(0);
+        let id = ScheduleId((*next_id).max(max_id + 1));
+        *next_id = id.0 + 1;
+        self.schedules.push(Schedule {
+            id,
+            template,
+            rule,
+            done: 0,
+        });
+        id
+    }
+
+    pub(super) fn remove(&mut self, id: ScheduleId) -> Result<Schedule, WalletError> {
+        let position = self
+            .schedules
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or_else(|| WalletError::InvalidInput(t!("error.recurring_not_found", id = id)))?;
+        Ok(self.schedules.remove(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet_manager::{TypeTransaction, money::Money};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn rule(frequency: Frequency, start: NaiveDate) -> Recurrence {
+        Recurrence::new(frequency, 1, start, None, None, false).unwrap()
+    }
+
+    fn dates(rule: &Recurrence, n: u32) -> Vec<NaiveDate> {
+        (0..n).map_while(|n| rule.occurrence(n)).collect()
+    }
+
+    #[test]
+    fn monthly_clamps_to_month_end_and_returns() {
+        let monthly = rule(Frequency::Monthly, date(2027, 1, 31));
+        assert_eq!(
+            dates(&monthly, 4),
+            [
+                date(2027, 1, 31),
+                date(2027, 2, 28),
+                date(2027, 3, 31),
+                date(2027, 4, 30)
+            ]
+        );
+        let yearly = rule(Frequency::Yearly, date(2024, 2, 29));
+        assert_eq!(yearly.occurrence(1), Some(date(2025, 2, 28)));
+        assert_eq!(yearly.occurrence(4), Some(date(2028, 2, 29)));
+    }
+
+    #[test]
+    fn last_business_day_never_falls_before_start() {
+        // Sabtu 31 Januari 2026: hari kerja terakhirnya Jumat 30 Januari.
+        let rule =
+            Recurrence::new(Frequency::Monthly, 1, date(2026, 1, 31), None, None, true).unwrap();
+        assert_eq!(dates(&rule, 2), [date(2026, 2, 27), date(2026, 3, 31)]);
+
+        let rule =
+            Recurrence::new(Frequency::Monthly, 1, date(2027, 1, 15), None, None, true).unwrap();
+        assert_eq!(rule.occurrence(0), Some(date(2027, 1, 29)));
+    }
+
+    #[test]
+    fn count_and_end_limit_occurrences() {
+        let counted =
+            Recurrence::new(Frequency::Weekly, 2, date(2026, 3, 2), None, Some(3), false).unwrap();
+        assert_eq!(
+            dates(&counted, 10),
+            [date(2026, 3, 2), date(2026, 3, 16), date(2026, 3, 30)]
+        );
+
+        let ended = Recurrence::new(
+            Frequency::Daily,
+            1,
+            date(2026, 3, 1),
+            Some(date(2026, 3, 3)),
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(dates(&ended, 10).last(), Some(&date(2026, 3, 3)));
+        assert_eq!(dates(&ended, 10).len(), 3);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let start = date(2026, 3, 1);
+        assert!(Recurrence::new(Frequency::Daily, 0, start, None, None, false).is_err());
+        assert!(Recurrence::new(Frequency::Daily, 1, start, None, Some(0), false).is_err());
+        assert!(
+            Recurrence::new(
+                Frequency::Daily,
+                1,
+                start,
+                Some(date(2026, 2, 1)),
+                None,
+                false
+            )
+            .is_err()
+        );
+        assert!(Recurrence::new(Frequency::Weekly, 1, start, None, None, true).is_err());
+    }
+
+    #[test]
+    fn removed_ids_are_not_reused() {
+        let template = WalletItems::new(
+            "Internet".to_string(),
+            Money::from_minor(35_000_000),
+            TypeTransaction::Expense,
+            date(2026, 3, 1),
+            None,
+            Vec::new(),
+            1,
+        );
+        let mut list = ScheduleList::default();
+        let mut next_id = 0;
+        let mut add = |list: &mut ScheduleList| {
+            let rule = rule(Frequency::Monthly, date(2026, 3, 1));
+            list.add(&mut next_id, template.clone(), rule)
+        };
+        assert_eq!(add(&mut list), ScheduleId(1));
+        assert_eq!(add(&mut list), ScheduleId(2));
+        list.remove(ScheduleId(2)).unwrap();
+        assert_eq!(add(&mut list), ScheduleId(3));
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    super::{
+        WalletError,
+        audit::{self, AuditEntry},
+        events::{self, Snapshot, WalletEvent},
+        i18n::t,
+    },
+    LogEntries, Storage, WalletData,
+};
+
+/// Versi format file data. Naikkan setiap kali struktur `WalletFile` berubah.
+const FILE_VERSION: u32 = 11;
+
+#[derive(Deserialize)]
+struct WalletFile {
+    version: u32,
+    #[serde(default)]
+    logs: Option<LogPositions>,
+    #[serde(flatten)]
+    data: WalletData,
+}
+
+#[derive(Serialize)]
+struct WalletFileRef<'a> {
+    version: u32,
+    logs: LogPositions,
+    #[serde(flatten)]
+    data: &'a WalletData,
+}
+
+/// Posisi di sebuah log: jumlah byte dan baris sebelumnya.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct LogPosition {
+    bytes: u64,
+    lines: usize,
+}
+
+impl LogPosition {
+    /// Untuk membaca log sampai akhir file.
+    const END: LogPosition = LogPosition {
+        bytes: u64::MAX,
+        lines: usize::MAX,
+    };
+}
+
+/// Akhir setiap log saat file data terakhir disimpan. Baris sesudahnya
+/// berasal dari penyimpanan yang gagal sebelum file data diganti; baris itu
+/// diabaikan saat membaca dan ditimpa saat menyimpan.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct LogPositions {
+    audit: LogPosition,
+    events: LogPosition,
+    snapshots: LogPosition,
+    /// Snapshot terbaru, agar memuat transaksi cukup membaca snapshot itu
+    /// dan peristiwa sesudahnya.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latest_snapshot: Option<SnapshotPosition>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SnapshotPosition {
+    seq: u64,
+    /// Awal baris snapshot di log snapshot.
+    snapshot: LogPosition,
+    /// Awal peristiwa sesudah `seq` di log peristiwa.
+    events: LogPosition,
+}
+
+/// Bagian file data yang dibutuhkan untuk membaca log.
+#[derive(Deserialize)]
+struct WalletFileHeader {
+    #[serde(default)]
+    logs: Option<LogPositions>,
+    #[serde(default)]
+    event_seq: u64,
+}
+
+/// Bagian awal snapshot, untuk memilih snapshot tanpa menyimpan seluruh
+/// transaksinya.
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    at: DateTime<Local>,
+}
+
+/// Penyimpanan berbasis satu file JSON berversi. Log audit, log peristiwa
+/// dan snapshot ditulis di sebelahnya sebagai JSON Lines yang hanya pernah
+/// ditambah di akhir, misalnya `wallet.json` → `wallet.audit.jsonl`,
+/// `wallet.events.jsonl` dan `wallet.snapshots.jsonl`. Transaksi tidak
+/// disimpan di file data; `WalletList` membangunnya dari snapshot terbaru
+/// dan peristiwa sesudahnya.
+pub(in crate::wallet_manager) struct FileStorage {
+    path: PathBuf,
+    /// Posisi log menurut file data, dibaca sekali saat pertama dibutuhkan.
+    committed: Option<LogPositions>,
+}
+
+impl FileStorage {
+    pub(in crate::wallet_manager) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            committed: None,
+        }
+    }
+
+    fn log_path(&self, name: &str) -> PathBuf {
+        self.path.with_extension(format!("{}.jsonl", name))
+    }
+
+    /// Posisi log yang sudah menjadi bagian dari file data.
+    fn committed(&mut self) -> Result<LogPositions, WalletError> {
+        if let Some(positions) = self.committed {
+            return Ok(positions);
+        }
+        let header: Option<WalletFileHeader> = match File::open(&self.path) {
+            Ok(file) => Some(
+                serde_json::from_reader(BufReader::new(file))
+                    .map_err(|e| WalletError::Storage(t!("error.corrupt_file", error = e)))?,
+            ),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(WalletError::Storage(e.to_string())),
+        };
+        let positions = match header {
+            Some(WalletFileHeader {
+                logs: Some(positions),
+                ..
+            }) => positions,
+            Some(WalletFileHeader { event_seq, .. }) => self.legacy_positions(event_seq)?,
+            None => self.legacy_positions(0)?,
+        };
+        self.committed = Some(positions);
+        Ok(positions)
+    }
+
+    /// File data dari versi sebelum `LogPositions` tidak mencatat posisi
+    /// log. Peristiwa dan snapshot sampai `event_seq` dianggap tersimpan;
+    /// log audit seluruhnya, karena tidak bisa dibedakan.
+    fn legacy_positions(&self, event_seq: u64) -> Result<LogPositions, WalletError> {
+        let mut positions = LogPositions::default();
+        self.read_lines(
+            "audit",
+            LogPosition::default(),
+            LogPosition::END,
+            |_, _, end| {
+                positions.audit = end;
+                Ok(())
+            },
+        )?;
+        let mut latest = None;
+        self.read_lines(
+            "snapshots",
+            LogPosition::default(),
+            LogPosition::END,
+            |line, start, end| {
+                let seq = events::parse_snapshot(&line, start.lines + 1)?.seq;
+                if seq <= event_seq {
+                    latest = Some((seq, start));
+                    positions.snapshots = end;
+                }
+                Ok(())
+            },
+        )?;
+        self.read_lines(
+            "events",
+            LogPosition::default(),
+            LogPosition::END,
+            |line, start, end| {
+                let seq = events::parse_event(&line, start.lines + 1)?.seq;
+                if seq <= event_seq {
+                    positions.events = end;
+                }
+                if let Some((snapshot_seq, snapshot)) = latest
+                    && snapshot_seq == seq
+                {
+                    positions.latest_snapshot = Some(SnapshotPosition {
+                        seq,
+                        snapshot,
+                        events: end,
+                    });
+                }
+                Ok(())
+            },
+        )?;
+        Ok(positions)
+    }
+
+    /// Menulis `records` tepat di posisi `committed` log `name` dan
+    /// mengembalikan akhir log yang baru. Sisa dari penyimpanan yang gagal
+    /// ikut tertimpa.
+    fn append<T: Serialize>(
+        &self,
+        name: &str,
+        committed: LogPosition,
+        records: &[T],
+    ) -> io::Result<LogPosition> {
+        if records.is_empty() {
+            return Ok(committed);
+        }
+        let mut lines = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut lines, record)?;
+            lines.push(b'\n');
+        }
+        let path = self.log_path(name);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        if file.metadata()?.len() < committed.bytes {
+            return Err(io::Error::other(t!(
+                "error.log_truncated",
+                file = path.display()
+            )));
+        }
+        file.set_len(committed.bytes)?;
+        file.seek(SeekFrom::Start(committed.bytes))?;
+        file.write_all(&lines)?;
+        file.sync_all()?;
+        Ok(LogPosition {
+            bytes: committed.bytes + lines.len() as u64,
+            lines: committed.lines + records.len(),
+        })
+    }
+
+    /// Memanggil `visit` untuk setiap baris tidak kosong log `name` antara
+    /// posisi `from` dan `to`, beserta posisi awal dan akhir baris itu
+    /// (nomor barisnya `start.lines + 1`). File yang belum ada berarti log
+    /// masih kosong.
+    fn read_lines(
+        &self,
+        name: &str,
+        from: LogPosition,
+        to: LogPosition,
+        mut visit: impl FnMut(String, LogPosition, LogPosition) -> Result<(), WalletError>,
+    ) -> Result<(), WalletError> {
+        let to_storage_error = |e: io::Error| WalletError::Storage(e.to_string());
+        let mut file = match File::open(self.log_path(name)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(to_storage_error(e)),
+        };
+        file.seek(SeekFrom::Start(from.bytes))
+            .map_err(to_storage_error)?;
+        let mut reader = BufReader::new(file.take(to.bytes.saturating_sub(from.bytes)));
+        let mut start = from;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line).map_err(to_storage_error)?;
+            if read == 0 {
+                return Ok(());
+            }
+            let end = LogPosition {
+                bytes: start.bytes + read as u64,
+                lines: start.lines + 1,
+            };
+            if !line.trim().is_empty() {
+                visit(line.trim_end().to_string(), start, end)?;
+            }
+            start = end;
+        }
+    }
+}
+
+impl Storage for FileStorage {
+    /// File yang belum ada dianggap dompet kosong.
+    fn load(&mut self) -> Result<WalletData, WalletError> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(WalletData::default()),
+            Err(e) => return Err(WalletError::Storage(e.to_string())),
+        };
+
+        let mut data: WalletFile = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| WalletError::Storage(t!("error.corrupt_file", error = e)))?;
+
+        if data.version > FILE_VERSION {
+            return Err(WalletError::Storage(t!(
+                "error.file_version",
+                version = data.version,
+                max = FILE_VERSION
+            )));
+        }
+
+        data.data.upgrade(data.version)?;
+        self.committed = Some(match data.logs {
+            Some(positions) => positions,
+            None => self.legacy_positions(data.data.event_seq)?,
+        });
+        Ok(data.data)
+    }
+
+    /// Entri log ditulis lebih dulu, tepat di posisi akhir log yang tercatat
+    /// di file data. Data beserta posisi log yang baru lalu ditulis ke file
+    /// sementara di folder yang sama, di-`fsync`, dan di-rename menimpa file
+    /// lama. Rename itulah yang menyimpan perubahan: jika gagal sebelumnya,
+    /// entri log yang sempat tertulis tidak pernah terbaca dan ditimpa oleh
+    /// penyimpanan berikutnya.
+    fn save(&mut self, data: &WalletData, log: &LogEntries) -> Result<(), WalletError> {
+        let committed = self.committed()?;
+        let tmp_path = temp_path(&self.path);
+        let write = || -> io::Result<LogPositions> {
+            let events = self.append("events", committed.events, &log.events)?;
+            // Snapshot selalu dibuat tepat setelah peristiwa terakhir di
+            // `log.events`, jadi peristiwa sesudahnya mulai di `events`.
+            let latest_snapshot = match &log.snapshot {
+                Some(snapshot) => Some(SnapshotPosition {
+                    seq: snapshot.seq,
+                    snapshot: committed.snapshots,
+                    events,
+                }),
+                None => committed.latest_snapshot,
+            };
+            let logs = LogPositions {
+                audit: self.append("audit", committed.audit, log.audit.as_slice())?,
+                events,
+                snapshots: self.append(
+                    "snapshots",
+                    committed.snapshots,
+                    log.snapshot.as_slice(),
+                )?,
+                latest_snapshot,
+            };
+            let data = WalletFileRef {
+                version: FILE_VERSION,
+                logs,
+                data,
+            };
+            let file = File::create(&tmp_path)?;
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, &data)?;
+            writer.write_all(b"\n")?;
+            writer
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .sync_all()?;
+            fs::rename(&tmp_path, &self.path)?;
+            Ok(logs)
+        };
+
+        match write() {
+            Ok(logs) => {
+                self.committed = Some(logs);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_path);
+                Err(WalletError::Storage(e.to_string()))
+            }
+        }
+    }
+
+    fn audit_log(&mut self) -> Result<Vec<AuditEntry>, WalletError> {
+        let committed = self.committed()?;
+        let mut entries = Vec::new();
+        self.read_lines(
+            "audit",
+            LogPosition::default(),
+            committed.audit,
+            |line, start, _| {
+                entries.push(audit::parse_line(&line, start.lines + 1)?);
+                Ok(())
+            },
+        )?;
+        Ok(entries)
+    }
+
+    /// Mulai membaca dari snapshot terbaru jika `seq` tidak lebih awal
+    /// darinya, sehingga memuat data tidak membaca seluruh log.
+    fn events_after(&mut self, seq: u64) -> Result<Vec<WalletEvent>, WalletError> {
+        let committed = self.committed()?;
+        let from = committed
+            .latest_snapshot
+            .filter(|snapshot| snapshot.seq <= seq)
+            .map_or(LogPosition::default(), |snapshot| snapshot.events);
+        let mut events = Vec::new();
+        self.read_lines("events", from, committed.events, |line, start, _| {
+            let event = events::parse_event(&line, start.lines + 1)?;
+            if event.seq > seq {
+                events.push(event);
+            }
+            Ok(())
+        })?;
+        Ok(events)
+    }
+
+    fn snapshot(
+        &mut self,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Option<Snapshot>, WalletError> {
+        let committed = self.committed()?;
+        let from = match (until, committed.latest_snapshot) {
+            (None, Some(latest)) => latest.snapshot,
+            _ => LogPosition::default(),
+        };
+        let mut latest = None;
+        self.read_lines("snapshots", from, committed.snapshots, |line, start, _| {
+            let header: SnapshotHeader = serde_json::from_str(&line).map_err(|e| {
+                WalletError::Storage(t!(
+                    "error.corrupt_snapshot",
+                    line = start.lines + 1,
+                    error = e
+                ))
+            })?;
+            if until.is_none_or(|until| header.at <= until) {
+                latest = Some((line, start.lines + 1));
+            }
+            Ok(())
+        })?;
+        latest
+            .map(|(line, number)| events::parse_snapshot(&line, number))
+            .transpose()
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::{
+        super::super::{
+            TransactionId,
+            account::{AccountKind, AccountList},
+            events::EventKind,
+            money::Currency,
+        },
+        *,
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("wallet-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn deleted(seq: u64) -> WalletEvent {
+        WalletEvent {
+            seq,
+            at: Local::now(),
+            kind: EventKind::TransactionDeleted {
+                id: TransactionId(seq),
+            },
+        }
+    }
+
+    fn save_event(storage: &mut FileStorage, seq: u64) -> Result<(), WalletError> {
+        let data = WalletData {
+            event_seq: seq,
+            ..WalletData::default()
+        };
+        let log = LogEntries {
+            events: vec![deleted(seq)],
+            ..LogEntries::default()
+        };
+        storage.save(&data, &log)
+    }
+
+    fn event_seqs(path: &Path) -> Vec<u64> {
+        let mut storage = FileStorage::new(path.to_path_buf());
+        storage.load().unwrap();
+        let events = storage.events_after(0).unwrap();
+        events.iter().map(|event| event.seq).collect()
+    }
+
+    #[test]
+    fn failed_save_leaves_no_events_behind() {
+        let dir = temp_dir("failed-save");
+        let path = dir.join("wallet.json");
+        let mut storage = FileStorage::new(path.clone());
+        storage.load().unwrap();
+        save_event(&mut storage, 1).unwrap();
+
+        // File sementara tidak bisa dibuat, setelah log peristiwa ditulis.
+        fs::create_dir(temp_path(&path)).unwrap();
+        assert!(save_event(&mut storage, 2).is_err());
+        assert_eq!(event_seqs(&path), [1]);
+
+        fs::remove_dir(temp_path(&path)).unwrap();
+        save_event(&mut storage, 2).unwrap();
+        assert_eq!(event_seqs(&path), [1, 2]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn loading_reads_events_from_the_latest_snapshot() {
+        let dir = temp_dir("snapshot-position");
+        let path = dir.join("wallet.json");
+        let mut storage = FileStorage::new(path.clone());
+        storage.load().unwrap();
+        let data = WalletData {
+            event_seq: 3,
+            ..WalletData::default()
+        };
+        let log = LogEntries {
+            events: (1..=3).map(deleted).collect(),
+            snapshot: Some(Snapshot {
+                seq: 3,
+                at: Local::now(),
+                items: Vec::new(),
+            }),
+            ..LogEntries::default()
+        };
+        storage.save(&data, &log).unwrap();
+        save_event(&mut storage, 4).unwrap();
+
+        // Peristiwa sebelum snapshot tidak dibaca lagi saat memuat data.
+        let events_path = dir.join("wallet.events.jsonl");
+        let lines = fs::read_to_string(&events_path).unwrap();
+        let first = lines.find('\n').unwrap();
+        let broken = "x".repeat(first) + &lines[first..];
+        fs::write(&events_path, broken).unwrap();
+
+        let mut storage = FileStorage::new(path);
+        storage.load().unwrap();
+        assert_eq!(storage.snapshot(None).unwrap().unwrap().seq, 3);
+        let events = storage.events_after(3).unwrap();
+        assert_eq!(events.iter().map(|e| e.seq).collect::<Vec<_>>(), [4]);
+        assert!(storage.events_after(0).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_file_ignores_events_after_event_seq() {
+        let dir = temp_dir("legacy-events");
+        let path = dir.join("wallet.json");
+        fs::write(&path, r#"{"version": 10, "items": [], "event_seq": 1}"#).unwrap();
+        let lines: Vec<String> = [deleted(1), deleted(2)]
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap() + "\n")
+            .collect();
+        fs::write(dir.join("wallet.events.jsonl"), lines.concat()).unwrap();
+
+        assert_eq!(event_seqs(&path), [1]);
+        let mut storage = FileStorage::new(path.clone());
+        storage.load().unwrap();
+        save_event(&mut storage, 2).unwrap();
+        assert_eq!(event_seqs(&path), [1, 2]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file_loads_an_empty_wallet() {
+        let dir = temp_dir("missing-file");
+        let path = dir.join("wallet.json");
+        let data = FileStorage::new(path.clone()).load().unwrap();
+        assert!(data.items.is_empty());
+        assert_eq!(data.next_id, 0);
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn saved_data_loads_back_in_the_next_session() {
+        let dir = temp_dir("round-trip");
+        let path = dir.join("wallet.json");
+        let mut storage = FileStorage::new(path.clone());
+        let mut data = storage.load().unwrap();
+        data.next_id = 8;
+        data.reporting_currency = "USD".parse().unwrap();
+        data.accounts = AccountList::with_defaults();
+        let bank = data
+            .accounts
+            .add("Bank".to_string(), AccountKind::Asset, Currency::IDR)
+            .unwrap();
+        storage.save(&data, &LogEntries::default()).unwrap();
+
+        let loaded = FileStorage::new(path.clone()).load().unwrap();
+        assert_eq!(loaded.next_id, 8);
+        assert_eq!(loaded.reporting_currency.code(), "USD");
+        assert_eq!(loaded.accounts.len(), 2);
+        assert_eq!(loaded.accounts.find("bank").unwrap(), bank);
+        assert!(!temp_path(&path).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn file_from_a_newer_version_is_rejected() {
+        let dir = temp_dir("newer-version");
+        let path = dir.join("wallet.json");
+        let version = FILE_VERSION + 1;
+        fs::write(&path, format!(r#"{{"version": {}, "items": []}}"#, version)).unwrap();
+        let result = FileStorage::new(path).load();
+        assert!(matches!(result, Err(WalletError::Storage(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
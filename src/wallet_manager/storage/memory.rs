@@ -0,0 +1,57 @@
+use chrono::{DateTime, Local};
+
+use super::{
+    super::{
+        WalletError,
+        audit::AuditEntry,
+        events::{Snapshot, WalletEvent},
+    },
+    LogEntries, Storage, WalletData,
+};
+
+/// Penyimpanan di memori saja; data hilang saat aplikasi ditutup.
+#[derive(Default)]
+pub(in crate::wallet_manager) struct MemoryStorage {
+    data: WalletData,
+    audit: Vec<AuditEntry>,
+    events: Vec<WalletEvent>,
+    snapshots: Vec<Snapshot>,
+}
+
+impl Storage for MemoryStorage {
+    fn load(&mut self) -> Result<WalletData, WalletError> {
+        Ok(self.data.clone())
+    }
+
+    fn save(&mut self, data: &WalletData, log: &LogEntries) -> Result<(), WalletError> {
+        self.data = data.clone();
+        self.audit.extend(log.audit.clone());
+        self.events.extend(log.events.iter().cloned());
+        self.snapshots.extend(log.snapshot.clone());
+        Ok(())
+    }
+
+    fn audit_log(&mut self) -> Result<Vec<AuditEntry>, WalletError> {
+        Ok(self.audit.clone())
+    }
+
+    fn events_after(&mut self, seq: u64) -> Result<Vec<WalletEvent>, WalletError> {
+        Ok(self
+            .events
+            .iter()
+            .filter(|event| event.seq > seq)
+            .cloned()
+            .collect())
+    }
+
+    fn snapshot(
+        &mut self,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Option<Snapshot>, WalletError> {
+        Ok(self
+            .snapshots
+            .iter()
+            .rfind(|snapshot| until.is_none_or(|until| snapshot.at <= until))
+            .cloned())
+    }
+}
@@ -0,0 +1,287 @@
+mod file;
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    WalletError, WalletItems,
+    account::{AccountList, OverdraftPolicy},
+    audit::AuditEntry,
+    budget::BudgetList,
+    category::CategoryList,
+    events::{Snapshot, WalletEvent},
+    exchange::RateTable,
+    goal::GoalList,
+    history::History,
+    i18n::{Language, t},
+    locale::NumberFormat,
+    money::{Currency, Money},
+    schedule::ScheduleList,
+};
+
+pub(super) use file::FileStorage;
+pub(super) use memory::MemoryStorage;
+#[cfg(feature = "sqlite")]
+pub(super) use sqlite::SqliteStorage;
+
+/// Seluruh isi dompet yang disimpan oleh backend penyimpanan.
+///
+/// Field baru wajib diberi `#[serde(default)]` agar data lama tetap terbaca.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct WalletData {
+    /// Hasil menerapkan log peristiwa, jadi tidak ikut disimpan. Hanya
+    /// dibaca dari data lama yang belum memiliki log peristiwa.
+    #[serde(default, skip_serializing)]
+    pub(super) items: Vec<WalletItems>,
+    #[serde(default)]
+    pub(super) categories: CategoryList,
+    /// ID berikutnya untuk transaksi baru; tidak pernah dipakai ulang.
+    #[serde(default)]
+    pub(super) next_id: u64,
+    #[serde(default)]
+    pub(super) accounts: AccountList,
+    /// Kurs antar mata uang per tanggal.
+    #[serde(default, skip_serializing_if = "RateTable::is_empty")]
+    pub(super) rates: RateTable,
+    /// Mata uang untuk ringkasan dan saldo bersih gabungan.
+    #[serde(default)]
+    pub(super) reporting_currency: Currency,
+    /// Pengaturan tampilan angka dan uang.
+    #[serde(default)]
+    pub(super) format: NumberFormat,
+    /// Bahasa antarmuka pilihan pengguna; kosong berarti bawaan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) language: Option<Language>,
+    /// Anggaran bulanan per kategori.
+    #[serde(default, skip_serializing_if = "BudgetList::is_empty")]
+    pub(super) budgets: BudgetList,
+    /// Transaksi berulang yang dicatat otomatis saat jatuh tempo.
+    #[serde(default, skip_serializing_if = "ScheduleList::is_empty")]
+    pub(super) schedules: ScheduleList,
+    /// ID berikutnya untuk transaksi berulang baru; tidak pernah dipakai ulang.
+    #[serde(default)]
+    pub(super) next_schedule_id: u32,
+    /// Tujuan tabungan.
+    #[serde(default, skip_serializing_if = "GoalList::is_empty")]
+    pub(super) goals: GoalList,
+    /// Perubahan transaksi yang bisa dibatalkan atau diulang.
+    #[serde(default, skip_serializing_if = "History::is_empty")]
+    pub(super) history: History,
+    /// Nomor peristiwa terakhir yang sudah diterapkan ke `items`; 0 berarti
+    /// log peristiwa belum dimulai.
+    #[serde(default)]
+    pub(super) event_seq: u64,
+}
+
+/// Versi data pertama yang menyimpan jumlah dalam sen; versi sebelumnya
+/// menyimpan rupiah utuh.
+const MINOR_UNITS_SINCE: u32 = 9;
+
+impl WalletData {
+    /// Menyesuaikan data yang ditulis oleh versi format `version` yang lebih
+    /// lama agar sesuai dengan struktur saat ini.
+    fn upgrade(&mut self, version: u32) -> Result<(), WalletError> {
+        if version < MINOR_UNITS_SINCE {
+            let to_sen = |amount: &mut Money| -> Result<(), WalletError> {
+                *amount = Money::from_major(amount.minor(), Currency::IDR)?;
+                Ok(())
+            };
+            for item in &mut self.items {
+                to_sen(&mut item.amount)?;
+                for posting in &mut item.postings {
+                    to_sen(&mut posting.amount)?;
+                }
+            }
+            for account in self.accounts.iter_mut() {
+                if let Some(OverdraftPolicy::Allow { limit: Some(limit) }) = &mut account.overdraft
+                {
+                    to_sen(limit)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Entri log hanya-tambah yang disimpan bersama satu perubahan data.
+#[derive(Debug, Default)]
+pub(super) struct LogEntries {
+    pub(super) audit: Option<AuditEntry>,
+    pub(super) events: Vec<WalletEvent>,
+    pub(super) snapshot: Option<Snapshot>,
+}
+
+/// Tempat `WalletList` memuat dan menyimpan datanya.
+pub(super) trait Storage {
+    fn load(&mut self) -> Result<WalletData, WalletError>;
+    /// Menyimpan `data` dan menambahkan `log` ke log audit, log peristiwa
+    /// dan snapshot dalam satu langkah: jika salah satunya gagal, semuanya
+    /// dianggap gagal dan entri yang sempat tertulis tidak pernah terbaca.
+    fn save(&mut self, data: &WalletData, log: &LogEntries) -> Result<(), WalletError>;
+    /// Seluruh log audit, dari entri terlama.
+    fn audit_log(&mut self) -> Result<Vec<AuditEntry>, WalletError>;
+    /// Peristiwa dengan nomor lebih besar dari `seq`, urut nomor.
+    fn events_after(&mut self, seq: u64) -> Result<Vec<WalletEvent>, WalletError>;
+    /// Snapshot terbaru yang dibuat paling lambat `until` (tanpa batas jika
+    /// `None`).
+    fn snapshot(&mut self, until: Option<DateTime<Local>>)
+    -> Result<Option<Snapshot>, WalletError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum StorageKind {
+    Memory,
+    File,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+impl StorageKind {
+    fn parse(value: &str) -> Result<Self, WalletError> {
+        match value.trim().to_lowercase().as_str() {
+            "memory" | "memori" => Ok(StorageKind::Memory),
+            "file" | "json" => Ok(StorageKind::File),
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Ok(StorageKind::Sqlite),
+            other => Err(WalletError::InvalidInput(t!(
+                "error.storage_backend",
+                backend = other
+            ))),
+        }
+    }
+
+    fn default_path(self) -> PathBuf {
+        match self {
+            StorageKind::Memory => PathBuf::new(),
+            StorageKind::File => PathBuf::from("wallet.json"),
+            #[cfg(feature = "sqlite")]
+            StorageKind::Sqlite => PathBuf::from("wallet.db"),
+        }
+    }
+}
+
+/// Pilihan backend penyimpanan.
+///
+/// Dibaca dari variabel lingkungan `WALLET_STORAGE` (`memory`, `file`, atau
+/// `sqlite`; default `file`) dan `WALLET_DATA_FILE` (lokasi file data).
+pub(super) struct StorageConfig {
+    pub(super) kind: StorageKind,
+    pub(super) path: PathBuf,
+}
+
+impl StorageConfig {
+    pub(super) fn from_env() -> Result<Self, WalletError> {
+        let kind = match std::env::var("WALLET_STORAGE") {
+            Ok(value) => StorageKind::parse(&value)?,
+            Err(_) => StorageKind::File,
+        };
+        let path = std::env::var_os("WALLET_DATA_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| kind.default_path());
+
+        Ok(Self { kind, path })
+    }
+
+    pub(super) fn open(&self) -> Result<Box<dyn Storage>, WalletError> {
+        Ok(match self.kind {
+            StorageKind::Memory => Box::new(MemoryStorage::default()),
+            StorageKind::File => Box::new(FileStorage::new(self.path.clone())),
+            #[cfg(feature = "sqlite")]
+            StorageKind::Sqlite => Box::new(SqliteStorage::open(&self.path)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::{
+        super::{TransactionId, events::EventKind},
+        *,
+    };
+
+    fn config(kind: StorageKind, name: &str) -> StorageConfig {
+        let dir = env::temp_dir().join(format!("wallet-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        StorageConfig {
+            kind,
+            path: dir.join(kind.default_path()),
+        }
+    }
+
+    /// Menyimpan lewat `storage`, lalu membaca kembali lewat `reopen`.
+    fn assert_round_trip(storage: &mut dyn Storage, reopen: impl FnOnce() -> Box<dyn Storage>) {
+        storage.load().unwrap();
+        let data = WalletData {
+            next_id: 5,
+            event_seq: 1,
+            ..WalletData::default()
+        };
+        let log = LogEntries {
+            events: vec![WalletEvent {
+                seq: 1,
+                at: Local::now(),
+                kind: EventKind::TransactionDeleted {
+                    id: TransactionId(4),
+                },
+            }],
+            ..LogEntries::default()
+        };
+        storage.save(&data, &log).unwrap();
+
+        let mut storage = reopen();
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.next_id, 5);
+        assert_eq!(loaded.event_seq, 1);
+        let events = storage.events_after(0).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind.id(), TransactionId(4));
+        assert!(storage.events_after(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn backend_names_are_parsed() {
+        assert_eq!(StorageKind::parse("memori").unwrap(), StorageKind::Memory);
+        assert_eq!(StorageKind::parse(" JSON ").unwrap(), StorageKind::File);
+        #[cfg(feature = "sqlite")]
+        assert_eq!(StorageKind::parse("sqlite").unwrap(), StorageKind::Sqlite);
+        assert!(StorageKind::parse("csv").is_err());
+    }
+
+    #[test]
+    fn memory_backend_keeps_saved_data() {
+        let mut storage = MemoryStorage::default();
+        storage.load().unwrap();
+        let data = WalletData {
+            next_id: 5,
+            ..WalletData::default()
+        };
+        storage.save(&data, &LogEntries::default()).unwrap();
+        assert_eq!(storage.load().unwrap().next_id, 5);
+    }
+
+    #[test]
+    fn file_backend_round_trips_through_the_trait() {
+        let config = config(StorageKind::File, "backend-file");
+        let mut storage = config.open().unwrap();
+        assert_round_trip(storage.as_mut(), || config.open().unwrap());
+        fs::remove_dir_all(config.path.parent().unwrap()).unwrap();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_backend_round_trips_through_the_trait() {
+        let config = config(StorageKind::Sqlite, "backend-sqlite");
+        let mut storage = config.open().unwrap();
+        assert_round_trip(storage.as_mut(), || config.open().unwrap());
+        drop(storage);
+        fs::remove_dir_all(config.path.parent().unwrap()).unwrap();
+    }
+}
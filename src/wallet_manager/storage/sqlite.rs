@@ -0,0 +1,265 @@
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::{Map, Value};
+
+use super::{
+    super::{
+        WalletError,
+        audit::{self, AuditEntry},
+        events::{self, Snapshot, WalletEvent},
+        i18n::t,
+    },
+    LogEntries, Storage, WalletData,
+};
+
+/// Versi skema database, disimpan di `PRAGMA user_version`.
+const SCHEMA_VERSION: i64 = 11;
+
+/// Penyimpanan di database SQLite tertanam.
+///
+/// Data di `WalletData` selain transaksi (kategori, dsb.) disimpan per field
+/// di tabel `collections` sebagai JSON dengan format yang sama seperti
+/// `FileStorage`. Log audit, log peristiwa dan snapshot ada di tabel
+/// `audit_log`, `events` dan `snapshots` yang dijaga trigger agar hanya bisa
+/// ditambah; transaksi dibangun dari snapshot terbaru dan peristiwa
+/// sesudahnya. Tabel `transactions` hanya dibaca dari database lama yang
+/// belum memiliki log peristiwa, dan dikosongkan setelah log dimulai.
+pub(in crate::wallet_manager) struct SqliteStorage {
+    conn: Connection,
+    /// Versi skema data yang sedang tersimpan; baru dinaikkan ke
+    /// `SCHEMA_VERSION` saat data pertama kali disimpan ulang.
+    version: i64,
+}
+
+impl SqliteStorage {
+    pub(in crate::wallet_manager) fn open(path: &Path) -> Result<Self, WalletError> {
+        let conn = Connection::open(path).map_err(to_storage_error)?;
+
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(to_storage_error)?;
+        if version > SCHEMA_VERSION {
+            return Err(WalletError::Storage(t!(
+                "error.database_version",
+                version = version,
+                max = SCHEMA_VERSION
+            )));
+        }
+
+        // Database baru langsung memakai skema terkini.
+        let version = if version == 0 {
+            SCHEMA_VERSION
+        } else {
+            version
+        };
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS transactions (
+                position INTEGER PRIMARY KEY,
+                data     TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS collections (
+                name TEXT PRIMARY KEY,
+                data TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS audit_log (
+                seq  INTEGER PRIMARY KEY AUTOINCREMENT,
+                data TEXT NOT NULL
+            );
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_update
+                BEFORE UPDATE ON audit_log
+                BEGIN SELECT RAISE(ABORT, 'audit_log hanya bisa ditambah'); END;
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_delete
+                BEFORE DELETE ON audit_log
+                BEGIN SELECT RAISE(ABORT, 'audit_log hanya bisa ditambah'); END;
+            CREATE TABLE IF NOT EXISTS events (
+                seq  INTEGER PRIMARY KEY,
+                data TEXT NOT NULL
+            );
+            CREATE TRIGGER IF NOT EXISTS events_no_update
+                BEFORE UPDATE ON events
+                BEGIN SELECT RAISE(ABORT, 'events hanya bisa ditambah'); END;
+            CREATE TRIGGER IF NOT EXISTS events_no_delete
+                BEFORE DELETE ON events
+                BEGIN SELECT RAISE(ABORT, 'events hanya bisa ditambah'); END;
+            CREATE TABLE IF NOT EXISTS snapshots (
+                seq  INTEGER PRIMARY KEY,
+                -- Waktu snapshot dalam milidetik Unix, untuk memilih snapshot
+                -- tanpa membaca isinya.
+                at   INTEGER NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE TRIGGER IF NOT EXISTS snapshots_no_update
+                BEFORE UPDATE ON snapshots
+                BEGIN SELECT RAISE(ABORT, 'snapshots hanya bisa ditambah'); END;
+            CREATE TRIGGER IF NOT EXISTS snapshots_no_delete
+                BEFORE DELETE ON snapshots
+                BEGIN SELECT RAISE(ABORT, 'snapshots hanya bisa ditambah'); END;",
+        )
+        .map_err(to_storage_error)?;
+
+        Ok(Self { conn, version })
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load(&mut self) -> Result<WalletData, WalletError> {
+        let mut object = Map::new();
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT data FROM transactions ORDER BY position")
+            .map_err(to_storage_error)?;
+        let items = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(to_storage_error)?
+            .map(|row| parse_json(&row.map_err(to_storage_error)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        object.insert("items".to_string(), Value::Array(items));
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, data FROM collections")
+            .map_err(to_storage_error)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(to_storage_error)?;
+        for row in rows {
+            let (name, data) = row.map_err(to_storage_error)?;
+            object.insert(name, parse_json(&data)?);
+        }
+
+        let mut data: WalletData = serde_json::from_value(Value::Object(object))
+            .map_err(|e| WalletError::Storage(t!("error.corrupt_data", error = e)))?;
+        data.upgrade(self.version as u32)?;
+        Ok(data)
+    }
+
+    /// Isi tabel `collections` diganti dan entri log ditambahkan dalam satu
+    /// transaksi database, sehingga kegagalan di tengah jalan tidak
+    /// meninggalkan data setengah tersimpan.
+    fn save(&mut self, data: &WalletData, log: &LogEntries) -> Result<(), WalletError> {
+        let Value::Object(object) =
+            serde_json::to_value(data).map_err(|e| WalletError::Storage(e.to_string()))?
+        else {
+            unreachable!("WalletData selalu diserialisasi sebagai objek");
+        };
+
+        let tx = self.conn.transaction().map_err(to_storage_error)?;
+
+        if data.event_seq > 0 {
+            tx.execute("DELETE FROM transactions", [])
+                .map_err(to_storage_error)?;
+        }
+        tx.execute("DELETE FROM collections", [])
+            .map_err(to_storage_error)?;
+        {
+            let mut stmt = tx
+                .prepare("INSERT INTO collections (name, data) VALUES (?1, ?2)")
+                .map_err(to_storage_error)?;
+            for (name, value) in &object {
+                stmt.execute(params![name, value.to_string()])
+                    .map_err(to_storage_error)?;
+            }
+        }
+        for event in &log.events {
+            tx.execute(
+                "INSERT INTO events (seq, data) VALUES (?1, ?2)",
+                params![event.seq as i64, to_json(event)?],
+            )
+            .map_err(to_storage_error)?;
+        }
+        if let Some(snapshot) = &log.snapshot {
+            tx.execute(
+                "INSERT INTO snapshots (seq, at, data) VALUES (?1, ?2, ?3)",
+                params![
+                    snapshot.seq as i64,
+                    snapshot.at.timestamp_millis(),
+                    to_json(snapshot)?
+                ],
+            )
+            .map_err(to_storage_error)?;
+        }
+        if let Some(entry) = &log.audit {
+            tx.execute(
+                "INSERT INTO audit_log (data) VALUES (?1)",
+                params![to_json(entry)?],
+            )
+            .map_err(to_storage_error)?;
+        }
+        tx.execute_batch(&format!("PRAGMA user_version = {};", SCHEMA_VERSION))
+            .map_err(to_storage_error)?;
+
+        tx.commit().map_err(to_storage_error)?;
+        self.version = SCHEMA_VERSION;
+        Ok(())
+    }
+
+    fn audit_log(&mut self) -> Result<Vec<AuditEntry>, WalletError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT seq, data FROM audit_log ORDER BY seq")
+            .map_err(to_storage_error)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(to_storage_error)?;
+        rows.map(|row| {
+            let (seq, data) = row.map_err(to_storage_error)?;
+            audit::parse_line(&data, seq as usize)
+        })
+        .collect()
+    }
+
+    fn events_after(&mut self, seq: u64) -> Result<Vec<WalletEvent>, WalletError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT seq, data FROM events WHERE seq > ?1 ORDER BY seq")
+            .map_err(to_storage_error)?;
+        let rows = stmt
+            .query_map(params![seq as i64], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(to_storage_error)?;
+        rows.map(|row| {
+            let (seq, data) = row.map_err(to_storage_error)?;
+            events::parse_event(&data, seq as usize)
+        })
+        .collect()
+    }
+
+    fn snapshot(
+        &mut self,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Option<Snapshot>, WalletError> {
+        let until = until.map_or(i64::MAX, |until| until.timestamp_millis());
+        self.conn
+            .query_row(
+                "SELECT seq, data FROM snapshots WHERE at <= ?1 ORDER BY seq DESC LIMIT 1",
+                params![until],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .map_err(to_storage_error)?
+            .map(|(seq, data)| events::parse_snapshot(&data, seq as usize))
+            .transpose()
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, WalletError> {
+    serde_json::to_string(value).map_err(|e| WalletError::Storage(e.to_string()))
+}
+
+fn parse_json(data: &str) -> Result<Value, WalletError> {
+    serde_json::from_str(data)
+        .map_err(|e| WalletError::Storage(t!("error.corrupt_data", error = e)))
+}
+
+fn to_storage_error(e: rusqlite::Error) -> WalletError {
+    WalletError::Storage(e.to_string())
+}
@@ -0,0 +1,64 @@
+use super::{WalletError, i18n::t, money::Money};
+
+/// Mengubah input seperti "#kantor, #Reimburse liburan-bali" menjadi daftar
+/// tag unik yang sudah dinormalisasi (huruf kecil, tanpa `#`).
+pub(super) fn parse_tags(input: &str) -> Result<Vec<String>, WalletError> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for raw in input.split(|c: char| c.is_whitespace() || c == ',') {
+        let tag = raw.trim_start_matches('#').to_lowercase();
+        if tag.is_empty() {
+            continue;
+        }
+
+        if !tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(WalletError::InvalidInput(t!("error.tag", tag = raw)));
+        }
+
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    Ok(tags)
+}
+
+/// Menampilkan tag dengan awalan `#`, misalnya "#kantor #reimburse".
+pub(super) fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{}", tag))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Total pemasukan dan pengeluaran untuk satu tag.
+pub(super) struct TagTotal {
+    pub(super) tag: String,
+    pub(super) count: usize,
+    pub(super) income: Money,
+    pub(super) expense: Money,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalized_and_unique() {
+        let tags = parse_tags("#Kantor, reimburse  #kantor liburan-bali").unwrap();
+        assert_eq!(tags, ["kantor", "reimburse", "liburan-bali"]);
+        assert_eq!(format_tags(&tags), "#kantor #reimburse #liburan-bali");
+        assert!(parse_tags(" , ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn tags_with_other_characters_are_rejected() {
+        assert!(matches!(
+            parse_tags("kantor #makan!"),
+            Err(WalletError::InvalidInput(_))
+        ));
+    }
+}